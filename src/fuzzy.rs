@@ -0,0 +1,60 @@
+/// Score how well a query matches a candidate as a case-insensitive subsequence
+///
+/// Returns None if the query characters don't all appear in order. Higher scores
+/// are better: matches at the start, on word boundaries and in consecutive runs
+/// are rewarded, gaps and unmatched trailing characters are penalised.
+pub fn score(query: &str, candidate: &str) -> Option<i64> {
+    let query: Vec<char> = query.to_lowercase().chars().collect();
+    let candidate: Vec<char> = candidate.to_lowercase().chars().collect();
+    if query.is_empty() {
+        return Some(0);
+    }
+
+    let mut score = 0i64;
+    let mut query_idx = 0;
+    let mut prev_match: Option<usize> = None;
+
+    for (i, c) in candidate.iter().enumerate() {
+        if query_idx == query.len() {
+            break;
+        }
+        if *c != query[query_idx] {
+            continue;
+        }
+
+        score += 1;
+        if i == 0 {
+            score += 8; // Prefix
+        } else if prev_match == Some(i - 1) {
+            score += 5; // Consecutive
+        } else if !candidate[i - 1].is_alphanumeric() {
+            score += 4; // Start of a word
+        }
+        if let Some(prev) = prev_match {
+            score -= (i - prev - 1).min(3) as i64; // Gap
+        }
+
+        prev_match = Some(i);
+        query_idx += 1;
+    }
+
+    if query_idx < query.len() {
+        return None;
+    }
+
+    // Prefer shorter candidates when everything else is equal
+    let unmatched = candidate.len().saturating_sub(query.len()) as i64;
+    Some(score - unmatched.min(10) / 2)
+}
+
+/// Check whether any word of a longer text starts with the query (case-insensitive)
+///
+/// Used instead of `score` for long free text, where a subsequence almost always matches.
+pub fn word_prefix_match(query: &str, text: &str) -> bool {
+    let query = query.to_lowercase();
+    !query.is_empty()
+        && text
+            .to_lowercase()
+            .split(|c: char| !c.is_alphanumeric())
+            .any(|word| word.starts_with(&query))
+}
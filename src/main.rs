@@ -1,22 +1,34 @@
+mod fuzzy;
 mod models;
 mod storage;
 mod ui;
+mod validation;
+mod yaml_edit;
 
 use anyhow::Result;
 use crossterm::{
-    event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyModifiers},
+    event::{
+        self, DisableBracketedPaste, DisableMouseCapture, EnableBracketedPaste, EnableMouseCapture, Event,
+        KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind,
+    },
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
+use models::TemplateFieldKind;
 use ratatui::{backend::CrosstermBackend, Terminal};
 use std::io;
-use ui::app::{App, LogFilterPanel, Screen, TodoFilterPanel};
+use ui::app::{App, AutocompleteType, BoardGrouping, ClickList, ClickTarget, DetailsTab, LogFilterPanel, Screen, TodoFilterPanel};
 
 fn main() -> Result<()> {
+    // Maintenance commands run without the TUI
+    if let Some(command) = std::env::args().nth(1) {
+        return run_command(&command);
+    }
+
     // Setup terminal
     enable_raw_mode()?;
     let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
+    execute!(stdout, EnterAlternateScreen, EnableMouseCapture, EnableBracketedPaste)?;
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
 
@@ -29,7 +41,8 @@ fn main() -> Result<()> {
     execute!(
         terminal.backend_mut(),
         LeaveAlternateScreen,
-        DisableMouseCapture
+        DisableMouseCapture,
+        DisableBracketedPaste
     )?;
     terminal.show_cursor()?;
 
@@ -40,8 +53,59 @@ fn main() -> Result<()> {
     Ok(())
 }
 
+fn run_command(command: &str) -> Result<()> {
+    let storage = storage::Storage::new()?;
+    match command {
+        "migrate-attachments" => {
+            let stats = storage.migrate_attachments()?;
+            println!(
+                "Moved {} attachment(s) into {}, freeing {} of duplicates",
+                stats.files,
+                storage.blobs_dir().display(),
+                ui::log_entry::format_size(stats.bytes)
+            );
+            println!("Set `attachments: {{ dedupe: true }}` in config.yml to store new attachments the same way");
+        }
+        "status-history" => {
+            // Tab-separated so it can be piped into other tools
+            let project_filter = std::env::args().nth(2);
+            let projects = storage.load_projects()?;
+            for project in &projects {
+                if project_filter.as_ref().is_some_and(|name| !models::project_within(&projects, &project.name, name)) {
+                    continue;
+                }
+                for change in &project.status_history {
+                    println!(
+                        "{}\t{}\t{}\t{}\t{}",
+                        change.at.format("%Y-%m-%d %H:%M:%S"),
+                        project.name,
+                        change.from,
+                        change.to,
+                        change.reason.as_deref().unwrap_or("")
+                    );
+                }
+            }
+        }
+        "gc" => {
+            let stats = storage.collect_garbage()?;
+            println!(
+                "Removed {} unreferenced blob(s) ({})",
+                stats.files,
+                ui::log_entry::format_size(stats.bytes)
+            );
+        }
+        _ => {
+            eprintln!("Unknown command: {}", command);
+            eprintln!("Usage: todo-log [migrate-attachments | gc | status-history [project]]");
+            std::process::exit(2);
+        }
+    }
+    Ok(())
+}
+
 fn run_app(terminal: &mut Terminal<CrosstermBackend<io::Stdout>>, app: &mut App) -> Result<()> {
     loop {
+        app.clear_click_areas();
         terminal.draw(|f| {
             let area = f.area();
             match &app.screen {
@@ -56,6 +120,8 @@ fn run_app(terminal: &mut Terminal<CrosstermBackend<io::Stdout>>, app: &mut App)
                 Screen::PeopleList => ui::people_list::render(f, app, area),
                 Screen::PersonDetails(idx) => ui::person_details::render(f, app, area, *idx),
                 Screen::PersonEdit(_) => ui::person_edit::render(f, app, area),
+                Screen::Settings => ui::settings::render(f, app, area),
+                Screen::Board => ui::board::render(f, app, area),
             }
         })?;
 
@@ -63,29 +129,469 @@ fn run_app(terminal: &mut Terminal<CrosstermBackend<io::Stdout>>, app: &mut App)
             return Ok(());
         }
 
+        app.autosave_draft();
+
         if event::poll(std::time::Duration::from_millis(100))? {
-            if let Event::Key(key) = event::read()? {
+            match event::read()? {
+                Event::Key(key) => {
+                    app.clear_status();
+                    handle_key(app, key)?;
+                }
+                Event::Paste(text) => {
+                    app.clear_status();
+                    handle_paste(app, &text);
+                }
+                Event::Mouse(mouse) => {
+                    handle_mouse(app, mouse)?;
+                }
+                _ => {}
+            }
+        }
+
+        if let Some(path) = app.external_edit.take() {
+            edit_externally(terminal, app, &path)?;
+        }
+    }
+}
+
+/// Suspend the TUI while $EDITOR edits a file, then pick up the changes
+fn edit_externally(
+    terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
+    app: &mut App,
+    path: &std::path::Path,
+) -> Result<()> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    disable_raw_mode()?;
+    execute!(terminal.backend_mut(), LeaveAlternateScreen, DisableMouseCapture, DisableBracketedPaste)?;
+
+    let editor = std::env::var("VISUAL")
+        .or_else(|_| std::env::var("EDITOR"))
+        .unwrap_or_else(|_| "vi".to_string());
+    // The editor setting may carry arguments, e.g. `code --wait`
+    let mut words = editor.split_whitespace();
+    let status = match words.next() {
+        Some(program) => std::process::Command::new(program).args(words).arg(path).status(),
+        None => Err(io::Error::new(io::ErrorKind::NotFound, "no editor set")),
+    };
+
+    enable_raw_mode()?;
+    execute!(terminal.backend_mut(), EnterAlternateScreen, EnableMouseCapture, EnableBracketedPaste)?;
+    terminal.clear()?;
+
+    match status {
+        Ok(status) if status.success() => {
+            app.reload_project_notes();
+            app.status_message = Some("Notes updated".to_string());
+        }
+        Ok(status) => app.status_message = Some(format!("{} exited with {}", editor, status)),
+        Err(err) => app.status_message = Some(format!("Couldn't run {}: {}", editor, err)),
+    }
+    Ok(())
+}
+
+fn handle_key(app: &mut App, key: KeyEvent) -> Result<()> {
+    match &app.screen {
+        Screen::Menu => handle_menu_input(app, key.code)?,
+        Screen::LogEntry => handle_log_entry_input(app, key.code, key.modifiers)?,
+        Screen::TodoList => handle_todo_list_input(app, key.code)?,
+        Screen::LogList => handle_log_list_input(app, key.code),
+        Screen::ViewLog(_) => handle_view_log_input(app, key.code),
+        Screen::ProjectList => handle_project_list_input(app, key.code)?,
+        Screen::ProjectDetails(_) => handle_project_details_input(app, key.code)?,
+        Screen::ProjectEdit(_) => {
+            handle_project_edit_input(app, key.code, key.modifiers)?;
+            app.refresh_edit_errors();
+        }
+        Screen::PeopleList => handle_people_list_input(app, key.code)?,
+        Screen::PersonDetails(_) => handle_person_details_input(app, key.code)?,
+        Screen::PersonEdit(_) => {
+            handle_person_edit_input(app, key.code, key.modifiers)?;
+            app.refresh_edit_errors();
+        }
+        Screen::Settings => handle_settings_input(app, key.code, key.modifiers)?,
+        Screen::Board => handle_board_input(app, key.code, key.modifiers)?,
+    }
+    Ok(())
+}
+
+/// Send a plain key press through the normal input handlers
+fn press(app: &mut App, code: KeyCode) -> Result<()> {
+    handle_key(app, KeyEvent::new(code, KeyModifiers::NONE))
+}
+
+fn handle_mouse(app: &mut App, mouse: MouseEvent) -> Result<()> {
+    match mouse.kind {
+        // The scroll wheel behaves like the arrow keys on every screen
+        MouseEventKind::ScrollUp => press(app, KeyCode::Up),
+        MouseEventKind::ScrollDown => press(app, KeyCode::Down),
+        MouseEventKind::Down(MouseButton::Left) => {
+            if let Some((area, target)) = app.click_target_at(mouse.column, mouse.row) {
                 app.clear_status();
+                handle_click(app, area, target, mouse.column, mouse.row)?;
+            }
+            Ok(())
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Select a clicked row, returning true if it was already selected
+fn select_row(selected: &mut usize, idx: usize) -> bool {
+    let was_selected = *selected == idx;
+    *selected = idx;
+    was_selected
+}
+
+fn handle_click(app: &mut App, area: ratatui::layout::Rect, target: ClickTarget, x: u16, y: u16) -> Result<()> {
+    let (list, idx) = match target {
+        ClickTarget::Blocker => return Ok(()),
+        ClickTarget::Editor => {
+            app.attachments_focused = false;
+            app.attachment_rename = None;
+            app.set_cursor_line_col((y - area.y) as usize, (x - area.x) as usize);
+            return Ok(());
+        }
+        ClickTarget::TodoCheckbox(idx) => {
+            if app.screen == Screen::TodoList {
+                app.todo_selected = idx;
+            } else {
+                app.details_todo_selected = idx;
+            }
+            return press(app, KeyCode::Char('x'));
+        }
+        ClickTarget::Row(list, idx) => (list, idx),
+    };
+
+    // Clicking a row selects it; clicking the selected row again opens it
+    match list {
+        ClickList::Menu => {
+            if select_row(&mut app.menu_selected, idx) {
+                press(app, KeyCode::Enter)?;
+            }
+        }
+        ClickList::Drafts => {
+            if select_row(&mut app.draft_selected, idx) {
+                press(app, KeyCode::Enter)?;
+            }
+        }
+        ClickList::Templates => {
+            if select_row(&mut app.template_selected, idx) {
+                press(app, KeyCode::Enter)?;
+            }
+        }
+        ClickList::RecentDirs => {
+            if select_row(&mut app.file_browser_recent_selected, idx) {
+                press(app, KeyCode::Enter)?;
+            }
+        }
+        ClickList::FileBrowser => {
+            if select_row(&mut app.file_browser_selected, idx) {
+                press(app, KeyCode::Enter)?;
+            }
+        }
+        ClickList::Attachments => {
+            if app.attachment_rename.is_none() {
+                app.attachments_focused = true;
+                app.attachment_selected = idx;
+            }
+        }
+        ClickList::ViewLogAttachments => {
+            app.view_log_attachments_focused = true;
+            if select_row(&mut app.view_log_attachment_selected, idx) {
+                app.open_view_log_attachment();
+            }
+        }
+        ClickList::Todos => {
+            if select_row(&mut app.todo_selected, idx) {
+                press(app, KeyCode::Char('l'))?;
+            }
+        }
+        ClickList::Logs => {
+            if select_row(&mut app.log_selected, idx) {
+                press(app, KeyCode::Enter)?;
+            }
+        }
+        ClickList::Projects => {
+            if select_row(&mut app.project_selected, idx) {
+                press(app, KeyCode::Enter)?;
+            }
+        }
+        ClickList::ProjectDetailsLogs => {
+            if select_row(&mut app.project_details_log_selected, idx) {
+                press(app, KeyCode::Enter)?;
+            }
+        }
+        ClickList::BoardCards(column) => {
+            let was_selected = app.board_column == column && app.board_selected == idx;
+            app.board_column = column;
+            app.board_selected = idx;
+            if was_selected {
+                press(app, KeyCode::Enter)?;
+            }
+        }
+        ClickList::DetailsTabs => {
+            app.set_details_tab(DetailsTab::ALL[idx]);
+        }
+        ClickList::DetailsTodos => {
+            if select_row(&mut app.details_todo_selected, idx) {
+                press(app, KeyCode::Enter)?;
+            }
+        }
+        ClickList::People => {
+            if select_row(&mut app.person_selected, idx) {
+                press(app, KeyCode::Enter)?;
+            }
+        }
+        ClickList::PersonDetailsLogs => {
+            if select_row(&mut app.person_details_log_selected, idx) {
+                press(app, KeyCode::Enter)?;
+            }
+        }
+
+        // Suggestions and dropdown options are chosen with a single click
+        ClickList::TemplateSuggestions => {
+            app.template_field_suggestion = idx;
+            press(app, KeyCode::Enter)?;
+        }
+        ClickList::Autocomplete => {
+            app.autocomplete_index = idx;
+            app.autocomplete_picked = true;
+            press(app, KeyCode::Enter)?;
+        }
+        ClickList::ProjectEditStatus => {
+            app.project_edit_status_dropdown_selected = idx;
+            press(app, KeyCode::Enter)?;
+        }
+        ClickList::ProjectEditGroup => {
+            app.project_edit_group_dropdown_selected = idx;
+            press(app, KeyCode::Enter)?;
+        }
+        ClickList::ProjectEditMilestones => {
+            app.project_edit_field = 7;
+            if select_row(&mut app.project_edit_milestone_selected, idx) {
+                press(app, KeyCode::Enter)?;
+            }
+        }
+        ClickList::SettingsStates => {
+            app.settings_section = ui::app::SettingsSection::States;
+            if select_row(&mut app.settings_state_selected, idx) {
+                press(app, KeyCode::Enter)?;
+            }
+        }
+        ClickList::SettingsGroups => {
+            app.settings_section = ui::app::SettingsSection::Groups;
+            if select_row(&mut app.settings_group_selected, idx) {
+                press(app, KeyCode::Enter)?;
+            }
+        }
+        ClickList::CustomFields => {
+            if matches!(app.screen, Screen::PersonEdit(_)) {
+                app.person_edit_field = 5;
+            } else {
+                app.project_edit_field = 8;
+            }
+            if select_row(&mut app.custom_field_selected, idx) {
+                press(app, KeyCode::Enter)?;
+            }
+        }
+
+        // Filter options toggle with a single click
+        ClickList::TodoFilterCompleted => {
+            if app.todo_filter.show_completed != (idx == 1) {
+                press(app, KeyCode::Char('x'))?;
+            }
+        }
+        ClickList::TodoFilterProjects => {
+            app.todo_filter_project_selected = idx;
+            press(app, KeyCode::Char('x'))?;
+        }
+        ClickList::TodoFilterPeople => {
+            app.todo_filter_people_selected = idx;
+            press(app, KeyCode::Char('x'))?;
+        }
+        ClickList::LogFilterProjects => {
+            app.log_filter_project_selected = idx;
+            press(app, KeyCode::Char('x'))?;
+        }
+        ClickList::AttachmentSearchResults => {
+            if select_row(&mut app.attachment_search_selected, idx) {
+                press(app, KeyCode::Enter)?;
+            }
+        }
+        ClickList::LogFilterPeople => {
+            app.log_filter_people_selected = idx;
+            press(app, KeyCode::Char('x'))?;
+        }
+        ClickList::ProjectFilterGroups => {
+            app.project_filter_group_selected = idx;
+            press(app, KeyCode::Char('x'))?;
+        }
+        ClickList::ProjectFilterStatuses => {
+            app.project_filter_status_selected = idx;
+            press(app, KeyCode::Char('x'))?;
+        }
+        ClickList::ProjectFilterFields => {
+            app.project_filter_field_selected = idx;
+            press(app, KeyCode::Char('x'))?;
+        }
+        ClickList::PeopleFilterFields => {
+            app.people_filter_field_selected = idx;
+            press(app, KeyCode::Char('x'))?;
+        }
 
-                match &app.screen {
-                    Screen::Menu => handle_menu_input(app, key.code),
-                    Screen::LogEntry => handle_log_entry_input(app, key.code, key.modifiers)?,
-                    Screen::TodoList => handle_todo_list_input(app, key.code)?,
-                    Screen::LogList => handle_log_list_input(app, key.code),
-                    Screen::ViewLog(_) => handle_view_log_input(app, key.code),
-                    Screen::ProjectList => handle_project_list_input(app, key.code)?,
-                    Screen::ProjectDetails(_) => handle_project_details_input(app, key.code)?,
-                    Screen::ProjectEdit(_) => handle_project_edit_input(app, key.code, key.modifiers)?,
-                    Screen::PeopleList => handle_people_list_input(app, key.code)?,
-                    Screen::PersonDetails(_) => handle_person_details_input(app, key.code)?,
-                    Screen::PersonEdit(_) => handle_person_edit_input(app, key.code, key.modifiers)?,
+        // Filter summaries open their popup, form fields take focus
+        ClickList::TodoFilterPanels => {
+            press(app, KeyCode::Char(['c', 'p', 'h'][idx]))?;
+        }
+        ClickList::LogFilterPanels => {
+            press(app, KeyCode::Char(['s', 'p', 'h'][idx]))?;
+        }
+        ClickList::ProjectFilterPanels => {
+            press(app, KeyCode::Char(['g', 't', '/', 'f'][idx]))?;
+        }
+        ClickList::PeopleFilterPanels => {
+            press(app, KeyCode::Char(['/', 'f'][idx]))?;
+        }
+        ClickList::ProjectEditFields | ClickList::PersonEditFields => {
+            if let Some(digit) = char::from_digit(idx as u32 + 1, 10) {
+                press(app, KeyCode::Char(digit))?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn handle_paste(app: &mut App, text: &str) {
+    match &app.screen {
+        Screen::LogEntry => {
+            if app.file_browser_open && !app.file_browser_recent_open {
+                // A pasted path goes into the path prompt
+                let line = text.lines().next().unwrap_or("").trim().trim_matches(|c| c == '\'' || c == '"');
+                app.file_browser_filter.clear();
+                app.file_browser_input(line);
+                return;
+            }
+            if app.template_picker_open || app.exit_prompt_open || app.file_browser_open {
+                return;
+            }
+            if app.template_pending.is_some() {
+                // Prompts are single line
+                let line = text.lines().next().unwrap_or("").trim();
+                app.template_field_input.push_str(line);
+                app.template_field_suggestion = 0;
+            } else if let Some(input) = app.attachment_rename.as_mut() {
+                input.push_str(text.lines().next().unwrap_or("").trim());
+            } else if app.attachments_focused {
+                // Ignore pastes while managing attachments
+            } else if app.inline_create.is_some() {
+                let line = ui::app::normalize_newlines(text).replace('\n', " ");
+                if let Some(field) = app.inline_create_input() {
+                    field.push_str(&line);
                 }
+            } else if app.timestamp_editing {
+                let line = text.lines().next().unwrap_or("").trim();
+                let mut chars: Vec<char> = app.timestamp_edit_input.chars().collect();
+                for (i, c) in line.chars().enumerate() {
+                    chars.insert(app.timestamp_edit_cursor + i, c);
+                }
+                app.timestamp_edit_input = chars.into_iter().collect();
+                app.timestamp_edit_cursor += line.chars().count();
+            } else {
+                app.paste_into_log(text);
+            }
+        }
+        Screen::ProjectEdit(_) => {
+            let line = ui::app::normalize_newlines(text).replace('\n', " ");
+            if let Some(input) = app
+                .project_edit_status_reason
+                .as_mut()
+                .or(app.project_edit_milestone_input.as_mut())
+                .or(app.custom_field_input.as_mut())
+            {
+                input.push_str(&line);
+                return;
             }
+            match app.project_edit_field {
+                0 => app.project_edit_name.push_str(&line),
+                1 => app.project_edit_description.push_str(&line),
+                2 => app.project_edit_jira.push_str(&line),
+                5 => app.project_edit_parent.push_str(&line),
+                6 => app.project_edit_target_date.push_str(&line),
+                _ => {}
+            }
+        }
+        Screen::PersonEdit(_) => {
+            let line = ui::app::normalize_newlines(text).replace('\n', " ");
+            if let Some(input) = app.custom_field_input.as_mut() {
+                input.push_str(&line);
+                return;
+            }
+            match app.person_edit_field {
+                0 => app.person_edit_name.push_str(&line),
+                1 => app.person_edit_full_name.push_str(&line),
+                2 => app.person_edit_email.push_str(&line),
+                3 => app.person_edit_tel.push_str(&line),
+                4 => app.person_edit_company.push_str(&line),
+                _ => {}
+            }
+        }
+        Screen::Settings => {
+            if let Some(input) = app.settings_input.as_mut() {
+                input.push_str(text.lines().next().unwrap_or("").trim());
+            }
+        }
+        Screen::LogList if app.log_filter_panel == LogFilterPanel::AttachmentSearch => {
+            app.attachment_search_query.push_str(text.lines().next().unwrap_or("").trim());
+            app.update_attachment_search();
         }
+        Screen::ProjectList if app.project_filter_panel == ui::app::ProjectFilterPanel::Search => {
+            app.project_filter.text.push_str(text.lines().next().unwrap_or("").trim());
+            app.apply_project_filter();
+            app.project_selected = 0;
+        }
+        Screen::PeopleList if app.people_filter_panel == ui::app::PeopleFilterPanel::Search => {
+            app.people_filter.text.push_str(text.lines().next().unwrap_or("").trim());
+            app.apply_people_filter();
+            app.person_selected = 0;
+        }
+        _ => {}
     }
 }
 
-fn handle_menu_input(app: &mut App, key: KeyCode) {
+// The arrow key arms keep their bounds checks inside the arm rather than in match guards,
+// so a failed check never falls through to a later arm
+#[allow(clippy::collapsible_match)]
+fn handle_menu_input(app: &mut App, key: KeyCode) -> Result<()> {
+    // Handle the draft picker first if open
+    if app.draft_picker_open {
+        match key {
+            KeyCode::Esc => {
+                app.draft_picker_open = false;
+            }
+            KeyCode::Up => {
+                if app.draft_selected > 0 {
+                    app.draft_selected -= 1;
+                }
+            }
+            KeyCode::Down => {
+                if app.draft_selected < app.drafts.len().saturating_sub(1) {
+                    app.draft_selected += 1;
+                }
+            }
+            KeyCode::Enter => {
+                app.resume_selected_draft();
+            }
+            KeyCode::Char('x') => {
+                app.delete_selected_draft()?;
+            }
+            _ => {}
+        }
+        return Ok(());
+    }
+
     match key {
         KeyCode::Up => {
             if app.menu_selected > 0 {
@@ -93,7 +599,7 @@ fn handle_menu_input(app: &mut App, key: KeyCode) {
             }
         }
         KeyCode::Down => {
-            if app.menu_selected < 4 {
+            if app.menu_selected < 5 {
                 app.menu_selected += 1;
             }
         }
@@ -120,9 +626,18 @@ fn handle_menu_input(app: &mut App, key: KeyCode) {
             app.menu_selected = 4;
             execute_menu_selection(app, 4);
         }
+        KeyCode::Char('6') => {
+            app.menu_selected = 5;
+            execute_menu_selection(app, 5);
+        }
+        KeyCode::Char('d') => {
+            app.open_draft_picker();
+        }
         KeyCode::Esc => app.quit(),
         _ => {}
     }
+
+    Ok(())
 }
 
 fn execute_menu_selection(app: &mut App, selection: usize) {
@@ -140,16 +655,149 @@ fn execute_menu_selection(app: &mut App, selection: usize) {
         4 => {
             let _ = app.show_people();
         }
+        5 => app.show_settings(),
         _ => {}
     }
 }
 
+#[allow(clippy::collapsible_match)]
 fn handle_log_entry_input(app: &mut App, key: KeyCode, modifiers: KeyModifiers) -> Result<()> {
-    // Handle file browser input first if open
+    // Handle template picker and template field prompts first
+    if app.template_picker_open {
+        match key {
+            KeyCode::Esc => {
+                app.template_picker_open = false;
+                app.go_to_screen(Screen::Menu);
+            }
+            KeyCode::Up => {
+                if app.template_selected > 0 {
+                    app.template_selected -= 1;
+                }
+            }
+            KeyCode::Down => {
+                if app.template_selected < app.templates.len() {
+                    app.template_selected += 1;
+                }
+            }
+            KeyCode::Enter => {
+                app.select_template();
+            }
+            _ => {}
+        }
+        return Ok(());
+    }
+
+    if app.template_pending.is_some() {
+        match key {
+            KeyCode::Esc => {
+                app.cancel_template();
+            }
+            KeyCode::Up => {
+                if app.template_field_suggestion > 0 {
+                    app.template_field_suggestion -= 1;
+                }
+            }
+            KeyCode::Down => {
+                if app.template_field_suggestion < app.template_field_suggestions().len().saturating_sub(1) {
+                    app.template_field_suggestion += 1;
+                }
+            }
+            KeyCode::Enter | KeyCode::Tab => {
+                app.submit_template_field();
+            }
+            KeyCode::Backspace => {
+                app.template_field_input.pop();
+                app.template_field_suggestion = 0;
+            }
+            KeyCode::Char(c) => {
+                // Tags can't contain whitespace
+                let is_tag = app.current_template_field().map(|f| f.kind != TemplateFieldKind::Text).unwrap_or(false);
+                if !(is_tag && c.is_whitespace()) {
+                    app.template_field_input.push(c);
+                    app.template_field_suggestion = 0;
+                }
+            }
+            _ => {}
+        }
+        return Ok(());
+    }
+
+    // Handle the pasted files prompt
+    if !app.paste_pending_files.is_empty() {
+        match key {
+            KeyCode::Char('a') => {
+                app.attach_pasted_files();
+            }
+            KeyCode::Char('t') => {
+                app.insert_pasted_text();
+            }
+            KeyCode::Esc => {
+                app.paste_pending_files.clear();
+                app.paste_pending_text.clear();
+            }
+            _ => {}
+        }
+        return Ok(());
+    }
+
+    // Handle the unsaved changes prompt
+    if app.exit_prompt_open {
+        match key {
+            KeyCode::Char('s') => {
+                app.exit_prompt_open = false;
+                app.save_log()?;
+            }
+            KeyCode::Char('d') if app.notes_editing.is_none() => {
+                app.keep_draft_and_leave()?;
+            }
+            KeyCode::Char('x') => {
+                app.discard_and_leave();
+            }
+            KeyCode::Esc => {
+                app.exit_prompt_open = false;
+            }
+            _ => {}
+        }
+        return Ok(());
+    }
+
+    // Handle the recent directories list of the file browser
+    if app.file_browser_open && app.file_browser_recent_open {
+        match key {
+            KeyCode::Esc => {
+                app.file_browser_recent_open = false;
+            }
+            KeyCode::Char('r') if modifiers.contains(KeyModifiers::CONTROL) => {
+                app.file_browser_recent_open = false;
+            }
+            KeyCode::Up => {
+                if app.file_browser_recent_selected > 0 {
+                    app.file_browser_recent_selected -= 1;
+                }
+            }
+            KeyCode::Down => {
+                if app.file_browser_recent_selected < app.ui_state.recent_dirs.len().saturating_sub(1) {
+                    app.file_browser_recent_selected += 1;
+                }
+            }
+            KeyCode::Enter => {
+                app.file_browser_open_recent();
+            }
+            _ => {}
+        }
+        return Ok(());
+    }
+
+    // Handle file browser input if open
     if app.file_browser_open {
         match key {
             KeyCode::Esc => {
-                app.close_file_browser();
+                if app.file_browser_filter.is_empty() {
+                    app.close_file_browser();
+                } else {
+                    app.file_browser_filter.clear();
+                    app.apply_file_browser_filter();
+                }
             }
             KeyCode::Up => {
                 if app.file_browser_selected > 0 {
@@ -165,7 +813,23 @@ fn handle_log_entry_input(app: &mut App, key: KeyCode, modifiers: KeyModifiers)
                 app.file_browser_enter();
             }
             KeyCode::Backspace => {
-                app.file_browser_go_up();
+                app.file_browser_backspace();
+            }
+            KeyCode::Tab => {
+                app.file_browser_complete();
+            }
+            KeyCode::Char('d') if modifiers.contains(KeyModifiers::CONTROL) => {
+                app.toggle_file_browser_hidden();
+            }
+            KeyCode::Char('r') if modifiers.contains(KeyModifiers::CONTROL) => {
+                app.file_browser_recent_selected = 0;
+                app.file_browser_recent_open = !app.ui_state.recent_dirs.is_empty();
+            }
+            KeyCode::Char(' ') if !app.file_browser_path_mode() => {
+                app.toggle_file_browser_mark();
+            }
+            KeyCode::Char(c) if !modifiers.contains(KeyModifiers::CONTROL) => {
+                app.file_browser_input(&c.to_string());
             }
             _ => {}
         }
@@ -242,6 +906,64 @@ fn handle_log_entry_input(app: &mut App, key: KeyCode, modifiers: KeyModifiers)
         }
     }
 
+    // Handle the inline project/person create form
+    if app.inline_create.is_some() {
+        let is_project = app.inline_create == Some(AutocompleteType::Project);
+        match key {
+            KeyCode::Esc => app.cancel_inline_create(),
+            KeyCode::Enter => app.save_inline_create()?,
+            KeyCode::Char('s') if modifiers.contains(KeyModifiers::CONTROL) => app.save_inline_create()?,
+            KeyCode::Tab | KeyCode::Down => app.inline_create_field = (app.inline_create_field + 1) % 3,
+            KeyCode::BackTab | KeyCode::Up => app.inline_create_field = (app.inline_create_field + 2) % 3,
+            KeyCode::Left if is_project && app.inline_create_field == 2 => app.cycle_inline_create_group(false),
+            KeyCode::Right if is_project && app.inline_create_field == 2 => app.cycle_inline_create_group(true),
+            KeyCode::Backspace => {
+                if let Some(field) = app.inline_create_input() {
+                    field.pop();
+                }
+            }
+            KeyCode::Char(c) => {
+                if let Some(field) = app.inline_create_input() {
+                    field.push(c);
+                }
+            }
+            _ => {}
+        }
+        return Ok(());
+    }
+
+    // Handle renaming an attachment
+    if let Some(input) = app.attachment_rename.as_mut() {
+        match key {
+            KeyCode::Enter => app.apply_attachment_rename(),
+            KeyCode::Esc => app.attachment_rename = None,
+            KeyCode::Backspace => {
+                input.pop();
+            }
+            KeyCode::Char(c) => input.push(c),
+            _ => {}
+        }
+        return Ok(());
+    }
+
+    // Handle the focused attachments pane
+    if app.attachments_focused {
+        match key {
+            KeyCode::Esc => app.attachments_focused = false,
+            KeyCode::Char('f') if modifiers.contains(KeyModifiers::CONTROL) => app.attachments_focused = false,
+            KeyCode::Up => app.attachment_selected = app.attachment_selected.saturating_sub(1),
+            KeyCode::Down => {
+                if app.attachment_selected + 1 < app.attachments.len() {
+                    app.attachment_selected += 1;
+                }
+            }
+            KeyCode::Char('r') | KeyCode::F(2) => app.start_attachment_rename(),
+            KeyCode::Char('d') | KeyCode::Delete => app.remove_attachment(app.attachment_selected),
+            _ => {}
+        }
+        return Ok(());
+    }
+
     // Handle autocomplete navigation
     if app.autocomplete_active {
         match key {
@@ -249,14 +971,20 @@ fn handle_log_entry_input(app: &mut App, key: KeyCode, modifiers: KeyModifiers)
                 if app.autocomplete_index < app.autocomplete_suggestions.len().saturating_sub(1) {
                     app.autocomplete_index += 1;
                 }
+                app.autocomplete_picked = true;
                 return Ok(());
             }
             KeyCode::Up => {
                 if app.autocomplete_index > 0 {
                     app.autocomplete_index -= 1;
                 }
+                app.autocomplete_picked = true;
                 return Ok(());
             }
+            KeyCode::Enter if !app.enter_accepts_autocomplete() => {
+                // Falls through to insert the newline
+                app.autocomplete_active = false;
+            }
             KeyCode::Tab | KeyCode::Enter => {
                 app.accept_autocomplete();
                 return Ok(());
@@ -276,6 +1004,10 @@ fn handle_log_entry_input(app: &mut App, key: KeyCode, modifiers: KeyModifiers)
                 app.save_log()?;
                 return Ok(());
             }
+            KeyCode::Char('a' | 't' | 'f') if app.notes_editing.is_some() => {
+                app.status_message = Some("Notes have no timestamp or attachments".to_string());
+                return Ok(());
+            }
             KeyCode::Char('a') => {
                 // Open file browser
                 app.open_file_browser();
@@ -286,13 +1018,23 @@ fn handle_log_entry_input(app: &mut App, key: KeyCode, modifiers: KeyModifiers)
                 app.start_timestamp_edit();
                 return Ok(());
             }
+            KeyCode::Char('f') => {
+                // Manage attachments
+                if app.attachments.is_empty() {
+                    app.status_message = Some("No attachments (Ctrl+A to add)".to_string());
+                } else {
+                    app.attachments_focused = true;
+                    app.attachment_selected = app.attachment_selected.min(app.attachments.len() - 1);
+                }
+                return Ok(());
+            }
             _ => {}
         }
     }
 
     match key {
         KeyCode::Esc => {
-            app.go_to_screen(Screen::Menu);
+            app.leave_log_entry();
         }
         KeyCode::Char(c) => {
             app.insert_char(c);
@@ -327,22 +1069,10 @@ fn handle_log_entry_input(app: &mut App, key: KeyCode, modifiers: KeyModifiers)
     Ok(())
 }
 
+#[allow(clippy::collapsible_match)]
 fn handle_todo_list_input(app: &mut App, key: KeyCode) -> Result<()> {
-    // Handle filter panel input if one is open
-    match app.todo_filter_panel {
-        TodoFilterPanel::Completed => {
-            handle_todo_completed_filter_input(app, key);
-            return Ok(());
-        }
-        TodoFilterPanel::Projects => {
-            handle_todo_project_filter_input(app, key);
-            return Ok(());
-        }
-        TodoFilterPanel::People => {
-            handle_todo_people_filter_input(app, key);
-            return Ok(());
-        }
-        TodoFilterPanel::None => {}
+    if handle_todo_filter_panel_input(app, key) {
+        return Ok(());
     }
 
     // Normal todo list navigation
@@ -381,6 +1111,72 @@ fn handle_todo_list_input(app: &mut App, key: KeyCode) -> Result<()> {
             app.todo_filter_people_selected = 0;
             app.todo_filter_panel = TodoFilterPanel::People;
         }
+        KeyCode::Char('b') => {
+            app.show_board()?;
+        }
+        _ => {}
+    }
+
+    Ok(())
+}
+
+/// Handle filter panel input if one is open, returning whether it took the key.
+/// The todo list and the board share the filter.
+fn handle_todo_filter_panel_input(app: &mut App, key: KeyCode) -> bool {
+    match app.todo_filter_panel {
+        TodoFilterPanel::Completed => handle_todo_completed_filter_input(app, key),
+        TodoFilterPanel::Projects => handle_todo_project_filter_input(app, key),
+        TodoFilterPanel::People => handle_todo_people_filter_input(app, key),
+        TodoFilterPanel::None => return false,
+    }
+    app.clamp_board_selection();
+    true
+}
+
+fn handle_board_input(app: &mut App, key: KeyCode, modifiers: KeyModifiers) -> Result<()> {
+    if handle_todo_filter_panel_input(app, key) {
+        return Ok(());
+    }
+
+    match key {
+        KeyCode::Esc | KeyCode::Char('b') => {
+            app.todo_selected = 0;
+            app.go_to_screen(Screen::TodoList);
+        }
+        KeyCode::Left if modifiers.contains(KeyModifiers::SHIFT) => app.move_board_card(false)?,
+        KeyCode::Right if modifiers.contains(KeyModifiers::SHIFT) => app.move_board_card(true)?,
+        KeyCode::Char('<') => app.move_board_card(false)?,
+        KeyCode::Char('>') => app.move_board_card(true)?,
+        KeyCode::Left => app.move_board_focus(false),
+        KeyCode::Right => app.move_board_focus(true),
+        KeyCode::Up => {
+            app.board_selected = app.board_selected.saturating_sub(1);
+        }
+        KeyCode::Down => {
+            app.board_selected += 1;
+            app.clamp_board_selection();
+        }
+        KeyCode::Char('x') => app.toggle_board_card()?,
+        KeyCode::Enter | KeyCode::Char('l') => app.view_board_todo_log(),
+        KeyCode::Char('g') => {
+            app.board_grouping = match app.board_grouping {
+                BoardGrouping::State => BoardGrouping::Project,
+                BoardGrouping::Project => BoardGrouping::State,
+            };
+            app.board_column = 0;
+            app.board_selected = 0;
+        }
+        KeyCode::Char('c') => {
+            app.todo_filter_panel = TodoFilterPanel::Completed;
+        }
+        KeyCode::Char('p') => {
+            app.todo_filter_project_selected = 0;
+            app.todo_filter_panel = TodoFilterPanel::Projects;
+        }
+        KeyCode::Char('h') => {
+            app.todo_filter_people_selected = 0;
+            app.todo_filter_panel = TodoFilterPanel::People;
+        }
         _ => {}
     }
 
@@ -401,6 +1197,7 @@ fn handle_todo_completed_filter_input(app: &mut App, key: KeyCode) {
     }
 }
 
+#[allow(clippy::collapsible_match)]
 fn handle_todo_project_filter_input(app: &mut App, key: KeyCode) {
     let all_projects = app.all_project_names();
 
@@ -430,6 +1227,7 @@ fn handle_todo_project_filter_input(app: &mut App, key: KeyCode) {
     }
 }
 
+#[allow(clippy::collapsible_match)]
 fn handle_todo_people_filter_input(app: &mut App, key: KeyCode) {
     let all_people = app.all_people_names();
 
@@ -459,6 +1257,7 @@ fn handle_todo_people_filter_input(app: &mut App, key: KeyCode) {
     }
 }
 
+#[allow(clippy::collapsible_match)]
 fn handle_log_list_input(app: &mut App, key: KeyCode) {
     // Handle filter panel input if one is open
     match app.log_filter_panel {
@@ -478,6 +1277,10 @@ fn handle_log_list_input(app: &mut App, key: KeyCode) {
             handle_people_filter_input(app, key);
             return;
         }
+        LogFilterPanel::AttachmentSearch => {
+            handle_attachment_search_input(app, key);
+            return;
+        }
         LogFilterPanel::None => {}
     }
 
@@ -519,6 +1322,41 @@ fn handle_log_list_input(app: &mut App, key: KeyCode) {
             app.log_filter_people_selected = 0;
             app.log_filter_panel = LogFilterPanel::People;
         }
+        KeyCode::Char('/') => {
+            // Search inside text attachments
+            app.open_attachment_search();
+        }
+        _ => {}
+    }
+}
+
+#[allow(clippy::collapsible_match)]
+fn handle_attachment_search_input(app: &mut App, key: KeyCode) {
+    match key {
+        KeyCode::Esc => {
+            app.log_filter_panel = LogFilterPanel::None;
+        }
+        KeyCode::Up => {
+            if app.attachment_search_selected > 0 {
+                app.attachment_search_selected -= 1;
+            }
+        }
+        KeyCode::Down => {
+            if app.attachment_search_selected < app.attachment_search_results.len().saturating_sub(1) {
+                app.attachment_search_selected += 1;
+            }
+        }
+        KeyCode::Enter => {
+            app.open_attachment_search_result();
+        }
+        KeyCode::Backspace => {
+            app.attachment_search_query.pop();
+            app.update_attachment_search();
+        }
+        KeyCode::Char(c) => {
+            app.attachment_search_query.push(c);
+            app.update_attachment_search();
+        }
         _ => {}
     }
 }
@@ -575,6 +1413,7 @@ fn handle_date_filter_input(app: &mut App, key: KeyCode, is_start: bool) {
     }
 }
 
+#[allow(clippy::collapsible_match)]
 fn handle_project_filter_input(app: &mut App, key: KeyCode) {
     let all_projects = app.all_project_names();
 
@@ -603,6 +1442,7 @@ fn handle_project_filter_input(app: &mut App, key: KeyCode) {
     }
 }
 
+#[allow(clippy::collapsible_match)]
 fn handle_people_filter_input(app: &mut App, key: KeyCode) {
     let all_people = app.all_people_names();
 
@@ -631,34 +1471,71 @@ fn handle_people_filter_input(app: &mut App, key: KeyCode) {
     }
 }
 
+#[allow(clippy::collapsible_match)]
 fn handle_view_log_input(app: &mut App, key: KeyCode) {
+    // Handle the focused attachments list
+    if app.view_log_attachments_focused {
+        match key {
+            KeyCode::Esc | KeyCode::Tab => app.view_log_attachments_focused = false,
+            KeyCode::Up => app.view_log_attachment_selected = app.view_log_attachment_selected.saturating_sub(1),
+            KeyCode::Down => {
+                if app.view_log_attachment_selected + 1 < app.view_log_attachments.len() + app.view_log_missing_refs.len() {
+                    app.view_log_attachment_selected += 1;
+                }
+            }
+            KeyCode::Enter | KeyCode::Char('o') => app.open_view_log_attachment(),
+            _ => {}
+        }
+        return;
+    }
+
     match key {
         KeyCode::Esc => {
             app.go_back();
         }
+        KeyCode::Tab => {
+            app.view_log_attachments_focused =
+                !app.view_log_attachments.is_empty() || !app.view_log_missing_refs.is_empty();
+        }
         KeyCode::Up => {
             if app.view_log_scroll > 0 {
                 app.view_log_scroll -= 1;
             }
         }
         KeyCode::Down => {
-            app.view_log_scroll += 1;
+            app.view_log_scroll = app.view_log_scroll.saturating_add(1);
         }
         KeyCode::PageUp => {
             app.view_log_scroll = app.view_log_scroll.saturating_sub(10);
         }
         KeyCode::PageDown => {
-            app.view_log_scroll += 10;
+            app.view_log_scroll = app.view_log_scroll.saturating_add(10);
+        }
+        _ => {}
+    }
+}
+
+#[allow(clippy::collapsible_match)]
+fn handle_project_list_input(app: &mut App, key: KeyCode) -> Result<()> {
+    // Handle filter panel input if one is open
+    match app.project_filter_panel {
+        ui::app::ProjectFilterPanel::Groups => {
+            handle_project_group_filter_input(app, key);
+            return Ok(());
+        }
+        ui::app::ProjectFilterPanel::Statuses => {
+            handle_project_status_filter_input(app, key);
+            return Ok(());
+        }
+        ui::app::ProjectFilterPanel::Search => {
+            handle_project_search_input(app, key);
+            return Ok(());
+        }
+        ui::app::ProjectFilterPanel::Fields => {
+            handle_project_field_filter_input(app, key);
+            return Ok(());
         }
-        _ => {}
-    }
-}
-
-fn handle_project_list_input(app: &mut App, key: KeyCode) -> Result<()> {
-    // Handle filter panel input if one is open
-    if app.project_filter_panel == ui::app::ProjectFilterPanel::Groups {
-        handle_project_group_filter_input(app, key);
-        return Ok(());
+        ui::app::ProjectFilterPanel::None => {}
     }
 
     // Normal project list navigation
@@ -694,23 +1571,43 @@ fn handle_project_list_input(app: &mut App, key: KeyCode) -> Result<()> {
             app.project_filter_group_selected = 0;
             app.project_filter_panel = ui::app::ProjectFilterPanel::Groups;
         }
+        KeyCode::Char('t') => {
+            app.project_filter_status_selected = 0;
+            app.project_filter_panel = ui::app::ProjectFilterPanel::Statuses;
+        }
+        KeyCode::Char('/') => {
+            app.project_filter_panel = ui::app::ProjectFilterPanel::Search;
+        }
+        KeyCode::Char('f') => {
+            app.project_filter_field_selected = 0;
+            app.project_filter_panel = ui::app::ProjectFilterPanel::Fields;
+        }
         KeyCode::Char('n') => {
             // Create new project
             app.start_new_project();
         }
+        KeyCode::Char('d') => {
+            app.toggle_project_sort_by_deadline();
+        }
+        KeyCode::Char('s') => {
+            app.set_project_sort(app.project_sort.next());
+        }
+        KeyCode::Char('A') => {
+            app.toggle_projects_show_archived();
+        }
         _ => {}
     }
 
     Ok(())
 }
 
+#[allow(clippy::collapsible_match)]
 fn handle_project_group_filter_input(app: &mut App, key: KeyCode) {
     let all_groups = app.all_group_names();
 
     match key {
         KeyCode::Esc => {
-            app.project_filter_panel = ui::app::ProjectFilterPanel::None;
-            app.project_selected = 0;
+            app.close_project_filter_panel();
         }
         KeyCode::Up => {
             if app.project_filter_group_selected > 0 {
@@ -733,11 +1630,109 @@ fn handle_project_group_filter_input(app: &mut App, key: KeyCode) {
     }
 }
 
+#[allow(clippy::collapsible_match)]
+fn handle_project_status_filter_input(app: &mut App, key: KeyCode) {
+    let states = app.config.allowed_state_names();
+
+    match key {
+        KeyCode::Esc => {
+            app.close_project_filter_panel();
+        }
+        KeyCode::Up => {
+            if app.project_filter_status_selected > 0 {
+                app.project_filter_status_selected -= 1;
+            }
+        }
+        KeyCode::Down => {
+            if app.project_filter_status_selected < states.len().saturating_sub(1) {
+                app.project_filter_status_selected += 1;
+            }
+        }
+        KeyCode::Char('x') => {
+            if let Some(state) = states.get(app.project_filter_status_selected) {
+                app.toggle_project_filter_status(state);
+            }
+            app.project_selected = 0;
+        }
+        KeyCode::Char('r') => {
+            app.reset_project_filter_statuses();
+            app.project_selected = 0;
+        }
+        _ => {}
+    }
+}
+
+#[allow(clippy::collapsible_match)]
+fn handle_project_field_filter_input(app: &mut App, key: KeyCode) {
+    let count = app.project_field_facets().len();
+
+    match key {
+        KeyCode::Esc => {
+            app.close_project_filter_panel();
+        }
+        KeyCode::Up => {
+            app.project_filter_field_selected = app.project_filter_field_selected.saturating_sub(1);
+        }
+        KeyCode::Down => {
+            if app.project_filter_field_selected + 1 < count {
+                app.project_filter_field_selected += 1;
+            }
+        }
+        KeyCode::Char('x') | KeyCode::Char(' ') => {
+            app.toggle_project_filter_field(app.project_filter_field_selected);
+            app.project_selected = 0;
+        }
+        _ => {}
+    }
+}
+
+fn handle_project_search_input(app: &mut App, key: KeyCode) {
+    match key {
+        KeyCode::Enter => {
+            app.close_project_filter_panel();
+            return;
+        }
+        KeyCode::Esc => {
+            app.project_filter.text.clear();
+            app.close_project_filter_panel();
+        }
+        KeyCode::Backspace => {
+            app.project_filter.text.pop();
+        }
+        KeyCode::Char(c) => {
+            app.project_filter.text.push(c);
+        }
+        _ => return,
+    }
+    app.apply_project_filter();
+    app.project_selected = 0;
+    app.project_list_scroll = 0;
+}
+
+#[allow(clippy::collapsible_match)]
 fn handle_project_details_input(app: &mut App, key: KeyCode) -> Result<()> {
+    // A pending delete takes the next key: y confirms, anything else cancels
+    if app.delete_confirm.is_some() {
+        if key == KeyCode::Char('y') {
+            app.confirm_delete()?;
+        } else {
+            app.delete_confirm = None;
+        }
+        return Ok(());
+    }
+
+    // The todo tabs take the list keys
+    if app.details_tab != DetailsTab::Logs && handle_details_todos_input(app, key)? {
+        return Ok(());
+    }
+
     match key {
         KeyCode::Esc => {
             app.go_back();
         }
+        KeyCode::Tab => {
+            app.next_details_tab();
+        }
         KeyCode::Up => {
             if app.project_details_log_selected > 0 {
                 app.project_details_log_selected -= 1;
@@ -754,6 +1749,24 @@ fn handle_project_details_input(app: &mut App, key: KeyCode) -> Result<()> {
         KeyCode::Char('e') => {
             app.start_edit_project_from_details();
         }
+        KeyCode::Char('n') => {
+            app.start_edit_notes();
+        }
+        KeyCode::Char('N') => {
+            app.start_external_notes_edit();
+        }
+        KeyCode::PageUp => {
+            app.project_details_notes_scroll = app.project_details_notes_scroll.saturating_sub(5);
+        }
+        KeyCode::PageDown => {
+            app.project_details_notes_scroll = app.project_details_notes_scroll.saturating_add(5);
+        }
+        KeyCode::Char('a') => {
+            app.toggle_archived()?;
+        }
+        KeyCode::Char('d') => {
+            app.start_delete();
+        }
         _ => {}
     }
 
@@ -761,11 +1774,86 @@ fn handle_project_details_input(app: &mut App, key: KeyCode) -> Result<()> {
 }
 
 fn handle_project_edit_input(app: &mut App, key: KeyCode, modifiers: KeyModifiers) -> Result<()> {
+    // The status change reason is typed before the save goes through
+    if let Some(reason) = app.project_edit_status_reason.as_mut() {
+        match key {
+            KeyCode::Esc => app.project_edit_status_reason = None,
+            KeyCode::Enter => app.save_edited_project()?,
+            KeyCode::Char('s') if modifiers.contains(KeyModifiers::CONTROL) => app.save_edited_project()?,
+            KeyCode::Backspace => {
+                reason.pop();
+            }
+            KeyCode::Char(c) => reason.push(c),
+            _ => {}
+        }
+        return Ok(());
+    }
+
     // Check for Ctrl combinations
-    if modifiers.contains(KeyModifiers::CONTROL) {
+    if modifiers.contains(KeyModifiers::CONTROL) && key == KeyCode::Char('s') {
+        if app.apply_pending_custom_field() {
+            app.save_edited_project()?;
+        }
+        return Ok(());
+    }
+
+    // The milestone input takes all keys while open
+    if let Some(input) = app.project_edit_milestone_input.as_mut() {
         match key {
-            KeyCode::Char('s') => {
-                app.save_edited_project()?;
+            KeyCode::Esc => app.project_edit_milestone_input = None,
+            KeyCode::Enter => app.apply_milestone_input(),
+            KeyCode::Backspace => {
+                input.pop();
+            }
+            KeyCode::Char(c) => input.push(c),
+            _ => {}
+        }
+        return Ok(());
+    }
+
+    // Custom fields handle their own keys while focused
+    if (app.custom_field_input.is_some() || app.project_edit_field == 8) && handle_custom_field_input(app, key) {
+        return Ok(());
+    }
+
+    // Dates need digits, so the target date field types them instead of jumping fields
+    if app.project_edit_field == 6 {
+        if let KeyCode::Char(c) = key {
+            app.project_edit_target_date.push(c);
+            return Ok(());
+        }
+    }
+
+    if app.project_edit_field == 7 {
+        match key {
+            KeyCode::Up => {
+                app.project_edit_milestone_selected = app.project_edit_milestone_selected.saturating_sub(1);
+                return Ok(());
+            }
+            KeyCode::Down => {
+                if app.project_edit_milestone_selected + 1 < app.project_edit_milestones.len() {
+                    app.project_edit_milestone_selected += 1;
+                }
+                return Ok(());
+            }
+            KeyCode::Char('a') => {
+                app.start_milestone_input(None);
+                return Ok(());
+            }
+            KeyCode::Enter | KeyCode::Char('e') => {
+                if app.project_edit_milestones.is_empty() {
+                    app.start_milestone_input(None);
+                } else {
+                    app.start_milestone_input(Some(app.project_edit_milestone_selected));
+                }
+                return Ok(());
+            }
+            KeyCode::Char(' ') | KeyCode::Char('x') => {
+                app.toggle_milestone_done();
+                return Ok(());
+            }
+            KeyCode::Char('d') | KeyCode::Delete => {
+                app.remove_milestone();
                 return Ok(());
             }
             _ => {}
@@ -780,8 +1868,9 @@ fn handle_project_edit_input(app: &mut App, key: KeyCode, modifiers: KeyModifier
             // Close any open dropdowns
             app.project_edit_status_dropdown_open = false;
             app.project_edit_group_dropdown_open = false;
-            // Cycle to next field (0-4)
-            app.project_edit_field = (app.project_edit_field + 1) % 5;
+            // Cycle to next field (0-7, plus 8 when custom fields are configured)
+            let fields = if app.config.fields.projects.is_empty() { 8 } else { 9 };
+            app.project_edit_field = (app.project_edit_field + 1) % fields;
             // Open dropdown if moving to status or group field
             if app.project_edit_field == 3 {
                 app.project_edit_status_dropdown_open = true;
@@ -816,6 +1905,26 @@ fn handle_project_edit_input(app: &mut App, key: KeyCode, modifiers: KeyModifier
             app.project_edit_field = 4;
             app.project_edit_group_dropdown_open = true;
         }
+        KeyCode::Char('6') => {
+            app.project_edit_status_dropdown_open = false;
+            app.project_edit_group_dropdown_open = false;
+            app.project_edit_field = 5;
+        }
+        KeyCode::Char('7') => {
+            app.project_edit_status_dropdown_open = false;
+            app.project_edit_group_dropdown_open = false;
+            app.project_edit_field = 6;
+        }
+        KeyCode::Char('8') => {
+            app.project_edit_status_dropdown_open = false;
+            app.project_edit_group_dropdown_open = false;
+            app.project_edit_field = 7;
+        }
+        KeyCode::Char('9') if !app.config.fields.projects.is_empty() => {
+            app.project_edit_status_dropdown_open = false;
+            app.project_edit_group_dropdown_open = false;
+            app.project_edit_field = 8;
+        }
         KeyCode::Up => {
             // Navigate dropdown if on status or group field
             if app.project_edit_field == 3 {
@@ -834,7 +1943,7 @@ fn handle_project_edit_input(app: &mut App, key: KeyCode, modifiers: KeyModifier
             // Navigate dropdown if on status or group field
             if app.project_edit_field == 3 {
                 app.project_edit_status_dropdown_open = true;
-                let max = app.config.allowed_state_names().len().saturating_sub(1);
+                let max = app.project_edit_status_options().len().saturating_sub(1);
                 if app.project_edit_status_dropdown_selected < max {
                     app.project_edit_status_dropdown_selected += 1;
                 }
@@ -849,7 +1958,7 @@ fn handle_project_edit_input(app: &mut App, key: KeyCode, modifiers: KeyModifier
         KeyCode::Enter => {
             // Confirm dropdown selection
             if app.project_edit_field == 3 && app.project_edit_status_dropdown_open {
-                let states = app.config.allowed_state_names();
+                let states = app.project_edit_status_options();
                 if let Some(selected) = states.get(app.project_edit_status_dropdown_selected) {
                     app.project_edit_status = selected.clone();
                 }
@@ -873,6 +1982,7 @@ fn handle_project_edit_input(app: &mut App, key: KeyCode, modifiers: KeyModifier
                 0 => app.project_edit_name.push(c),
                 1 => app.project_edit_description.push(c),
                 2 => app.project_edit_jira.push(c),
+                5 => app.project_edit_parent.push(c),
                 // Fields 3 and 4 are dropdowns, no character input
                 _ => {}
             }
@@ -883,6 +1993,8 @@ fn handle_project_edit_input(app: &mut App, key: KeyCode, modifiers: KeyModifier
                 0 => { app.project_edit_name.pop(); }
                 1 => { app.project_edit_description.pop(); }
                 2 => { app.project_edit_jira.pop(); }
+                5 => { app.project_edit_parent.pop(); }
+                6 => { app.project_edit_target_date.pop(); }
                 // Fields 3 and 4 are dropdowns, no backspace
                 _ => {}
             }
@@ -893,7 +2005,21 @@ fn handle_project_edit_input(app: &mut App, key: KeyCode, modifiers: KeyModifier
     Ok(())
 }
 
+#[allow(clippy::collapsible_match)]
 fn handle_people_list_input(app: &mut App, key: KeyCode) -> Result<()> {
+    // Handle filter panel input if one is open
+    match app.people_filter_panel {
+        ui::app::PeopleFilterPanel::Search => {
+            handle_people_search_input(app, key);
+            return Ok(());
+        }
+        ui::app::PeopleFilterPanel::Fields => {
+            handle_people_field_filter_input(app, key);
+            return Ok(());
+        }
+        ui::app::PeopleFilterPanel::None => {}
+    }
+
     match key {
         KeyCode::Esc => {
             app.go_to_screen(ui::app::Screen::Menu);
@@ -908,7 +2034,7 @@ fn handle_people_list_input(app: &mut App, key: KeyCode) -> Result<()> {
             }
         }
         KeyCode::Down => {
-            if app.person_selected < app.people.len().saturating_sub(1) {
+            if app.person_selected < app.filtered_people.len().saturating_sub(1) {
                 app.person_selected += 1;
                 // Adjust scroll if selection moves below visible area
                 let visible_items = 10;
@@ -923,17 +2049,93 @@ fn handle_people_list_input(app: &mut App, key: KeyCode) -> Result<()> {
         KeyCode::Char('n') => {
             app.start_new_person();
         }
+        KeyCode::Char('A') => {
+            app.toggle_people_show_archived();
+        }
+        KeyCode::Char('/') => {
+            app.people_filter_panel = ui::app::PeopleFilterPanel::Search;
+        }
+        KeyCode::Char('f') => {
+            app.people_filter_field_selected = 0;
+            app.people_filter_panel = ui::app::PeopleFilterPanel::Fields;
+        }
         _ => {}
     }
 
     Ok(())
 }
 
+#[allow(clippy::collapsible_match)]
+fn handle_people_field_filter_input(app: &mut App, key: KeyCode) {
+    let count = app.people_field_facets().len();
+
+    match key {
+        KeyCode::Esc => {
+            app.close_people_filter_panel();
+        }
+        KeyCode::Up => {
+            app.people_filter_field_selected = app.people_filter_field_selected.saturating_sub(1);
+        }
+        KeyCode::Down => {
+            if app.people_filter_field_selected + 1 < count {
+                app.people_filter_field_selected += 1;
+            }
+        }
+        KeyCode::Char('x') | KeyCode::Char(' ') => {
+            app.toggle_people_filter_field(app.people_filter_field_selected);
+            app.person_selected = 0;
+        }
+        _ => {}
+    }
+}
+
+fn handle_people_search_input(app: &mut App, key: KeyCode) {
+    match key {
+        KeyCode::Enter => {
+            app.close_people_filter_panel();
+            return;
+        }
+        KeyCode::Esc => {
+            app.people_filter.text.clear();
+            app.close_people_filter_panel();
+        }
+        KeyCode::Backspace => {
+            app.people_filter.text.pop();
+        }
+        KeyCode::Char(c) => {
+            app.people_filter.text.push(c);
+        }
+        _ => return,
+    }
+    app.apply_people_filter();
+    app.person_selected = 0;
+    app.person_list_scroll = 0;
+}
+
+#[allow(clippy::collapsible_match)]
 fn handle_person_details_input(app: &mut App, key: KeyCode) -> Result<()> {
+    // A pending delete takes the next key: y confirms, anything else cancels
+    if app.delete_confirm.is_some() {
+        if key == KeyCode::Char('y') {
+            app.confirm_delete()?;
+        } else {
+            app.delete_confirm = None;
+        }
+        return Ok(());
+    }
+
+    // The todo tabs take the list keys
+    if app.details_tab != DetailsTab::Logs && handle_details_todos_input(app, key)? {
+        return Ok(());
+    }
+
     match key {
         KeyCode::Esc => {
             app.go_back();
         }
+        KeyCode::Tab => {
+            app.next_details_tab();
+        }
         KeyCode::Up => {
             if app.person_details_log_selected > 0 {
                 app.person_details_log_selected -= 1;
@@ -950,22 +2152,52 @@ fn handle_person_details_input(app: &mut App, key: KeyCode) -> Result<()> {
         KeyCode::Char('e') => {
             app.start_edit_person_from_details();
         }
+        KeyCode::Char('a') => {
+            app.toggle_archived()?;
+        }
+        KeyCode::Char('d') => {
+            app.start_delete();
+        }
         _ => {}
     }
 
     Ok(())
 }
 
+/// List keys of the todo tabs on the details screens, returning whether the key was used
+fn handle_details_todos_input(app: &mut App, key: KeyCode) -> Result<bool> {
+    match key {
+        KeyCode::Up => {
+            app.details_todo_selected = app.details_todo_selected.saturating_sub(1);
+        }
+        KeyCode::Down => {
+            if app.details_todo_selected + 1 < app.details_tab_todos().len() {
+                app.details_todo_selected += 1;
+            }
+        }
+        KeyCode::Char('x') => {
+            app.toggle_details_todo()?;
+        }
+        KeyCode::Enter | KeyCode::Char('l') => {
+            app.view_details_todo_log();
+        }
+        _ => return Ok(false),
+    }
+    Ok(true)
+}
+
 fn handle_person_edit_input(app: &mut App, key: KeyCode, modifiers: KeyModifiers) -> Result<()> {
     // Check for Ctrl combinations
-    if modifiers.contains(KeyModifiers::CONTROL) {
-        match key {
-            KeyCode::Char('s') => {
-                app.save_edited_person()?;
-                return Ok(());
-            }
-            _ => {}
+    if modifiers.contains(KeyModifiers::CONTROL) && key == KeyCode::Char('s') {
+        if app.apply_pending_custom_field() {
+            app.save_edited_person()?;
         }
+        return Ok(());
+    }
+
+    // Custom fields handle their own keys while focused
+    if (app.custom_field_input.is_some() || app.person_edit_field == 5) && handle_custom_field_input(app, key) {
+        return Ok(());
     }
 
     match key {
@@ -973,8 +2205,9 @@ fn handle_person_edit_input(app: &mut App, key: KeyCode, modifiers: KeyModifiers
             app.go_back();
         }
         KeyCode::Tab => {
-            // Cycle to next field (0-4)
-            app.person_edit_field = (app.person_edit_field + 1) % 5;
+            // Cycle to next field (0-4, plus 5 when custom fields are configured)
+            let fields = if app.config.fields.people.is_empty() { 5 } else { 6 };
+            app.person_edit_field = (app.person_edit_field + 1) % fields;
         }
         KeyCode::Char('1') => {
             app.person_edit_field = 0;
@@ -991,6 +2224,9 @@ fn handle_person_edit_input(app: &mut App, key: KeyCode, modifiers: KeyModifiers
         KeyCode::Char('5') => {
             app.person_edit_field = 4;
         }
+        KeyCode::Char('6') if !app.config.fields.people.is_empty() => {
+            app.person_edit_field = 5;
+        }
         KeyCode::Char(c) => {
             // Insert character into current field
             match app.person_edit_field {
@@ -1018,3 +2254,101 @@ fn handle_person_edit_input(app: &mut App, key: KeyCode, modifiers: KeyModifiers
 
     Ok(())
 }
+
+/// Keys for the custom field list of an edit screen and its value popup.
+/// Returns false for keys the edit screen should handle itself.
+fn handle_custom_field_input(app: &mut App, key: KeyCode) -> bool {
+    // The value popup takes all keys while open
+    if let Some(input) = app.custom_field_input.as_mut() {
+        match key {
+            KeyCode::Esc => app.custom_field_input = None,
+            KeyCode::Enter => app.apply_custom_field_input(),
+            KeyCode::Backspace => {
+                input.pop();
+            }
+            KeyCode::Char(c) => input.push(c),
+            _ => {}
+        }
+        return true;
+    }
+
+    match key {
+        KeyCode::Up => {
+            app.custom_field_selected = app.custom_field_selected.saturating_sub(1);
+        }
+        KeyCode::Down => {
+            if app.custom_field_selected + 1 < app.custom_field_defs().len() {
+                app.custom_field_selected += 1;
+            }
+        }
+        KeyCode::Enter | KeyCode::Char('e') => app.start_custom_field_input(),
+        KeyCode::Left => app.cycle_custom_field_option(false),
+        KeyCode::Right | KeyCode::Char(' ') => app.cycle_custom_field_option(true),
+        KeyCode::Char('d') | KeyCode::Delete | KeyCode::Backspace => app.clear_custom_field(),
+        _ => return false,
+    }
+    true
+}
+
+#[allow(clippy::collapsible_match)]
+fn handle_settings_input(app: &mut App, key: KeyCode, modifiers: KeyModifiers) -> Result<()> {
+    use ui::app::SettingsSection;
+
+    // A rename of a state/group in use asks about its projects first
+    if app.settings_rename_confirm.is_some() {
+        app.answer_settings_rename(key == KeyCode::Char('y'));
+        return Ok(());
+    }
+
+    // The name input takes all keys while open
+    if let Some(input) = app.settings_input.as_mut() {
+        match key {
+            KeyCode::Esc => app.settings_input = None,
+            KeyCode::Enter => app.apply_settings_input(),
+            KeyCode::Backspace => {
+                input.pop();
+            }
+            KeyCode::Char(c) => input.push(c),
+            _ => {}
+        }
+        return Ok(());
+    }
+
+    if modifiers.contains(KeyModifiers::CONTROL) && key == KeyCode::Char('s') {
+        app.save_settings()?;
+        return Ok(());
+    }
+
+    let len = app.settings_items(app.settings_section).len();
+    let selected = match app.settings_section {
+        SettingsSection::States => &mut app.settings_state_selected,
+        SettingsSection::Groups => &mut app.settings_group_selected,
+    };
+    match key {
+        KeyCode::Esc => {
+            app.go_back();
+        }
+        KeyCode::Tab | KeyCode::BackTab => {
+            app.settings_section = match app.settings_section {
+                SettingsSection::States => SettingsSection::Groups,
+                SettingsSection::Groups => SettingsSection::States,
+            };
+        }
+        KeyCode::Up => {
+            *selected = selected.saturating_sub(1);
+        }
+        KeyCode::Down => {
+            if *selected + 1 < len {
+                *selected += 1;
+            }
+        }
+        KeyCode::Left => app.cycle_settings_color(false),
+        KeyCode::Right => app.cycle_settings_color(true),
+        KeyCode::Char('a') => app.start_settings_input(false),
+        KeyCode::Enter | KeyCode::Char('r') => app.start_settings_input(true),
+        KeyCode::Char('d') | KeyCode::Delete => app.remove_settings_item(),
+        _ => {}
+    }
+
+    Ok(())
+}
@@ -1,7 +1,7 @@
 use chrono::{DateTime, Local, NaiveDate};
 use ratatui::style::Color;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::path::PathBuf;
 
 /// A project that can be tagged in log entries
@@ -16,6 +16,49 @@ pub struct Project {
     pub status: String,
     #[serde(default)]
     pub group: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent: Option<String>, // Parent project; `a/b` style names imply one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target_date: Option<NaiveDate>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub milestones: Vec<Milestone>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub status_history: Vec<StatusChange>, // Oldest first
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub archived: bool, // Still resolvable, but hidden from lists and autocomplete
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub fields: BTreeMap<String, String>, // Custom fields declared in config.yml
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StatusChange {
+    pub at: DateTime<Local>,
+    #[serde(default)]
+    pub from: String, // Empty when the project was created
+    pub to: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Milestone {
+    pub name: String,
+    pub date: NaiveDate,
+    #[serde(default)]
+    pub done: bool,
+}
+
+impl Milestone {
+    /// Parse the `YYYY-MM-DD name` form used by the project edit screen
+    pub fn parse(input: &str) -> Option<Self> {
+        let (date, name) = input.trim().split_once(char::is_whitespace)?;
+        let date = NaiveDate::parse_from_str(date, "%Y-%m-%d").ok()?;
+        let name = name.trim();
+        if name.is_empty() {
+            return None;
+        }
+        Some(Self { name: name.to_string(), date, done: false })
+    }
 }
 
 fn default_status() -> String {
@@ -30,10 +73,75 @@ impl Project {
             description: Some("A project to create a new look on our website".to_string()),
             status: "open".to_string(),
             group: String::new(),
+            parent: None,
+            target_date: None,
+            milestones: Vec::new(),
+            status_history: Vec::new(),
+            archived: false,
+            fields: BTreeMap::new(),
+        }
+    }
+
+    /// The parent project: the explicit `parent`, or the path prefix of an `a/b` style name
+    pub fn parent_name(&self) -> Option<String> {
+        self.parent
+            .clone()
+            .filter(|p| !p.is_empty())
+            .or_else(|| self.name.rsplit_once('/').map(|(parent, _)| parent.to_string()))
+    }
+
+    /// Change the status, recording the change in the history
+    pub fn set_status(&mut self, status: &str, reason: Option<String>) {
+        if self.status == status {
+            return;
         }
+        self.status_history.push(StatusChange {
+            at: Local::now(),
+            from: std::mem::replace(&mut self.status, status.to_string()),
+            to: status.to_string(),
+            reason: reason.filter(|r| !r.trim().is_empty()),
+        });
+    }
+
+    /// The nearest open deadline: the target date or an unfinished milestone, overdue ones included
+    pub fn next_deadline(&self) -> Option<NaiveDate> {
+        self.milestones
+            .iter()
+            .filter(|m| !m.done)
+            .map(|m| m.date)
+            .chain(self.target_date)
+            .min()
     }
 }
 
+/// The chain of ancestors of a project tag, nearest first
+///
+/// Registered projects use their parent; unregistered tags like `infra/k8s` fall back
+/// to their path prefix.
+pub fn project_ancestors(projects: &[Project], tag: &str) -> Vec<String> {
+    let mut ancestors: Vec<String> = Vec::new();
+    let mut current = tag.to_string();
+    loop {
+        let parent = match projects.iter().find(|p| p.name == current) {
+            Some(project) => project.parent_name(),
+            None => current.rsplit_once('/').map(|(parent, _)| parent.to_string()),
+        };
+        match parent {
+            // Stop on cycles in hand-edited parents
+            Some(parent) if parent != tag && !ancestors.contains(&parent) => {
+                ancestors.push(parent.clone());
+                current = parent;
+            }
+            _ => return ancestors,
+        }
+    }
+}
+
+/// Whether a project tag is the given project or one of its sub-projects
+pub fn project_within(projects: &[Project], tag: &str, project: &str) -> bool {
+    tag == project || project_ancestors(projects, tag).iter().any(|a| a == project)
+}
+
 /// A person that can be tagged in log entries
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Person {
@@ -46,6 +154,10 @@ pub struct Person {
     pub tel: Option<String>,
     #[serde(default)]
     pub company: Option<String>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub archived: bool, // Still resolvable, but hidden from lists and autocomplete
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub fields: BTreeMap<String, String>, // Custom fields declared in config.yml
 }
 
 impl Person {
@@ -56,10 +168,196 @@ impl Person {
             email: Some("john@example.com".to_string()),
             tel: Some("555 123 3333".to_string()),
             company: Some("foo works".to_string()),
+            archived: false,
+            fields: BTreeMap::new(),
         }
     }
 }
 
+/// A reusable log entry template loaded from the templates directory
+///
+/// Templates are plain text with `{{...}}` placeholders:
+/// - `{{date}}`, `{{time}}`, `{{weekday}}` expand from the entry timestamp
+/// - `{{cursor}}` marks where the editor cursor is placed
+/// - `{{person}}`, `{{project}}` (optionally `{{person:Label}}`) prompt for a tag
+/// - `{{prompt:Label}}` prompts for free text
+/// - `{{todos:#tag}}`, `{{todos:@tag}}` or `{{todos:person}}` list open todos for a tag
+#[derive(Debug, Clone)]
+pub struct LogTemplate {
+    pub name: String,
+    pub content: String,
+}
+
+/// The kind of value a template field prompts for
+#[derive(Debug, Clone, PartialEq)]
+pub enum TemplateFieldKind {
+    Person,
+    Project,
+    Text,
+}
+
+/// A value the user is asked for before a template is expanded
+#[derive(Debug, Clone, PartialEq)]
+pub struct TemplateField {
+    pub key: String, // Placeholder body, e.g. "person:Attendee"
+    pub label: String,
+    pub kind: TemplateFieldKind,
+}
+
+impl TemplateField {
+    /// Parse a placeholder body into a prompted field, if it is one
+    fn parse(body: &str) -> Option<Self> {
+        let (head, label) = match body.split_once(':') {
+            Some((head, label)) => (head, Some(label.trim())),
+            None => (body, None),
+        };
+        let (kind, default_label) = match head {
+            "person" => (TemplateFieldKind::Person, "Person"),
+            "project" => (TemplateFieldKind::Project, "Project"),
+            "prompt" if label.is_some() => (TemplateFieldKind::Text, ""),
+            _ => return None,
+        };
+        Some(Self {
+            key: body.to_string(),
+            label: label.filter(|l| !l.is_empty()).unwrap_or(default_label).to_string(),
+            kind,
+        })
+    }
+}
+
+impl LogTemplate {
+    pub fn examples() -> Vec<Self> {
+        vec![
+            Self {
+                name: "standup".to_string(),
+                content: "Standup {{weekday}} {{date}}\n\nYesterday:\n{{cursor}}\n\nToday:\n\nBlockers:\n".to_string(),
+            },
+            Self {
+                name: "one-on-one".to_string(),
+                content: "1:1 with {{person}} - {{date}}\n\nOpen items:\n{{todos:person}}\n\nNotes:\n{{cursor}}\n".to_string(),
+            },
+            Self {
+                name: "incident".to_string(),
+                content: "Incident {{date}} {{time}} {{project:Affected project}}\n\nSummary: {{prompt:Summary}}\n\nTimeline:\n{{cursor}}\n\nFollow-ups:\n".to_string(),
+            },
+        ]
+    }
+
+    /// Find all `{{...}}` placeholders as (start byte, end byte, body)
+    fn placeholders(&self) -> Vec<(usize, usize, &str)> {
+        let mut found = Vec::new();
+        let mut offset = 0;
+        while let Some(start) = self.content[offset..].find("{{") {
+            let start = offset + start;
+            match self.content[start + 2..].find("}}") {
+                Some(len) => {
+                    let end = start + 2 + len + 2;
+                    found.push((start, end, self.content[start + 2..end - 2].trim()));
+                    offset = end;
+                }
+                None => break,
+            }
+        }
+        found
+    }
+
+    /// Get the fields the user must fill in, in order of first appearance
+    pub fn fields(&self) -> Vec<TemplateField> {
+        let mut fields: Vec<TemplateField> = Vec::new();
+        for (_, _, body) in self.placeholders() {
+            let field_body = body.strip_prefix("todos:").unwrap_or(body);
+            if let Some(field) = TemplateField::parse(field_body) {
+                if !fields.iter().any(|f| f.key == field.key) {
+                    fields.push(field);
+                }
+            }
+        }
+        fields
+    }
+
+    /// Expand the template, returning the content and the cursor position in chars
+    pub fn expand(
+        &self,
+        timestamp: DateTime<Local>,
+        values: &HashMap<String, String>,
+        todos: &[Todo],
+    ) -> (String, usize) {
+        let mut output = String::new();
+        let mut cursor = None;
+        let mut last = 0;
+
+        for (start, end, body) in self.placeholders() {
+            output.push_str(&self.content[last..start]);
+            last = end;
+
+            let value = match body {
+                "date" => timestamp.format("%Y-%m-%d").to_string(),
+                "time" => timestamp.format("%H:%M").to_string(),
+                "weekday" => timestamp.format("%A").to_string(),
+                "cursor" => {
+                    cursor.get_or_insert(output.chars().count());
+                    String::new()
+                }
+                _ => {
+                    if let Some(tag) = body.strip_prefix("todos:") {
+                        Self::open_todos_for(tag, values, todos)
+                    } else if let Some(field) = TemplateField::parse(body) {
+                        Self::field_value(&field, values)
+                    } else {
+                        // Leave unknown placeholders untouched
+                        self.content[start..end].to_string()
+                    }
+                }
+            };
+            output.push_str(&value);
+        }
+        output.push_str(&self.content[last..]);
+
+        let cursor = cursor.unwrap_or_else(|| output.chars().count());
+        (output, cursor)
+    }
+
+    /// Format a prompted field's value as it should appear in the log
+    fn field_value(field: &TemplateField, values: &HashMap<String, String>) -> String {
+        let value = values.get(&field.key).map(|v| v.as_str()).unwrap_or("");
+        if value.is_empty() {
+            return String::new();
+        }
+        match field.kind {
+            TemplateFieldKind::Person => format!("@{}", value),
+            TemplateFieldKind::Project => format!("#{}", value),
+            TemplateFieldKind::Text => value.to_string(),
+        }
+    }
+
+    /// List open todos for a literal tag (`#x`/`@x`) or a prompted field's tag
+    fn open_todos_for(tag: &str, values: &HashMap<String, String>, todos: &[Todo]) -> String {
+        let tag = match TemplateField::parse(tag) {
+            Some(field) => Self::field_value(&field, values),
+            None => tag.to_string(),
+        };
+
+        let matching: Vec<&Todo> = if let Some(project) = tag.strip_prefix('#') {
+            todos.iter().filter(|t| !t.completed && t.projects.iter().any(|p| p == project)).collect()
+        } else if let Some(person) = tag.strip_prefix('@') {
+            todos.iter().filter(|t| !t.completed && t.people.iter().any(|p| p == person)).collect()
+        } else {
+            Vec::new()
+        };
+
+        if matching.is_empty() {
+            return "(no open todos)".to_string();
+        }
+
+        // Listed as bullets rather than [] so the todos are not duplicated
+        matching
+            .iter()
+            .map(|t| format!("- {}", t.text))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
 /// A log entry containing text, project/people tags, and todos
 #[derive(Debug, Clone)]
 pub struct LogEntry {
@@ -68,8 +366,7 @@ pub struct LogEntry {
     pub projects: Vec<String>,
     pub people: Vec<String>,
     pub todos: Vec<Todo>,
-    #[allow(dead_code)] // Planned feature for file attachments
-    pub attachments: Vec<PathBuf>,
+    pub attachments: Vec<PathBuf>, // Files stored next to log.txt, filled in by Storage
     pub file_path: PathBuf,
 }
 
@@ -104,7 +401,7 @@ impl LogEntry {
             }
         }
 
-        // Extract projects (words starting with #)
+        // Extract projects (words starting with #, `/` separating sub-projects)
         for word in content.split_whitespace() {
             if word.starts_with('#') && word.len() > 1 {
                 let project = word[1..].trim_matches(|c: char| !c.is_alphanumeric() && c != '-' && c != '_');
@@ -124,24 +421,13 @@ impl LogEntry {
             }
         }
 
-        // Extract todos (lines starting with [] or [x])
+        // Extract todos (lines starting with [], [/], [w] or [x])
         for (line_num, line) in content.lines().enumerate() {
-            let trimmed = line.trim();
-            if trimmed.starts_with("[]") {
-                let text = trimmed[2..].trim().to_string();
-                entry.todos.push(Todo {
-                    text,
-                    completed: false,
-                    line_number: line_num,
-                    projects: entry.projects.clone(),
-                    people: entry.people.clone(),
-                    log_path: entry.file_path.clone(),
-                });
-            } else if trimmed.starts_with("[x]") || trimmed.starts_with("[X]") {
-                let text = trimmed[3..].trim().to_string();
+            if let Some((state, rest)) = TodoState::parse(line.trim()) {
                 entry.todos.push(Todo {
-                    text,
-                    completed: true,
+                    text: rest.trim().to_string(),
+                    completed: state == TodoState::Done,
+                    state,
                     line_number: line_num,
                     projects: entry.projects.clone(),
                     people: entry.people.clone(),
@@ -175,11 +461,205 @@ impl Default for LogEntry {
     }
 }
 
+/// A file waiting to be copied into a log entry directory
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(from = "AttachmentDef")]
+pub struct Attachment {
+    pub source: PathBuf,
+    pub name: String, // File name to use in the entry directory
+}
+
+/// Drafts saved before attachments could be renamed list them as bare paths
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum AttachmentDef {
+    Path(PathBuf),
+    Full { source: PathBuf, name: String },
+}
+
+impl From<AttachmentDef> for Attachment {
+    fn from(def: AttachmentDef) -> Self {
+        match def {
+            AttachmentDef::Path(source) => Self::new(source),
+            AttachmentDef::Full { source, name } => Self { source, name },
+        }
+    }
+}
+
+impl Attachment {
+    pub fn new(source: PathBuf) -> Self {
+        let name = source
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| "attachment".to_string());
+        Self { source, name }
+    }
+}
+
+/// A reference to an attachment inside log text: `![[name]]` or `[[file:name]]`
+#[derive(Debug, Clone, PartialEq)]
+pub struct AttachmentRef {
+    pub start: usize, // Byte range of the whole reference in the text
+    pub end: usize,
+    pub name: String,
+}
+
+impl AttachmentRef {
+    /// Find all attachment references in a piece of text
+    pub fn find_all(text: &str) -> Vec<AttachmentRef> {
+        let mut refs = Vec::new();
+        let mut pos = 0;
+        while let Some(offset) = text[pos..].find("[[") {
+            let open = pos + offset;
+            let Some(close) = text[open..].find("]]").map(|i| open + i) else {
+                break;
+            };
+            let inner = &text[open + 2..close];
+            let start = if open > 0 && text.as_bytes()[open - 1] == b'!' { Some(open - 1) } else { None };
+
+            // `![[name]]` embeds, `[[file:name]]` links
+            let found = match (start, inner.strip_prefix("file:")) {
+                (_, Some(name)) => Some((open, name)),
+                (Some(start), None) => Some((start, inner)),
+                _ => None,
+            };
+            match found {
+                Some((start, name)) if !name.trim().is_empty() && !name.contains('\n') => {
+                    refs.push(AttachmentRef { start, end: close + 2, name: name.trim().to_string() });
+                    pos = close + 2;
+                }
+                _ => pos = open + 2,
+            }
+        }
+        refs
+    }
+
+    /// Point references at renamed attachments, keeping each one's embed or link form
+    pub fn rename_all(text: &str, renames: &HashMap<String, String>) -> String {
+        let mut result = text.to_string();
+        for r in Self::find_all(text).into_iter().rev() {
+            if let Some(new_name) = renames.get(&r.name) {
+                let syntax = if text[r.start..].starts_with('!') {
+                    format!("![[{}]]", new_name)
+                } else {
+                    format!("[[file:{}]]", new_name)
+                };
+                result.replace_range(r.start..r.end, &syntax);
+            }
+        }
+        result
+    }
+
+    /// The reference syntax for a file name: images are embedded, anything else linked
+    pub fn syntax_for(name: &str) -> String {
+        let ext = std::path::Path::new(name)
+            .extension()
+            .map(|e| e.to_string_lossy().to_lowercase())
+            .unwrap_or_default();
+        if matches!(ext.as_str(), "png" | "jpg" | "jpeg" | "gif" | "svg" | "webp" | "bmp") {
+            format!("![[{}]]", name)
+        } else {
+            format!("[[file:{}]]", name)
+        }
+    }
+}
+
+/// UI state remembered between sessions (state.yml)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct UiState {
+    #[serde(default)]
+    pub last_attach_dir: Option<PathBuf>,
+    #[serde(default)]
+    pub recent_dirs: Vec<PathBuf>, // Most recent first
+    #[serde(default)]
+    pub project_filter: ProjectFilter,
+    #[serde(default)]
+    pub people_filter: PeopleFilter,
+}
+
+impl UiState {
+    const MAX_RECENT_DIRS: usize = 10;
+
+    /// Record a directory files were attached from
+    pub fn remember_dir(&mut self, dir: PathBuf) {
+        self.recent_dirs.retain(|d| d != &dir);
+        self.recent_dirs.insert(0, dir.clone());
+        self.recent_dirs.truncate(Self::MAX_RECENT_DIRS);
+        self.last_attach_dir = Some(dir);
+    }
+}
+
+/// An unsaved log entry kept in the drafts directory so it survives crashes
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Draft {
+    pub timestamp: DateTime<Local>,
+    pub content: String,
+    #[serde(default)]
+    pub cursor: usize,
+    #[serde(default)]
+    pub attachments: Vec<Attachment>,
+    #[serde(skip)]
+    pub file_path: PathBuf,
+}
+
+impl Draft {
+    /// Whether there is nothing in the draft worth keeping
+    pub fn is_empty(&self) -> bool {
+        self.content.trim().is_empty() && self.attachments.is_empty()
+    }
+}
+
+/// Where a todo stands, written as the marker at the start of its line
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TodoState {
+    Open,       // []
+    InProgress, // [/]
+    Waiting,    // [w]
+    Done,       // [x]
+}
+
+impl TodoState {
+    pub const ALL: [TodoState; 4] = [TodoState::Open, TodoState::InProgress, TodoState::Waiting, TodoState::Done];
+
+    pub fn marker(self) -> &'static str {
+        match self {
+            TodoState::Open => "[]",
+            TodoState::InProgress => "[/]",
+            TodoState::Waiting => "[w]",
+            TodoState::Done => "[x]",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            TodoState::Open => "Open",
+            TodoState::InProgress => "In progress",
+            TodoState::Waiting => "Waiting",
+            TodoState::Done => "Done",
+        }
+    }
+
+    /// Split a trimmed line into its todo state and the text after the marker
+    pub fn parse(line: &str) -> Option<(TodoState, &str)> {
+        if let Some(rest) = line.strip_prefix("[]") {
+            return Some((TodoState::Open, rest));
+        }
+        let state = match line.get(..3)? {
+            "[/]" => TodoState::InProgress,
+            "[w]" | "[W]" => TodoState::Waiting,
+            "[x]" | "[X]" => TodoState::Done,
+            _ => return None,
+        };
+        Some((state, &line[3..]))
+    }
+}
+
 /// A todo item extracted from a log entry
 #[derive(Debug, Clone)]
 pub struct Todo {
     pub text: String,
     pub completed: bool,
+    pub state: TodoState, // Finer than `completed`, which is true only when done
     pub line_number: usize,
     pub projects: Vec<String>,
     pub people: Vec<String>,
@@ -189,6 +669,11 @@ pub struct Todo {
 impl Todo {
     /// Toggle the completion status of this todo in the log file
     pub fn toggle(&mut self) -> anyhow::Result<()> {
+        self.set_state(if self.completed { TodoState::Open } else { TodoState::Done })
+    }
+
+    /// Rewrite the marker of this todo in the log file
+    pub fn set_state(&mut self, state: TodoState) -> anyhow::Result<()> {
         use std::fs;
 
         let content = fs::read_to_string(&self.log_path)?;
@@ -196,32 +681,107 @@ impl Todo {
 
         let mut new_lines: Vec<String> = Vec::new();
         for (i, line) in lines.iter().enumerate() {
-            if i == self.line_number {
-                let trimmed = line.trim();
-                if self.completed {
-                    // Change [x] to []
-                    let new_line = line.replace("[x]", "[]").replace("[X]", "[]");
-                    new_lines.push(new_line);
-                } else {
-                    // Change [] to [x]
-                    let new_line = if trimmed.starts_with("[]") {
-                        line.replacen("[]", "[x]", 1)
-                    } else {
-                        line.to_string()
-                    };
-                    new_lines.push(new_line);
+            let trimmed = line.trim_start();
+            match TodoState::parse(trimmed) {
+                Some((_, rest)) if i == self.line_number => {
+                    // Keep the indentation and everything after the marker
+                    let indent = &line[..line.len() - trimmed.len()];
+                    new_lines.push(format!("{}{}{}", indent, state.marker(), rest));
                 }
-            } else {
-                new_lines.push(line.to_string());
+                _ => new_lines.push(line.to_string()),
             }
         }
 
-        fs::write(&self.log_path, new_lines.join("\n"))?;
-        self.completed = !self.completed;
+        let mut updated = new_lines.join("\n");
+        if content.ends_with('\n') {
+            updated.push('\n');
+        }
+        fs::write(&self.log_path, updated)?;
+        self.state = state;
+        self.completed = state == TodoState::Done;
         Ok(())
     }
 }
 
+/// Filter configuration for the project list view
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ProjectFilter {
+    #[serde(default)]
+    pub groups: Vec<String>, // Empty shows all groups
+    #[serde(default)]
+    pub statuses: Option<Vec<String>>, // Unset hides terminal states
+    #[serde(default)]
+    pub text: String, // Matched against name, description and custom fields
+    #[serde(default)]
+    pub fields: FieldFacets,
+    #[serde(default)]
+    pub show_archived: bool,
+}
+
+impl ProjectFilter {
+    pub fn matches(&self, project: &Project, config: &Config) -> bool {
+        if project.archived && !self.show_archived {
+            return false;
+        }
+
+        if !self.groups.is_empty() && !self.groups.contains(&project.group) {
+            return false;
+        }
+
+        if !self.shows_status(&project.status, config) {
+            return false;
+        }
+
+        let text = [project.name.as_str(), project.description.as_deref().unwrap_or("")];
+        search_matches(&self.text, &text, &project.fields) && facets_match(&self.fields, &project.fields)
+    }
+
+    /// Whether projects in a state are shown
+    pub fn shows_status(&self, status: &str, config: &Config) -> bool {
+        match &self.statuses {
+            Some(statuses) => statuses.iter().any(|s| s == status),
+            None => !config.is_terminal_state(status),
+        }
+    }
+
+    /// Show or hide a state, starting from the default of all non-terminal states
+    pub fn toggle_status(&mut self, status: &str, config: &Config) {
+        let statuses = self.statuses.get_or_insert_with(|| {
+            config
+                .allowed_state_names()
+                .into_iter()
+                .filter(|s| !config.is_terminal_state(s))
+                .collect()
+        });
+        if let Some(pos) = statuses.iter().position(|s| s == status) {
+            statuses.remove(pos);
+        } else {
+            statuses.push(status.to_string());
+        }
+    }
+}
+
+/// Filter configuration for the people list view
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PeopleFilter {
+    #[serde(default)]
+    pub text: String, // Matched against names, email, company and custom fields
+    #[serde(default)]
+    pub fields: FieldFacets,
+}
+
+impl PeopleFilter {
+    pub fn matches(&self, person: &Person) -> bool {
+        let text = [
+            person.name.as_str(),
+            person.full_name.as_deref().unwrap_or(""),
+            person.email.as_deref().unwrap_or(""),
+            person.company.as_deref().unwrap_or(""),
+        ];
+        search_matches(&self.text, &text, &person.fields) && facets_match(&self.fields, &person.fields)
+    }
+}
+
 /// Filter configuration for todo list view
 #[derive(Debug, Clone, Default)]
 pub struct TodoFilter {
@@ -231,15 +791,18 @@ pub struct TodoFilter {
 }
 
 impl TodoFilter {
-    pub fn matches(&self, todo: &Todo) -> bool {
+    pub fn matches(&self, todo: &Todo, projects: &[Project]) -> bool {
         // Filter by completion status
         if !self.show_completed && todo.completed {
             return false;
         }
 
-        // Filter by projects (if any selected)
+        // Filter by projects (if any selected), sub-projects included
         if !self.projects.is_empty() {
-            let has_matching_project = todo.projects.iter().any(|p| self.projects.contains(p));
+            let has_matching_project = todo
+                .projects
+                .iter()
+                .any(|p| self.projects.iter().any(|f| project_within(projects, p, f)));
             if !has_matching_project {
                 return false;
             }
@@ -267,10 +830,13 @@ pub struct LogFilter {
 }
 
 impl LogFilter {
-    pub fn matches(&self, entry: &LogEntry) -> bool {
-        // Filter by projects
+    pub fn matches(&self, entry: &LogEntry, projects: &[Project]) -> bool {
+        // Filter by projects, sub-projects included
         if !self.projects.is_empty() {
-            let has_matching_project = entry.projects.iter().any(|p| self.projects.contains(p));
+            let has_matching_project = entry
+                .projects
+                .iter()
+                .any(|p| self.projects.iter().any(|f| project_within(projects, p, f)));
             if !has_matching_project {
                 return false;
             }
@@ -305,25 +871,304 @@ impl LogFilter {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Config {
     pub projects: ProjectConfig,
+    #[serde(default)]
+    pub attachments: AttachmentConfig,
+    #[serde(default, skip_serializing_if = "FieldsConfig::is_empty")]
+    pub fields: FieldsConfig,
+}
+
+/// Extra fields for projects and people, declared per entity in config.yml
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FieldsConfig {
+    #[serde(default)]
+    pub projects: Vec<CustomField>,
+    #[serde(default)]
+    pub people: Vec<CustomField>,
+}
+
+impl FieldsConfig {
+    fn is_empty(&self) -> bool {
+        self.projects.is_empty() && self.people.is_empty()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CustomField {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    #[serde(rename = "type", default)]
+    pub kind: CustomFieldKind,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub options: Vec<String>, // Choices for `enum` fields
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CustomFieldKind {
+    #[default]
+    Text,
+    Url,
+    Date,
+    Enum,
+    Person,  // Tag reference to a person
+    Project, // Tag reference to a project
+}
+
+impl CustomField {
+    pub fn label(&self) -> &str {
+        self.label.as_deref().unwrap_or(&self.name)
+    }
+
+    /// Short description of the expected input
+    pub fn hint(&self) -> String {
+        match self.kind {
+            CustomFieldKind::Text => "text".to_string(),
+            CustomFieldKind::Url => "https://...".to_string(),
+            CustomFieldKind::Date => "YYYY-MM-DD".to_string(),
+            CustomFieldKind::Enum => self.options.join(" | "),
+            CustomFieldKind::Person => "@person".to_string(),
+            CustomFieldKind::Project => "#project".to_string(),
+        }
+    }
+
+    /// Check a value typed for this field, returning it normalized or an error message
+    ///
+    /// An empty value is valid and clears the field.
+    pub fn validate(&self, value: &str, projects: &[Project], people: &[Person]) -> Result<String, String> {
+        let value = value.trim();
+        if value.is_empty() {
+            return Ok(String::new());
+        }
+
+        match self.kind {
+            CustomFieldKind::Text => Ok(value.to_string()),
+            CustomFieldKind::Url => crate::validation::check_url(value)
+                .map(|_| value.to_string())
+                .map_err(|error| format!("{}: {}", self.label(), error)),
+            CustomFieldKind::Date => NaiveDate::parse_from_str(value, "%Y-%m-%d")
+                .map(|d| d.format("%Y-%m-%d").to_string())
+                .map_err(|_| format!("{} must be a date (YYYY-MM-DD)", self.label())),
+            CustomFieldKind::Enum => self
+                .options
+                .iter()
+                .find(|o| o.eq_ignore_ascii_case(value))
+                .cloned()
+                .ok_or_else(|| format!("{} must be one of: {}", self.label(), self.options.join(", "))),
+            CustomFieldKind::Person => {
+                let name = value.trim_start_matches('@');
+                if people.iter().any(|p| p.name == name) {
+                    Ok(name.to_string())
+                } else {
+                    Err(format!("{}: unknown person '{}'", self.label(), name))
+                }
+            }
+            CustomFieldKind::Project => {
+                let name = value.trim_start_matches('#');
+                if projects.iter().any(|p| p.name == name) {
+                    Ok(name.to_string())
+                } else {
+                    Err(format!("{}: unknown project '{}'", self.label(), name))
+                }
+            }
+        }
+    }
+
+    /// How a stored value is displayed, with the tag prefix for references
+    pub fn display(&self, value: &str) -> String {
+        match self.kind {
+            CustomFieldKind::Person => format!("@{}", value),
+            CustomFieldKind::Project => format!("#{}", value),
+            _ => value.to_string(),
+        }
+    }
+
+    /// The values a filter can choose from: an enum's options, or the tags a reference
+    /// field holds in `entries`. Other kinds are left to the text search.
+    pub fn facet_values<'a>(&self, entries: impl Iterator<Item = &'a BTreeMap<String, String>>) -> Vec<String> {
+        match self.kind {
+            CustomFieldKind::Enum => self.options.clone(),
+            CustomFieldKind::Person | CustomFieldKind::Project => {
+                let mut values: Vec<String> = entries.filter_map(|fields| fields.get(&self.name).cloned()).collect();
+                values.sort();
+                values.dedup();
+                values
+            }
+            _ => Vec::new(),
+        }
+    }
+}
+
+/// Values chosen per enum or tag-reference custom field; an entry must have one of them
+pub type FieldFacets = BTreeMap<String, Vec<String>>;
+
+/// Whether an entry's custom fields have one of the chosen values for every faceted field
+pub fn facets_match(facets: &FieldFacets, fields: &BTreeMap<String, String>) -> bool {
+    facets
+        .iter()
+        .all(|(name, values)| values.is_empty() || fields.get(name).is_some_and(|v| values.contains(v)))
+}
+
+/// Choose or unchoose one value of a faceted field
+pub fn toggle_facet(facets: &mut FieldFacets, name: &str, value: &str) {
+    let values = facets.entry(name.to_string()).or_default();
+    if let Some(pos) = values.iter().position(|v| v == value) {
+        values.remove(pos);
+    } else {
+        values.push(value.to_string());
+    }
+    if values.is_empty() {
+        facets.remove(name);
+    }
+}
+
+/// Match a search like `website tier:gold` against an entry's text and custom fields
+///
+/// `name:value` terms match the custom field of that name; other words must each appear
+/// in the text or in one of the field values. Matching ignores case.
+pub fn search_matches(query: &str, text: &[&str], fields: &BTreeMap<String, String>) -> bool {
+    query.split_whitespace().all(|term| {
+        let term = term.to_lowercase();
+        if let Some((key, value)) = term.split_once(':') {
+            if let Some((_, field_value)) = fields.iter().find(|(name, _)| name.to_lowercase() == key) {
+                return field_value.to_lowercase().contains(value);
+            }
+        }
+        text.iter().any(|t| t.to_lowercase().contains(&term))
+            || fields.values().any(|v| v.to_lowercase().contains(&term))
+    })
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AttachmentConfig {
+    /// Store attachment contents once under blobs/ and hard-link them into entry directories
+    #[serde(default)]
+    pub dedupe: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ProjectConfig {
-    pub allowed_states: HashMap<String, String>, // state name -> color
+    pub allowed_states: BTreeMap<String, StateConfig>, // state name -> settings
     pub groups: Vec<String>,
 }
 
+/// A project state: its colour, whether it ends the project, and which states may follow it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(from = "StateConfigDef")]
+pub struct StateConfig {
+    pub color: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub terminal: Option<bool>, // Unset falls back to names like "closed" or "done"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub transitions: Option<Vec<Transition>>, // Unset allows moving to any state
+}
+
+/// A state can also be written as just its colour, as older configs do
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum StateConfigDef {
+    Color(String),
+    Full {
+        color: String,
+        #[serde(default)]
+        terminal: Option<bool>,
+        #[serde(default)]
+        transitions: Option<Vec<Transition>>,
+    },
+}
+
+impl From<StateConfigDef> for StateConfig {
+    fn from(def: StateConfigDef) -> Self {
+        match def {
+            StateConfigDef::Color(color) => Self { color, terminal: None, transitions: None },
+            StateConfigDef::Full { color, terminal, transitions } => Self { color, terminal, transitions },
+        }
+    }
+}
+
+impl StateConfig {
+    /// A state with just a colour, open to and from every other state
+    pub fn with_color(color: &str) -> Self {
+        Self { color: color.to_string(), terminal: None, transitions: None }
+    }
+
+    fn new(color: &str, terminal: bool, transitions: Vec<Transition>) -> Self {
+        Self {
+            color: color.to_string(),
+            terminal: Some(terminal),
+            transitions: Some(transitions),
+        }
+    }
+}
+
+/// An allowed next state, written as a plain name or as `{ to: name, comment: true }`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(from = "TransitionDef")]
+pub struct Transition {
+    pub to: String,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub comment: bool, // Require a comment, which is saved as a log entry
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum TransitionDef {
+    To(String),
+    Full {
+        to: String,
+        #[serde(default)]
+        comment: bool,
+    },
+}
+
+impl From<TransitionDef> for Transition {
+    fn from(def: TransitionDef) -> Self {
+        match def {
+            TransitionDef::To(to) => Self { to, comment: false },
+            TransitionDef::Full { to, comment } => Self { to, comment },
+        }
+    }
+}
+
+impl Transition {
+    fn to(state: &str) -> Self {
+        Self { to: state.to_string(), comment: false }
+    }
+
+    fn with_comment(state: &str) -> Self {
+        Self { to: state.to_string(), comment: true }
+    }
+}
+
+/// The colour names understood for states, in the order the settings screen offers them
+pub const STATE_COLORS: &[&str] = &[
+    "red", "green", "yellow", "blue", "magenta", "cyan", "white", "gray", "darkgray",
+    "lightred", "lightgreen", "lightyellow", "lightblue", "lightmagenta", "lightcyan", "black",
+];
+
 impl Default for Config {
     fn default() -> Self {
-        let mut allowed_states = HashMap::new();
-        allowed_states.insert("open".to_string(), "blue".to_string());
-        allowed_states.insert("closed".to_string(), "red".to_string());
+        let mut allowed_states = BTreeMap::new();
+        allowed_states.insert(
+            "open".to_string(),
+            StateConfig::new("blue", false, vec![Transition::to("hold"), Transition::with_comment("closed")]),
+        );
+        allowed_states.insert(
+            "hold".to_string(),
+            StateConfig::new("yellow", false, vec![Transition::to("open"), Transition::with_comment("closed")]),
+        );
+        allowed_states.insert(
+            "closed".to_string(),
+            StateConfig::new("red", true, vec![Transition::with_comment("open")]),
+        );
 
         Self {
             projects: ProjectConfig {
                 allowed_states,
                 groups: vec![],
-            }
+            },
+            attachments: AttachmentConfig::default(),
+            fields: FieldsConfig::default(),
         }
     }
 }
@@ -336,22 +1181,109 @@ impl Config {
         states
     }
 
+    /// The state new projects start in: the first one that isn't terminal
+    pub fn initial_state(&self) -> String {
+        let states = self.allowed_state_names();
+        states
+            .iter()
+            .find(|s| !self.is_terminal_state(s))
+            .or(states.first())
+            .cloned()
+            .unwrap_or_else(|| "open".to_string())
+    }
+
+    /// Whether a state means the project is finished, from its `terminal` flag
+    pub fn is_terminal_state(&self, state: &str) -> bool {
+        match self.projects.allowed_states.get(state).and_then(|s| s.terminal) {
+            Some(terminal) => terminal,
+            // States without the flag are judged by their name
+            None => matches!(
+                state.to_lowercase().as_str(),
+                "close" | "closed" | "done" | "cancelled" | "canceled" | "archived"
+            ),
+        }
+    }
+
+    /// The states a project may move to from `current`, starting with `current` itself
+    pub fn next_states(&self, current: &str) -> Vec<String> {
+        let transitions = self.projects.allowed_states.get(current).and_then(|s| s.transitions.as_ref());
+        let Some(transitions) = transitions else {
+            return self.allowed_state_names();
+        };
+
+        let mut states = vec![current.to_string()];
+        for transition in transitions {
+            if self.projects.allowed_states.contains_key(&transition.to) && !states.contains(&transition.to) {
+                states.push(transition.to.clone());
+            }
+        }
+        states
+    }
+
+    /// Whether moving from one state to another needs a comment
+    pub fn transition_requires_comment(&self, from: &str, to: &str) -> bool {
+        self.projects
+            .allowed_states
+            .get(from)
+            .and_then(|s| s.transitions.as_ref())
+            .is_some_and(|transitions| transitions.iter().any(|t| t.to == to && t.comment))
+    }
+
     /// Get list of allowed groups
     pub fn allowed_groups(&self) -> Vec<String> {
         self.projects.groups.clone()
     }
 
+    /// Rename a state, keeping the transitions that lead to it
+    pub fn rename_state(&mut self, from: &str, to: &str) {
+        if let Some(state) = self.projects.allowed_states.remove(from) {
+            self.projects.allowed_states.insert(to.to_string(), state);
+        }
+        for state in self.projects.allowed_states.values_mut() {
+            for transition in state.transitions.iter_mut().flatten() {
+                if transition.to == from {
+                    transition.to = to.to_string();
+                }
+            }
+        }
+    }
+
+    /// Remove a state along with the transitions that lead to it
+    pub fn remove_state(&mut self, name: &str) {
+        self.projects.allowed_states.remove(name);
+        for state in self.projects.allowed_states.values_mut() {
+            if let Some(transitions) = state.transitions.as_mut() {
+                transitions.retain(|t| t.to != name);
+            }
+        }
+    }
+
+    /// Step a state's colour through the palette
+    pub fn cycle_state_color(&mut self, name: &str, forward: bool) {
+        let Some(state) = self.projects.allowed_states.get_mut(name) else {
+            return;
+        };
+        let current = STATE_COLORS.iter().position(|c| c.eq_ignore_ascii_case(&state.color));
+        let len = STATE_COLORS.len();
+        let next = match (current, forward) {
+            (Some(i), true) => (i + 1) % len,
+            (Some(i), false) => (i + len - 1) % len,
+            (None, _) => 0,
+        };
+        state.color = STATE_COLORS[next].to_string();
+    }
+
     /// Get the color for a given state
     pub fn get_state_color(&self, state: &str) -> Color {
-        if let Some(color_name) = self.projects.allowed_states.get(state) {
-            Self::parse_color(color_name)
+        if let Some(state_config) = self.projects.allowed_states.get(state) {
+            Self::parse_color(&state_config.color)
         } else {
             Color::Yellow // Default color for unknown states
         }
     }
 
     /// Parse a color name string to a ratatui Color
-    fn parse_color(color_name: &str) -> Color {
+    pub fn parse_color(color_name: &str) -> Color {
         match color_name.to_lowercase().as_str() {
             "black" => Color::Black,
             "red" => Color::Red,
@@ -373,3 +1305,85 @@ impl Config {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rename_all_rebuilds_references() {
+        let renames = HashMap::from([("a.png".to_string(), "b.png".to_string())]);
+        assert_eq!(
+            AttachmentRef::rename_all("see ![[a.png]] and [[file:a.png]], not [[file:c.png]]", &renames),
+            "see ![[b.png]] and [[file:b.png]], not [[file:c.png]]"
+        );
+    }
+
+    #[test]
+    fn rename_all_with_name_inside_the_prefix() {
+        for old in ["file", "le", "fi"] {
+            let renames = HashMap::from([(old.to_string(), "notes.txt".to_string())]);
+            let text = format!("[[file:{}]] ![[{}]]", old, old);
+            assert_eq!(AttachmentRef::rename_all(&text, &renames), "[[file:notes.txt]] ![[notes.txt]]");
+        }
+    }
+
+    #[test]
+    fn people_filter_facets_and_text() {
+        let mut person = Person::example();
+        person.fields.insert("team".to_string(), "core".to_string());
+        let mut filter = PeopleFilter::default();
+        assert!(filter.matches(&person));
+
+        toggle_facet(&mut filter.fields, "team", "infra");
+        assert!(!filter.matches(&person));
+        toggle_facet(&mut filter.fields, "team", "core");
+        assert!(filter.matches(&person));
+        toggle_facet(&mut filter.fields, "team", "infra");
+        toggle_facet(&mut filter.fields, "team", "core");
+        assert!(filter.fields.is_empty());
+
+        filter.text = "team:core smith".to_string();
+        assert!(filter.matches(&person));
+        filter.text = "team:infra".to_string();
+        assert!(!filter.matches(&person));
+    }
+
+    #[test]
+    fn todo_state_markers() {
+        assert_eq!(TodoState::parse("[] open"), Some((TodoState::Open, " open")));
+        assert_eq!(TodoState::parse("[/] started"), Some((TodoState::InProgress, " started")));
+        assert_eq!(TodoState::parse("[w] blocked"), Some((TodoState::Waiting, " blocked")));
+        assert_eq!(TodoState::parse("[W] blocked"), Some((TodoState::Waiting, " blocked")));
+        assert_eq!(TodoState::parse("[x] done"), Some((TodoState::Done, " done")));
+        assert_eq!(TodoState::parse("[X] done"), Some((TodoState::Done, " done")));
+
+        assert_eq!(TodoState::parse("[wip] not a todo"), None);
+        assert_eq!(TodoState::parse("[ ] not a todo"), None);
+        assert_eq!(TodoState::parse("plain text"), None);
+        assert_eq!(TodoState::parse("[x"), None);
+    }
+
+    #[test]
+    fn set_state_keeps_the_line_around_the_marker() {
+        let dir = std::env::temp_dir().join(format!("todo-log-test-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("log.txt");
+        let content = "Notes\n  [] write spec #alpha\n\t[X] ship it\n";
+        std::fs::write(&path, content).unwrap();
+
+        let mut todos = LogEntry::parse(content, path.clone()).todos;
+        todos[0].set_state(TodoState::InProgress).unwrap();
+        todos[1].set_state(TodoState::Waiting).unwrap();
+        let after = std::fs::read_to_string(&path).unwrap();
+        todos[0].set_state(TodoState::Open).unwrap();
+        todos[1].set_state(TodoState::Done).unwrap();
+        let restored = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(after, "Notes\n  [/] write spec #alpha\n\t[w] ship it\n");
+        assert_eq!(restored, "Notes\n  [] write spec #alpha\n\t[x] ship it\n");
+        assert_eq!(todos[0].state, TodoState::Open);
+        assert!(todos[1].completed);
+    }
+}
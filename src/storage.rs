@@ -1,9 +1,50 @@
-use crate::models::{Config, LogEntry, Person, Project, Todo};
+use crate::models::{Attachment, AttachmentRef, Config, Draft, LogEntry, LogTemplate, Person, Project, Todo, UiState};
 use anyhow::{Context, Result};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_yaml::Value;
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fs;
-use std::path::PathBuf;
+use std::io;
+use std::path::{Path, PathBuf};
 use walkdir::WalkDir;
 
+/// Name of the per-entry file mapping attachment names to blob hashes
+const MANIFEST_FILE: &str = "attachments.yml";
+
+/// Attachment extensions treated as plain text for searching
+const TEXT_ATTACHMENT_EXTENSIONS: &[&str] = &["txt", "md", "markdown", "csv", "tsv", "log", "json", "yml", "yaml", "xml"];
+
+/// Only the start of huge text attachments is searched
+const MAX_ATTACHMENT_TEXT_BYTES: u64 = 1024 * 1024;
+
+/// Attachment name -> SHA-256 of its contents
+type Manifest = BTreeMap<String, String>;
+
+/// The new text of a list file of named records, changing only the records that differ.
+/// Comments and layout are kept; the file is rewritten whole if that can't be done safely.
+fn records_yaml<T: Serialize + DeserializeOwned>(path: &Path, records: &[T]) -> Result<String> {
+    let new = serde_yaml::to_value(records)?;
+    let old = fs::read_to_string(path).unwrap_or_default();
+
+    // Compare records in the form they'd be written, so defaults don't count as changes
+    let normalize = |value: &Value| serde_yaml::from_value::<T>(value.clone()).ok().and_then(|r| serde_yaml::to_value(r).ok());
+    let edited = crate::yaml_edit::update_records(&old, &new, "name", normalize)
+        .filter(|text| serde_yaml::from_str::<Vec<T>>(text).ok().and_then(|r| serde_yaml::to_value(r).ok()).as_ref() == Some(&new));
+
+    match edited {
+        Some(text) => Ok(text),
+        None => Ok(serde_yaml::to_string(records)?),
+    }
+}
+
+/// What a blob store maintenance run did
+#[derive(Debug, Default)]
+pub struct BlobStats {
+    pub files: usize,
+    pub bytes: u64,
+}
+
 /// Storage manager for the todo-log application
 pub struct Storage {
     pub base_dir: PathBuf,
@@ -37,6 +78,37 @@ impl Storage {
         self.base_dir.join("config.yml")
     }
 
+    pub fn state_file(&self) -> PathBuf {
+        self.base_dir.join("state.yml")
+    }
+
+    /// Markdown notes of a project; sub-projects nest like their names
+    pub fn project_notes_file(&self, project: &str) -> PathBuf {
+        self.base_dir.join("projects").join(format!("{}.md", project))
+    }
+
+    pub fn templates_dir(&self) -> PathBuf {
+        self.base_dir.join("templates")
+    }
+
+    pub fn drafts_dir(&self) -> PathBuf {
+        self.base_dir.join("drafts")
+    }
+
+    pub fn blobs_dir(&self) -> PathBuf {
+        self.base_dir.join("blobs")
+    }
+
+    fn blob_path(&self, hash: &str) -> PathBuf {
+        self.blobs_dir().join(&hash[..2]).join(hash)
+    }
+
+    /// Get a fresh path for a new draft file
+    pub fn new_draft_path(&self) -> PathBuf {
+        let name = chrono::Local::now().format("%Y-%m-%d_%H-%M-%S%.3f");
+        self.drafts_dir().join(format!("{}.yml", name))
+    }
+
     /// Initialize the storage directory with example files if it doesn't exist
     pub fn initialize(&self) -> Result<()> {
         if !self.base_dir.exists() {
@@ -62,6 +134,16 @@ impl Storage {
                 .context("Failed to create config.yml")?;
         }
 
+        // Example templates, also for installs from before templates existed
+        if !self.templates_dir().exists() {
+            fs::create_dir_all(self.templates_dir())
+                .context("Failed to create templates directory")?;
+            for template in LogTemplate::examples() {
+                fs::write(self.templates_dir().join(format!("{}.txt", template.name)), template.content)
+                    .context("Failed to create example template")?;
+            }
+        }
+
         Ok(())
     }
 
@@ -85,12 +167,11 @@ impl Storage {
         Ok(projects)
     }
 
-    /// Save projects to the projects.yml file
+    /// Save projects to the projects.yml file, keeping its formatting
     pub fn save_projects(&self, projects: &[Project]) -> Result<()> {
-        let path = self.projects_file();
-        let yaml = serde_yaml::to_string(projects)
+        let yaml = records_yaml(&self.projects_file(), projects)
             .context("Failed to serialize projects")?;
-        fs::write(&path, yaml)
+        fs::write(self.projects_file(), yaml)
             .context("Failed to write projects.yml")?;
         Ok(())
     }
@@ -115,16 +196,59 @@ impl Storage {
         Ok(people)
     }
 
-    /// Save people to the people.yml file
+    /// Save people to the people.yml file, keeping its formatting
     pub fn save_people(&self, people: &[Person]) -> Result<()> {
-        let path = self.people_file();
-        let yaml = serde_yaml::to_string(people)
+        let yaml = records_yaml(&self.people_file(), people)
             .context("Failed to serialize people")?;
-        fs::write(&path, yaml)
+        fs::write(self.people_file(), yaml)
             .context("Failed to write people.yml")?;
         Ok(())
     }
 
+    /// Load the notes of a project, if it has any
+    pub fn load_project_notes(&self, project: &str) -> Option<String> {
+        fs::read_to_string(self.project_notes_file(project))
+            .ok()
+            .filter(|notes| !notes.trim().is_empty())
+    }
+
+    /// Save the notes of a project, removing the file when they're emptied
+    pub fn save_project_notes(&self, project: &str, notes: &str) -> Result<()> {
+        let path = self.project_notes_file(project);
+        if notes.trim().is_empty() {
+            return self.delete_project_notes(project);
+        }
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).context("Failed to create projects directory")?;
+        }
+        fs::write(&path, notes)
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+        Ok(())
+    }
+
+    /// Move a project's notes along when it is renamed
+    pub fn rename_project_notes(&self, from: &str, to: &str) -> Result<()> {
+        let old = self.project_notes_file(from);
+        if !old.exists() {
+            return Ok(());
+        }
+        let new = self.project_notes_file(to);
+        if let Some(dir) = new.parent() {
+            fs::create_dir_all(dir).context("Failed to create projects directory")?;
+        }
+        fs::rename(&old, &new).context("Failed to rename project notes")?;
+        Ok(())
+    }
+
+    /// Remove a project's notes
+    pub fn delete_project_notes(&self, project: &str) -> Result<()> {
+        let path = self.project_notes_file(project);
+        if path.exists() {
+            fs::remove_file(&path).context("Failed to remove project notes")?;
+        }
+        Ok(())
+    }
+
     /// Load configuration from config.yml file
     pub fn load_config(&self) -> Result<Config> {
         let path = self.config_file();
@@ -146,54 +270,229 @@ impl Storage {
         Ok(config)
     }
 
+    /// Save configuration to the config.yml file
+    ///
+    /// Comments and layout are kept; the file is rewritten whole if that can't be done safely.
+    pub fn save_config(&self, config: &Config) -> Result<()> {
+        let new = serde_yaml::to_value(config)
+            .context("Failed to serialize config")?;
+        let old = fs::read_to_string(self.config_file()).unwrap_or_default();
+        let normalize = |value: &Value| serde_yaml::from_value::<Config>(value.clone()).ok().and_then(|c| serde_yaml::to_value(c).ok());
+        let edited = crate::yaml_edit::update_mapping_file(&old, &new, normalize)
+            .filter(|text| serde_yaml::from_str::<Value>(text).ok().as_ref().and_then(normalize).as_ref() == Some(&new));
+
+        let yaml = match edited {
+            Some(text) => text,
+            None => serde_yaml::to_string(config).context("Failed to serialize config")?,
+        };
+        fs::write(self.config_file(), yaml)
+            .context("Failed to write config.yml")?;
+        Ok(())
+    }
+
+    /// Load all log entry templates from the templates directory, sorted by name
+    pub fn load_templates(&self) -> Result<Vec<LogTemplate>> {
+        let dir = self.templates_dir();
+        if !dir.exists() {
+            return Ok(Vec::new());
+        }
+
+        let mut templates = Vec::new();
+        for entry in fs::read_dir(&dir).context("Failed to read templates directory")?.flatten() {
+            let path = entry.path();
+            let name = match path.file_stem().and_then(|n| n.to_str()) {
+                Some(name) if !name.starts_with('.') => name.to_string(),
+                _ => continue,
+            };
+            if path.is_file() {
+                if let Ok(content) = fs::read_to_string(&path) {
+                    templates.push(LogTemplate { name, content });
+                }
+            }
+        }
+
+        templates.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(templates)
+    }
+
+    /// Save a draft to its file in the drafts directory
+    pub fn save_draft(&self, draft: &Draft) -> Result<()> {
+        fs::create_dir_all(self.drafts_dir())
+            .context("Failed to create drafts directory")?;
+        let yaml = serde_yaml::to_string(draft)
+            .context("Failed to serialize draft")?;
+        fs::write(&draft.file_path, yaml)
+            .context("Failed to write draft")?;
+        Ok(())
+    }
+
+    /// Load the remembered UI state, falling back to defaults if missing or unreadable
+    pub fn load_ui_state(&self) -> UiState {
+        fs::read_to_string(self.state_file())
+            .ok()
+            .and_then(|content| serde_yaml::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    /// Save the remembered UI state
+    pub fn save_ui_state(&self, state: &UiState) -> Result<()> {
+        let yaml = serde_yaml::to_string(state)?;
+        fs::write(self.state_file(), yaml)
+            .context("Failed to write state.yml")?;
+        Ok(())
+    }
+
+    /// Load all drafts, newest first
+    pub fn load_drafts(&self) -> Result<Vec<Draft>> {
+        let dir = self.drafts_dir();
+        if !dir.exists() {
+            return Ok(Vec::new());
+        }
+
+        let mut drafts = Vec::new();
+        for entry in fs::read_dir(&dir).context("Failed to read drafts directory")?.flatten() {
+            let path = entry.path();
+            if path.extension().and_then(|e| e.to_str()) != Some("yml") {
+                continue;
+            }
+            if let Ok(content) = fs::read_to_string(&path) {
+                if let Ok(mut draft) = serde_yaml::from_str::<Draft>(&content) {
+                    draft.file_path = path;
+                    drafts.push(draft);
+                }
+            }
+        }
+
+        drafts.sort_by_key(|d| std::cmp::Reverse(d.file_path.clone()));
+        Ok(drafts)
+    }
+
+    /// Delete a draft file if it exists
+    pub fn delete_draft(&self, path: &PathBuf) -> Result<()> {
+        if path.exists() {
+            fs::remove_file(path).context("Failed to delete draft")?;
+        }
+        Ok(())
+    }
+
     /// Save a log entry to disk
-    pub fn save_log_entry(&self, entry: &LogEntry, attachments: &[PathBuf]) -> Result<PathBuf> {
-        let year_dir = self.base_dir.join(format!("log-{}", entry.year()));
-        let entry_dir = year_dir.join(entry.dir_name());
+    ///
+    /// With `dedupe`, attachment contents go to the blob store and the entry directory gets
+    /// hard links (falling back to copies) plus a manifest recording which blob each file is.
+    pub fn save_log_entry(&self, entry: &LogEntry, attachments: &[Attachment], dedupe: bool) -> Result<PathBuf> {
+        // Entries are named by the second they were stamped, so take the next free one
+        // rather than writing over an entry saved in the same second
+        let mut slot = entry.clone();
+        let entry_dir = loop {
+            let dir = self.base_dir.join(format!("log-{}", slot.year())).join(slot.dir_name());
+            if !dir.exists() {
+                break dir;
+            }
+            slot.timestamp += chrono::Duration::seconds(1);
+        };
 
         fs::create_dir_all(&entry_dir)
             .context("Failed to create log entry directory")?;
 
-        let log_file = entry_dir.join("log.txt");
-        fs::write(&log_file, &entry.content)
-            .context("Failed to write log file")?;
-
-        // Copy attachments
+        // Copy attachments, never overwriting a file already in the entry
+        let mut manifest = Self::read_manifest(&entry_dir);
+        let mut renames = HashMap::new();
         for attachment in attachments {
-            if attachment.exists() {
-                if let Some(filename) = attachment.file_name() {
-                    let dest = entry_dir.join(filename);
-                    fs::copy(attachment, &dest)
+            if attachment.source.exists() {
+                let dest = Self::unique_path(&entry_dir, &attachment.name);
+                let written = file_name_string(&dest);
+                if written != attachment.name {
+                    renames.insert(attachment.name.clone(), written);
+                }
+                if dedupe {
+                    let hash = self.store_blob(&attachment.source)?;
+                    self.link_blob(&hash, &dest)?;
+                    manifest.insert(file_name_string(&dest), hash);
+                } else {
+                    fs::copy(&attachment.source, &dest)
                         .context("Failed to copy attachment")?;
                 }
             }
         }
+        if dedupe {
+            Self::write_manifest(&entry_dir, &manifest)?;
+        }
+
+        // References in the text follow any attachment that had to be renamed
+        let log_file = entry_dir.join("log.txt");
+        fs::write(&log_file, AttachmentRef::rename_all(&entry.content, &renames))
+            .context("Failed to write log file")?;
 
         Ok(log_file)
     }
 
-    /// Load all log entries from disk
-    pub fn load_all_logs(&self) -> Result<Vec<LogEntry>> {
-        let mut entries = Vec::new();
+    /// Pick a path in dir for a file name, adding -2, -3, ... before the extension if taken
+    fn unique_path(dir: &Path, name: &str) -> PathBuf {
+        let taken = |p: &Path| p.exists() || p.file_name().is_some_and(|n| n == MANIFEST_FILE);
+        let candidate = dir.join(name);
+        if !taken(&candidate) {
+            return candidate;
+        }
+
+        let path = Path::new(name);
+        let stem = path.file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default();
+        let ext = path.extension().map(|e| format!(".{}", e.to_string_lossy())).unwrap_or_default();
+        (2..)
+            .map(|n| dir.join(format!("{}-{}{}", stem, n, ext)))
+            .find(|p| !taken(p))
+            .unwrap()
+    }
+
+    /// List the attachment files stored in a log entry directory
+    pub fn list_attachments(entry_dir: &Path) -> Vec<PathBuf> {
+        let mut files: Vec<PathBuf> = fs::read_dir(entry_dir)
+            .into_iter()
+            .flatten()
+            .flatten()
+            .map(|e| e.path())
+            .filter(|p| p.is_file() && p.file_name().is_some_and(|n| n != "log.txt" && n != MANIFEST_FILE))
+            .collect();
+        files.sort();
+        files
+    }
+
+    /// Read a text-based attachment for searching, skipping binary files
+    pub fn read_text_attachment(path: &Path) -> Option<String> {
+        let ext = path.extension()?.to_string_lossy().to_lowercase();
+        if !TEXT_ATTACHMENT_EXTENSIONS.contains(&ext.as_str()) {
+            return None;
+        }
+
+        let text = Self::read_text_capped(path)?;
+        if text.contains('\0') {
+            return None; // Binary content despite the extension
+        }
+        Some(text)
+    }
+
+    /// Read at most the first MAX_ATTACHMENT_TEXT_BYTES of a file as text
+    pub fn read_text_capped(path: &Path) -> Option<String> {
+        use std::io::Read;
+
+        let mut bytes = Vec::new();
+        fs::File::open(path).ok()?.take(MAX_ATTACHMENT_TEXT_BYTES).read_to_end(&mut bytes).ok()?;
+        Some(String::from_utf8_lossy(&bytes).into_owned())
+    }
+
+    /// All log entry directories (those containing a log.txt)
+    fn entry_dirs(&self) -> Vec<PathBuf> {
+        let mut dirs = Vec::new();
 
         // Find all log-* directories
-        for entry in fs::read_dir(&self.base_dir).into_iter().flatten() {
-            if let Ok(entry) = entry {
-                let path = entry.path();
-                if path.is_dir() {
-                    if let Some(name) = path.file_name().and_then(|n| n.to_str()) {
-                        if name.starts_with("log-") {
-                            // This is a year directory, scan for log entries
-                            for sub_entry in WalkDir::new(&path).min_depth(1).max_depth(1) {
-                                if let Ok(sub_entry) = sub_entry {
-                                    let log_file = sub_entry.path().join("log.txt");
-                                    if log_file.exists() {
-                                        if let Ok(content) = fs::read_to_string(&log_file) {
-                                            let log_entry = LogEntry::parse(&content, log_file);
-                                            entries.push(log_entry);
-                                        }
-                                    }
-                                }
+        for entry in fs::read_dir(&self.base_dir).into_iter().flatten().flatten() {
+            let path = entry.path();
+            if path.is_dir() {
+                if let Some(name) = path.file_name().and_then(|n| n.to_str()) {
+                    if name.starts_with("log-") {
+                        // This is a year directory, scan for log entries
+                        for sub_entry in WalkDir::new(&path).min_depth(1).max_depth(1).into_iter().flatten() {
+                            if sub_entry.path().join("log.txt").exists() {
+                                dirs.push(sub_entry.path().to_path_buf());
                             }
                         }
                     }
@@ -201,12 +500,159 @@ impl Storage {
             }
         }
 
+        dirs
+    }
+
+    /// Load all log entries from disk
+    pub fn load_all_logs(&self) -> Result<Vec<LogEntry>> {
+        let mut entries = Vec::new();
+
+        for entry_dir in self.entry_dirs() {
+            let log_file = entry_dir.join("log.txt");
+            if let Ok(content) = fs::read_to_string(&log_file) {
+                let mut log_entry = LogEntry::parse(&content, log_file);
+                log_entry.attachments = Self::list_attachments(&entry_dir);
+                entries.push(log_entry);
+            }
+        }
+
         // Sort by timestamp (newest first)
-        entries.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
+        entries.sort_by_key(|e| std::cmp::Reverse(e.timestamp));
 
         Ok(entries)
     }
 
+    /// Hash a file's contents with SHA-256
+    fn hash_file(path: &Path) -> Result<String> {
+        let mut file = fs::File::open(path)
+            .with_context(|| format!("Failed to open {}", path.display()))?;
+        let mut hasher = Sha256::new();
+        io::copy(&mut file, &mut hasher)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        Ok(hasher.finalize().iter().map(|b| format!("{:02x}", b)).collect())
+    }
+
+    /// Whether the blob store holds an intact copy of the given contents
+    ///
+    /// A blob changed through one of its links no longer matches its name and is not reused.
+    fn has_blob(&self, hash: &str) -> bool {
+        let blob = self.blob_path(hash);
+        blob.exists() && Self::hash_file(&blob).is_ok_and(|h| h == hash)
+    }
+
+    /// Copy a file into the blob store unless its contents are already there
+    fn store_blob(&self, source: &Path) -> Result<String> {
+        let hash = Self::hash_file(source)?;
+        let blob = self.blob_path(&hash);
+        if !self.has_blob(&hash) {
+            let dir = blob.parent().unwrap();
+            fs::create_dir_all(dir).context("Failed to create blob directory")?;
+
+            // Copy under a temporary name so a partial copy never looks like a blob
+            let tmp = dir.join(format!("{}.tmp", hash));
+            fs::copy(source, &tmp).context("Failed to copy attachment into blob store")?;
+            fs::rename(&tmp, &blob).context("Failed to store attachment blob")?;
+        }
+        Ok(hash)
+    }
+
+    /// Make a blob appear at dest, as a hard link when the filesystem allows it
+    ///
+    /// Blobs are made read-only first, since every entry linking one shares the same file.
+    fn link_blob(&self, hash: &str, dest: &Path) -> Result<()> {
+        let blob = self.blob_path(hash);
+        let mut permissions = fs::metadata(&blob).context("Failed to read attachment blob")?.permissions();
+        if !permissions.readonly() {
+            permissions.set_readonly(true);
+            fs::set_permissions(&blob, permissions).context("Failed to protect attachment blob")?;
+        }
+        if fs::hard_link(&blob, dest).is_err() {
+            fs::copy(&blob, dest).context("Failed to copy attachment")?;
+        }
+        Ok(())
+    }
+
+    fn read_manifest(entry_dir: &Path) -> Manifest {
+        fs::read_to_string(entry_dir.join(MANIFEST_FILE))
+            .ok()
+            .and_then(|content| serde_yaml::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    fn write_manifest(entry_dir: &Path, manifest: &Manifest) -> Result<()> {
+        if manifest.is_empty() {
+            let path = entry_dir.join(MANIFEST_FILE);
+            if path.exists() {
+                fs::remove_file(path).context("Failed to remove attachment manifest")?;
+            }
+            return Ok(());
+        }
+        let yaml = serde_yaml::to_string(manifest)?;
+        fs::write(entry_dir.join(MANIFEST_FILE), yaml).context("Failed to write attachment manifest")
+    }
+
+    /// Move the attachments of existing entries into the blob store
+    ///
+    /// Returns how many files were moved and how many bytes of duplicates were freed.
+    pub fn migrate_attachments(&self) -> Result<BlobStats> {
+        let mut stats = BlobStats::default();
+
+        for entry_dir in self.entry_dirs() {
+            let mut manifest = Self::read_manifest(&entry_dir);
+            for path in Self::list_attachments(&entry_dir) {
+                let name = file_name_string(&path);
+                if manifest.contains_key(&name) {
+                    continue;
+                }
+
+                let hash = Self::hash_file(&path)?;
+                if self.has_blob(&hash) {
+                    // Same contents already stored: drop this copy
+                    stats.bytes += fs::metadata(&path).map(|m| m.len()).unwrap_or(0);
+                    fs::remove_file(&path).context("Failed to remove duplicate attachment")?;
+                } else {
+                    self.store_blob(&path)?;
+                    fs::remove_file(&path).context("Failed to replace attachment with link")?;
+                }
+                self.link_blob(&hash, &path)?;
+
+                manifest.insert(name, hash);
+                stats.files += 1;
+            }
+            Self::write_manifest(&entry_dir, &manifest)?;
+        }
+
+        Ok(stats)
+    }
+
+    /// Delete blobs that no entry manifest refers to
+    ///
+    /// Manifest entries for attachments deleted from their entry are dropped first.
+    pub fn collect_garbage(&self) -> Result<BlobStats> {
+        let mut referenced = HashSet::new();
+        for entry_dir in self.entry_dirs() {
+            let mut manifest = Self::read_manifest(&entry_dir);
+            let before = manifest.len();
+            manifest.retain(|name, _| entry_dir.join(name).is_file());
+            if manifest.len() != before {
+                Self::write_manifest(&entry_dir, &manifest)?;
+            }
+            referenced.extend(manifest.into_values());
+        }
+
+        let mut stats = BlobStats::default();
+        for entry in WalkDir::new(self.blobs_dir()).min_depth(2).max_depth(2).into_iter().flatten() {
+            let name = entry.file_name().to_string_lossy().to_string();
+            if entry.file_type().is_file() && !referenced.contains(&name) {
+                stats.bytes += entry.metadata().map(|m| m.len()).unwrap_or(0);
+                fs::remove_file(entry.path()).context("Failed to remove unreferenced blob")?;
+                stats.files += 1;
+            }
+        }
+
+        Ok(stats)
+    }
+
     /// Load all todos from all log entries
     pub fn load_all_todos(&self) -> Result<Vec<Todo>> {
         let entries = self.load_all_logs()?;
@@ -236,3 +682,7 @@ impl Default for Storage {
         Self::new().expect("Failed to create default storage")
     }
 }
+
+fn file_name_string(path: &Path) -> String {
+    path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default()
+}
@@ -1,8 +1,22 @@
-use crate::models::{Config, LogEntry, LogFilter, Person, Project, Todo, TodoFilter};
+use crate::models::{
+    project_ancestors, project_within, toggle_facet, Attachment, AttachmentRef, Config, StateConfig, Draft, LogEntry, LogFilter, CustomField, CustomFieldKind, LogTemplate, Milestone, PeopleFilter, Person, Project, ProjectFilter, StatusChange, TemplateField, TemplateFieldKind, Todo, TodoState,
+    TodoFilter, UiState,
+};
+use crate::fuzzy;
 use crate::storage::Storage;
+use crate::validation::{self, Field, FieldErrors};
+use super::log_entry::highlight_line;
 use anyhow::Result;
-use chrono::TimeZone;
+use chrono::{DateTime, Local, NaiveDate, TimeZone};
+use ratatui::layout::{Position, Rect};
+use ratatui::text::{Line, Span};
+use std::cell::RefCell;
+use std::collections::{BTreeMap, HashMap};
 use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+/// How often the log entry being edited is autosaved as a draft
+const DRAFT_AUTOSAVE_INTERVAL: Duration = Duration::from_secs(3);
 
 /// The current screen/view of the application
 #[derive(Debug, Clone, PartialEq)]
@@ -18,6 +32,8 @@ pub enum Screen {
     PeopleList,
     PersonDetails(usize), // Index in people list
     PersonEdit(Option<usize>), // None = new person, Some(idx) = edit existing
+    Settings,
+    Board,
 }
 
 /// Main application state
@@ -38,33 +54,89 @@ pub struct App {
     // Log entry editing state
     pub current_log: LogEntry,
     pub log_cursor_pos: usize,
-    pub attachments: Vec<PathBuf>,
-    pub autocomplete_suggestions: Vec<String>,
+    pub attachments: Vec<Attachment>,
+    pub attachments_focused: bool, // Attachments pane has focus instead of the text
+    pub attachment_selected: usize,
+    pub attachment_rename: Option<String>, // New name being typed for the selected attachment
+    pub autocomplete_suggestions: Vec<AutocompleteSuggestion>,
     pub autocomplete_index: usize,
+    pub autocomplete_picked: bool, // The highlight was moved with the arrows or a click
     pub autocomplete_active: bool,
     pub autocomplete_type: AutocompleteType,
+    pub tag_frecency: HashMap<String, f64>, // "#project"/"@person" -> recency-weighted use count
     pub timestamp_editing: bool,
     pub timestamp_edit_input: String,
     pub timestamp_edit_cursor: usize,
 
+    // Template picker state
+    pub templates: Vec<LogTemplate>,
+    pub template_picker_open: bool,
+    pub template_selected: usize, // 0 = blank entry, n = templates[n - 1]
+    pub template_pending: Option<LogTemplate>, // Template waiting for its fields to be filled in
+    pub template_fields: Vec<TemplateField>,
+    pub template_field_index: usize,
+    pub template_field_input: String,
+    pub template_field_suggestion: usize,
+    pub template_field_values: HashMap<String, String>,
+
+    // Draft state
+    pub drafts: Vec<Draft>,
+    pub draft_picker_open: bool,
+    pub draft_selected: usize,
+    pub draft_file: Option<PathBuf>, // Draft file for the log entry being edited
+    pub draft_last_saved: Option<Draft>,
+    pub draft_last_check: Instant,
+    pub exit_prompt_open: bool,
+
+    // Inline project/person creation from the editor
+    pub inline_create: Option<AutocompleteType>,
+    pub inline_create_field: usize, // Project: 0=name, 1=description, 2=group; Person: 0=name, 1=full_name, 2=email
+
+    // Paste state
+    pub paste_pending_text: String,
+    pub paste_pending_files: Vec<PathBuf>, // Pasted paths that could be attached instead
+
     // File browser state
     pub file_browser_open: bool,
     pub file_browser_dir: PathBuf,
-    pub file_browser_entries: Vec<FileEntry>,
+    pub file_browser_entries: Vec<FileEntry>, // Entries shown, after filtering
+    pub file_browser_all: Vec<FileEntry>,     // Everything in file_browser_dir
     pub file_browser_selected: usize,
+    pub file_browser_filter: String,          // Fuzzy filter, or a path if it starts with / or ~
+    pub file_browser_show_hidden: bool,
+    pub file_browser_marked: Vec<PathBuf>,    // Files picked for attaching together
+    pub file_browser_recent_open: bool,
+    pub file_browser_recent_selected: usize,
+    pub ui_state: UiState,
 
     // Todo list state
     pub todo_filter: TodoFilter,
     pub todo_selected: usize,
     pub filtered_todos: Vec<Todo>,
 
+    // Todo board state, showing the filtered todos
+    pub board_grouping: BoardGrouping,
+    pub board_column: usize,
+    pub board_selected: usize, // Within the focused column
+
     // Log list state
     pub log_filter: LogFilter,
     pub log_selected: usize,
     pub filtered_logs: Vec<LogEntry>,
 
+    // Attachment search state
+    pub attachment_texts: Vec<AttachmentText>,
+    pub attachment_search_query: String,
+    pub attachment_search_results: Vec<AttachmentSearchResult>,
+    pub attachment_search_selected: usize,
+
     // View log state
     pub view_log_scroll: u16,
+    pub view_log_lines: Vec<Line<'static>>, // Highlighted once when the screen opens
+    pub view_log_attachments: Vec<PathBuf>,
+    pub view_log_missing_refs: Vec<String>, // Referenced in the text but not in the entry directory
+    pub view_log_attachments_focused: bool,
+    pub view_log_attachment_selected: usize,
 
     // Todo filter editing state
     pub todo_filter_panel: TodoFilterPanel,
@@ -82,21 +154,39 @@ pub struct App {
     pub project_selected: usize,
     pub project_list_scroll: usize,
     pub filtered_projects: Vec<Project>,
-    pub project_filter_groups: Vec<String>,
+    pub filtered_project_depths: Vec<usize>, // Tree depth of each filtered project
+    pub project_sort: ProjectSort,
+    pub project_stats: HashMap<String, ProjectStats>,
+    pub project_filter: ProjectFilter,
     pub project_filter_panel: ProjectFilterPanel,
     pub project_filter_group_selected: usize,
+    pub project_filter_status_selected: usize,
+    pub project_filter_field_selected: usize,
 
     // Project details state
     pub project_details_log_selected: usize,
     pub project_details_logs: Vec<LogEntry>,
+    pub project_details_open_todos: usize, // Including sub-projects
+    pub project_details_notes: Option<String>,
+    pub project_details_notes_scroll: u16,
+    pub notes_editing: Option<String>, // Project whose notes are open in the log editor
+    pub notes_saved: String, // The notes as last saved, to spot unsaved changes
+    pub external_edit: Option<PathBuf>, // File for the main loop to open in $EDITOR
 
     // Project edit state
-    pub project_edit_field: usize, // 0=name, 1=description, 2=jira, 3=status, 4=group
+    pub project_edit_field: usize, // 0=name, 1=description, 2=jira, 3=status, 4=group, 5=parent, 6=target date, 7=milestones
     pub project_edit_name: String,
     pub project_edit_description: String,
     pub project_edit_jira: String,
     pub project_edit_status: String,
     pub project_edit_group: String,
+    pub project_edit_parent: String,
+    pub project_edit_target_date: String,
+    pub project_edit_milestones: Vec<Milestone>,
+    pub project_edit_milestone_selected: usize,
+    pub project_edit_milestone_input: Option<String>, // `YYYY-MM-DD name` being typed
+    pub project_edit_milestone_editing: Option<usize>, // None when adding a milestone
+    pub project_edit_status_reason: Option<String>, // Asked for on save when the status changed
     pub project_edit_status_dropdown_open: bool,
     pub project_edit_status_dropdown_selected: usize,
     pub project_edit_group_dropdown_open: bool,
@@ -105,6 +195,19 @@ pub struct App {
     // People list state
     pub person_selected: usize,
     pub person_list_scroll: usize,
+    pub filtered_people: Vec<Person>,
+    pub people_show_archived: bool,
+    pub people_filter: PeopleFilter,
+    pub people_filter_panel: PeopleFilterPanel,
+    pub people_filter_field_selected: usize,
+
+    // Delete confirmation on the project and person details screens
+    pub delete_confirm: Option<DeleteConfirm>,
+
+    // Todo tabs on the project and person details screens
+    pub details_tab: DetailsTab,
+    pub details_todos: Vec<Todo>, // Todos of the shown project or person, newest log first
+    pub details_todo_selected: usize, // Within the todos of the current tab
 
     // Person details state
     pub person_details_log_selected: usize,
@@ -118,8 +221,27 @@ pub struct App {
     pub person_edit_tel: String,
     pub person_edit_company: String,
 
+    // Custom field editing, shared by the project and person edit screens
+    pub edit_custom_fields: BTreeMap<String, String>,
+    pub custom_field_selected: usize,
+    pub custom_field_input: Option<String>,
+    pub edit_errors: FieldErrors, // Problems found by the last save of a project/person form
+
+    // Settings screen state, edited on a copy of the config until saved
+    pub settings_config: Config,
+    pub settings_section: SettingsSection,
+    pub settings_state_selected: usize,
+    pub settings_group_selected: usize,
+    pub settings_input: Option<String>, // Name being typed for a new or renamed entry
+    pub settings_input_renaming: Option<String>, // Entry being renamed, None when adding
+    pub settings_rename_confirm: Option<SettingsRename>, // Asking whether projects follow a rename
+    pub settings_project_renames: Vec<SettingsRename>, // Renames to apply to projects on save
+
     // Status message
     pub status_message: Option<String>,
+
+    // Clickable regions recorded during the last render
+    pub click_areas: RefCell<Vec<(Rect, ClickTarget)>>,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -129,6 +251,34 @@ pub enum AutocompleteType {
     Person,
 }
 
+/// Text extracted from a log entry's attachment for searching
+#[derive(Debug, Clone)]
+pub struct AttachmentText {
+    pub timestamp: DateTime<Local>, // Of the log entry the file belongs to
+    pub path: PathBuf,
+    pub content: String,
+}
+
+/// A line of an attachment matching the attachment search
+#[derive(Debug, Clone)]
+pub struct AttachmentSearchResult {
+    pub text_index: usize, // Into App::attachment_texts
+    pub line: usize,
+    pub snippet: String,
+    pub match_start: usize, // Char range of the match within the snippet
+    pub match_len: usize,
+}
+
+/// A tag suggestion shown in the editor's autocomplete popup
+#[derive(Debug, Clone, PartialEq)]
+pub struct AutocompleteSuggestion {
+    pub name: String,
+    pub detail: String, // Full name for people, status and description for projects
+    pub status: Option<String>,
+    pub inactive: bool, // Closed projects are listed after everything else
+    pub create: bool,   // Offers to create a new project/person with this name
+}
+
 /// Which filter panel is currently being edited in log list view
 #[derive(Debug, Clone, PartialEq)]
 pub enum LogFilterPanel {
@@ -137,6 +287,7 @@ pub enum LogFilterPanel {
     EndDate,
     Projects,
     People,
+    AttachmentSearch,
 }
 
 /// Which filter panel is currently being edited in todo list view
@@ -148,10 +299,172 @@ pub enum TodoFilterPanel {
     People,
 }
 
+/// How the todo board splits todos into columns
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BoardGrouping {
+    State,
+    Project,
+}
+
+/// The tabs below the info on the project and person details screens
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DetailsTab {
+    Logs,
+    OpenTodos,
+    CompletedTodos,
+}
+
+impl DetailsTab {
+    pub const ALL: [DetailsTab; 3] = [DetailsTab::Logs, DetailsTab::OpenTodos, DetailsTab::CompletedTodos];
+
+    pub fn next(self) -> Self {
+        match self {
+            DetailsTab::Logs => DetailsTab::OpenTodos,
+            DetailsTab::OpenTodos => DetailsTab::CompletedTodos,
+            DetailsTab::CompletedTodos => DetailsTab::Logs,
+        }
+    }
+}
+
+/// The list focused on the settings screen
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SettingsSection {
+    States,
+    Groups,
+}
+
+/// A state or group renamed on the settings screen, and how many projects use the old name
+#[derive(Debug, Clone)]
+pub struct SettingsRename {
+    pub section: SettingsSection,
+    pub from: String,
+    pub to: String,
+    pub projects: usize,
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum ProjectFilterPanel {
     None,
     Groups,
+    Statuses,
+    Search,
+    Fields,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum PeopleFilterPanel {
+    None,
+    Search,
+    Fields,
+}
+
+/// How projects are ordered within each group of the project list
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ProjectSort {
+    Tree,
+    LastActivity,
+    Name,
+    Status,
+    OpenTodos,
+    Deadline,
+}
+
+impl ProjectSort {
+    pub fn next(self) -> Self {
+        match self {
+            ProjectSort::Tree => ProjectSort::LastActivity,
+            ProjectSort::LastActivity => ProjectSort::Name,
+            ProjectSort::Name => ProjectSort::Status,
+            ProjectSort::Status => ProjectSort::OpenTodos,
+            ProjectSort::OpenTodos => ProjectSort::Deadline,
+            ProjectSort::Deadline => ProjectSort::Tree,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ProjectSort::Tree => "tree",
+            ProjectSort::LastActivity => "last activity",
+            ProjectSort::Name => "name",
+            ProjectSort::Status => "status",
+            ProjectSort::OpenTodos => "open todos",
+            ProjectSort::Deadline => "nearest deadline",
+        }
+    }
+}
+
+/// A project or person about to be deleted, with the references left behind
+#[derive(Debug, Clone)]
+pub struct DeleteConfirm {
+    pub name: String,
+    pub logs: usize,
+    pub todos: usize,
+}
+
+/// Number of weeks covered by the project list activity sparkline
+pub const ACTIVITY_WEEKS: usize = 8;
+
+/// Todo counts and log activity of a project, sub-projects included
+#[derive(Debug, Clone, Default)]
+pub struct ProjectStats {
+    pub open_todos: usize,
+    pub completed_todos: usize,
+    pub last_log: Option<DateTime<Local>>,
+    pub weekly_logs: [usize; ACTIVITY_WEEKS], // Oldest week first
+}
+
+/// What a mouse click on a rendered region should act on
+#[derive(Debug, Clone, PartialEq)]
+pub enum ClickTarget {
+    Blocker, // Swallows clicks behind a popup
+    Editor,
+    TodoCheckbox(usize),
+    Row(ClickList, usize),
+}
+
+/// The lists (and list-like rows) that can be clicked
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ClickList {
+    Menu,
+    Drafts,
+    Templates,
+    TemplateSuggestions,
+    Autocomplete,
+    FileBrowser,
+    RecentDirs,
+    Attachments,
+    ViewLogAttachments,
+    Todos,
+    TodoFilterPanels,
+    TodoFilterCompleted,
+    TodoFilterProjects,
+    TodoFilterPeople,
+    Logs,
+    LogFilterPanels,
+    LogFilterProjects,
+    LogFilterPeople,
+    AttachmentSearchResults,
+    Projects,
+    ProjectFilterPanels,
+    ProjectFilterGroups,
+    ProjectFilterStatuses,
+    ProjectFilterFields,
+    ProjectDetailsLogs,
+    BoardCards(usize), // Column
+    DetailsTabs,
+    DetailsTodos,
+    People,
+    PeopleFilterPanels,
+    PeopleFilterFields,
+    PersonDetailsLogs,
+    ProjectEditFields,
+    ProjectEditStatus,
+    ProjectEditGroup,
+    ProjectEditMilestones,
+    SettingsStates,
+    SettingsGroups,
+    CustomFields,
+    PersonEditFields,
 }
 
 /// A file or directory entry in the file browser
@@ -171,6 +484,11 @@ impl App {
         let config = storage.load_config().unwrap_or_default();
         let projects = storage.load_projects().unwrap_or_default();
         let people = storage.load_people().unwrap_or_default();
+        let drafts = storage.load_drafts().unwrap_or_default();
+        let draft_picker_open = !drafts.is_empty();
+        let ui_state = storage.load_ui_state();
+        let project_filter = ui_state.project_filter.clone();
+        let people_filter = ui_state.people_filter.clone();
 
         Ok(Self {
             storage,
@@ -188,28 +506,83 @@ impl App {
             current_log: LogEntry::new(),
             log_cursor_pos: 0,
             attachments: Vec::new(),
+            attachments_focused: false,
+            attachment_selected: 0,
+            attachment_rename: None,
             autocomplete_suggestions: Vec::new(),
             autocomplete_index: 0,
+            autocomplete_picked: false,
             autocomplete_active: false,
             autocomplete_type: AutocompleteType::None,
+            tag_frecency: HashMap::new(),
             timestamp_editing: false,
             timestamp_edit_input: String::new(),
             timestamp_edit_cursor: 0,
 
+            templates: Vec::new(),
+            template_picker_open: false,
+            template_selected: 0,
+            template_pending: None,
+            template_fields: Vec::new(),
+            template_field_index: 0,
+            template_field_input: String::new(),
+            template_field_suggestion: 0,
+            template_field_values: HashMap::new(),
+
+            drafts,
+            draft_picker_open,
+            draft_selected: 0,
+            draft_file: None,
+            draft_last_saved: None,
+            draft_last_check: Instant::now(),
+            exit_prompt_open: false,
+
+            inline_create: None,
+            inline_create_field: 0,
+
+            paste_pending_text: String::new(),
+            paste_pending_files: Vec::new(),
+
             file_browser_open: false,
-            file_browser_dir: dirs::home_dir().unwrap_or_else(|| PathBuf::from("/")),
+            file_browser_dir: ui_state
+                .last_attach_dir
+                .clone()
+                .filter(|d| d.is_dir())
+                .or_else(dirs::home_dir)
+                .unwrap_or_else(|| PathBuf::from("/")),
             file_browser_entries: Vec::new(),
+            file_browser_all: Vec::new(),
             file_browser_selected: 0,
+            file_browser_filter: String::new(),
+            file_browser_show_hidden: false,
+            file_browser_marked: Vec::new(),
+            file_browser_recent_open: false,
+            file_browser_recent_selected: 0,
+            ui_state,
 
             todo_filter: TodoFilter::default(),
             todo_selected: 0,
             filtered_todos: Vec::new(),
 
+            board_grouping: BoardGrouping::State,
+            board_column: 0,
+            board_selected: 0,
+
             log_filter: LogFilter::default(),
             log_selected: 0,
             filtered_logs: Vec::new(),
 
+            attachment_texts: Vec::new(),
+            attachment_search_query: String::new(),
+            attachment_search_results: Vec::new(),
+            attachment_search_selected: 0,
+
             view_log_scroll: 0,
+            view_log_lines: Vec::new(),
+            view_log_attachments: Vec::new(),
+            view_log_missing_refs: Vec::new(),
+            view_log_attachments_focused: false,
+            view_log_attachment_selected: 0,
 
             todo_filter_panel: TodoFilterPanel::None,
             todo_filter_project_selected: 0,
@@ -224,12 +597,23 @@ impl App {
             project_selected: 0,
             project_list_scroll: 0,
             filtered_projects: Vec::new(),
-            project_filter_groups: Vec::new(),
+            filtered_project_depths: Vec::new(),
+            project_sort: ProjectSort::Tree,
+            project_stats: HashMap::new(),
+            project_filter,
             project_filter_panel: ProjectFilterPanel::None,
             project_filter_group_selected: 0,
+            project_filter_status_selected: 0,
+            project_filter_field_selected: 0,
 
             project_details_log_selected: 0,
             project_details_logs: Vec::new(),
+            project_details_open_todos: 0,
+            project_details_notes: None,
+            project_details_notes_scroll: 0,
+            notes_editing: None,
+            notes_saved: String::new(),
+            external_edit: None,
 
             project_edit_field: 0,
             project_edit_name: String::new(),
@@ -237,6 +621,13 @@ impl App {
             project_edit_jira: String::new(),
             project_edit_status: String::new(),
             project_edit_group: String::new(),
+            project_edit_parent: String::new(),
+            project_edit_target_date: String::new(),
+            project_edit_milestones: Vec::new(),
+            project_edit_milestone_selected: 0,
+            project_edit_milestone_input: None,
+            project_edit_milestone_editing: None,
+            project_edit_status_reason: None,
             project_edit_status_dropdown_open: false,
             project_edit_status_dropdown_selected: 0,
             project_edit_group_dropdown_open: false,
@@ -244,9 +635,18 @@ impl App {
 
             person_selected: 0,
             person_list_scroll: 0,
+            filtered_people: Vec::new(),
+            people_show_archived: false,
+            people_filter,
+            people_filter_panel: PeopleFilterPanel::None,
+            people_filter_field_selected: 0,
+            delete_confirm: None,
 
             person_details_log_selected: 0,
             person_details_logs: Vec::new(),
+            details_tab: DetailsTab::Logs,
+            details_todos: Vec::new(),
+            details_todo_selected: 0,
 
             person_edit_field: 0,
             person_edit_name: String::new(),
@@ -255,7 +655,22 @@ impl App {
             person_edit_tel: String::new(),
             person_edit_company: String::new(),
 
+            edit_custom_fields: BTreeMap::new(),
+            custom_field_selected: 0,
+            custom_field_input: None,
+            edit_errors: FieldErrors::new(),
+            settings_config: Config::default(),
+            settings_section: SettingsSection::States,
+            settings_state_selected: 0,
+            settings_group_selected: 0,
+            settings_input: None,
+            settings_input_renaming: None,
+            settings_rename_confirm: None,
+            settings_project_renames: Vec::new(),
+
             status_message: None,
+
+            click_areas: RefCell::new(Vec::new()),
         })
     }
 
@@ -271,6 +686,47 @@ impl App {
         if matches!(screen, Screen::Menu) {
             self.menu_selected = 0;
         }
+
+        // Pick up the files stored with a log entry
+        if let Screen::ViewLog(path) = &screen {
+            self.view_log_attachments = path.parent().map(Storage::list_attachments).unwrap_or_default();
+            let text = Storage::read_text_capped(path);
+            let attachments = &self.view_log_attachments;
+            self.view_log_lines = match &text {
+                Some(text) => text
+                    .lines()
+                    .map(|line| {
+                        // Syntax highlight the content, flagging references to files that aren't there
+                        let highlighted = highlight_line(line, |name| {
+                            attachments.iter().any(|p| p.file_name().is_some_and(|n| n == name))
+                        });
+                        Line::from(
+                            highlighted
+                                .spans
+                                .into_iter()
+                                .map(|s| Span::styled(s.content.into_owned(), s.style))
+                                .collect::<Vec<_>>(),
+                        )
+                    })
+                    .collect(),
+                None => vec![Line::from("Error reading log file")],
+            };
+
+            // References only mean something in log.txt, not in attachments opened from search
+            let content = match text {
+                Some(text) if path.file_name().is_some_and(|n| n == "log.txt") => text,
+                _ => String::new(),
+            };
+            self.view_log_missing_refs = Vec::new();
+            for reference in AttachmentRef::find_all(&content) {
+                let exists = self.view_log_attachments.iter().any(|p| p.file_name().is_some_and(|n| n == reference.name.as_str()));
+                if !exists && !self.view_log_missing_refs.contains(&reference.name) {
+                    self.view_log_missing_refs.push(reference.name);
+                }
+            }
+            self.view_log_attachments_focused = false;
+            self.view_log_attachment_selected = 0;
+        }
     }
 
     pub fn go_back(&mut self) {
@@ -292,24 +748,266 @@ impl App {
         self.current_log = LogEntry::new();
         self.log_cursor_pos = 0;
         self.attachments.clear();
+        self.attachments_focused = false;
+        self.attachment_selected = 0;
+        self.attachment_rename = None;
         self.autocomplete_suggestions.clear();
         self.autocomplete_active = false;
+        self.draft_file = None;
+        self.draft_last_saved = None;
+        self.exit_prompt_open = false;
+        self.refresh_tag_frecency();
+
+        // Offer a template picker if any templates exist
+        self.templates = self.storage.load_templates().unwrap_or_default();
+        self.template_pending = None;
+        self.template_selected = 0;
+        self.template_picker_open = !self.templates.is_empty();
+
         self.go_to_screen(Screen::LogEntry);
     }
 
+    /// Use the template highlighted in the picker (or a blank entry)
+    pub fn select_template(&mut self) {
+        self.template_picker_open = false;
+        let template = match self.template_selected.checked_sub(1).and_then(|i| self.templates.get(i)) {
+            Some(template) => template.clone(),
+            None => return, // Blank entry
+        };
+
+        self.template_fields = template.fields();
+        self.template_field_values.clear();
+        self.template_field_index = 0;
+        self.template_field_input.clear();
+        self.template_field_suggestion = 0;
+
+        if self.template_fields.is_empty() {
+            self.apply_template(&template);
+        } else {
+            self.template_pending = Some(template);
+        }
+    }
+
+    /// Cancel filling in template fields and return to the picker
+    pub fn cancel_template(&mut self) {
+        self.template_pending = None;
+        self.template_picker_open = true;
+    }
+
+    /// Get the field currently being prompted for
+    pub fn current_template_field(&self) -> Option<&TemplateField> {
+        self.template_pending.as_ref()?;
+        self.template_fields.get(self.template_field_index)
+    }
+
+    /// Get tag suggestions for the field currently being prompted for
+    pub fn template_field_suggestions(&self) -> Vec<String> {
+        let names = match self.current_template_field().map(|f| &f.kind) {
+            Some(TemplateFieldKind::Person) => self.all_people_names(),
+            Some(TemplateFieldKind::Project) => self.all_project_names(),
+            _ => return Vec::new(),
+        };
+        let prefix = self.template_field_input.to_lowercase();
+        names
+            .into_iter()
+            .filter(|n| n.to_lowercase().starts_with(&prefix))
+            .collect()
+    }
+
+    /// Accept the value for the current field and move to the next one
+    pub fn submit_template_field(&mut self) {
+        let field = match self.current_template_field() {
+            Some(field) => field.clone(),
+            None => return,
+        };
+
+        // Tag fields take an exact match of what was typed, then the highlighted
+        // suggestion, then the typed text as is. Left empty, the field stays empty.
+        let typed = self.template_field_input.trim().to_string();
+        let suggestions = self.template_field_suggestions();
+        let value = if typed.is_empty() {
+            typed
+        } else if let Some(exact) = suggestions.iter().find(|s| s.eq_ignore_ascii_case(&typed)) {
+            exact.clone()
+        } else {
+            suggestions.get(self.template_field_suggestion).cloned().unwrap_or(typed)
+        };
+        self.template_field_values.insert(field.key, value);
+
+        self.template_field_index += 1;
+        self.template_field_input.clear();
+        self.template_field_suggestion = 0;
+
+        if self.template_field_index >= self.template_fields.len() {
+            if let Some(template) = self.template_pending.take() {
+                self.apply_template(&template);
+            }
+        }
+    }
+
+    /// Expand a template into the current log entry
+    fn apply_template(&mut self, template: &LogTemplate) {
+        let todos = self.storage.load_all_todos().unwrap_or_default();
+        let (content, cursor) = template.expand(self.current_log.timestamp, &self.template_field_values, &todos);
+        self.current_log.content = content;
+        self.log_cursor_pos = cursor;
+        self.status_message = Some(format!("Using template: {}", template.name));
+    }
+
     /// Save the current log entry
     pub fn save_log(&mut self) -> Result<()> {
+        if self.notes_editing.is_some() {
+            return self.save_notes();
+        }
         if self.current_log.content.trim().is_empty() {
             self.status_message = Some("Cannot save empty log entry".to_string());
             return Ok(());
         }
 
-        let path = self.storage.save_log_entry(&self.current_log, &self.attachments)?;
+        let path = self.storage.save_log_entry(&self.current_log, &self.attachments, self.config.attachments.dedupe)?;
+        self.discard_draft();
         self.status_message = Some(format!("Log saved to {:?}", path));
         self.go_to_screen(Screen::Menu);
         Ok(())
     }
 
+    /// Snapshot the log entry being edited as a draft
+    fn current_draft(&self) -> Draft {
+        Draft {
+            timestamp: self.current_log.timestamp,
+            content: self.current_log.content.clone(),
+            cursor: self.log_cursor_pos,
+            attachments: self.attachments.clone(),
+            file_path: self.draft_file.clone().unwrap_or_default(),
+        }
+    }
+
+    /// Autosave the log entry being edited if it changed since the last save
+    pub fn autosave_draft(&mut self) {
+        if self.screen != Screen::LogEntry || self.notes_editing.is_some() || self.draft_last_check.elapsed() < DRAFT_AUTOSAVE_INTERVAL {
+            return;
+        }
+        self.draft_last_check = Instant::now();
+
+        if let Err(e) = self.write_draft() {
+            self.status_message = Some(format!("Failed to autosave draft: {}", e));
+        }
+    }
+
+    /// Write the log entry being edited to its draft file
+    fn write_draft(&mut self) -> Result<()> {
+        let mut draft = self.current_draft();
+        if draft.is_empty() {
+            self.discard_draft();
+            return Ok(());
+        }
+        if self.draft_last_saved.as_ref() == Some(&draft) {
+            return Ok(());
+        }
+
+        // Drafts get their file on first save
+        draft.file_path = self
+            .draft_file
+            .get_or_insert_with(|| self.storage.new_draft_path())
+            .clone();
+        self.storage.save_draft(&draft)?;
+        self.draft_last_saved = Some(draft);
+        Ok(())
+    }
+
+    /// Delete the draft file for the log entry being edited
+    pub fn discard_draft(&mut self) {
+        if let Some(path) = self.draft_file.take() {
+            let _ = self.storage.delete_draft(&path);
+        }
+        self.draft_last_saved = None;
+    }
+
+    /// Leave the editor, asking what to do with unsaved content first
+    pub fn leave_log_entry(&mut self) {
+        if self.notes_editing.is_some() {
+            if self.current_log.content == self.notes_saved {
+                self.notes_editing = None;
+                self.go_back();
+            } else {
+                self.exit_prompt_open = true;
+            }
+            return;
+        }
+        if self.current_draft().is_empty() {
+            self.discard_draft();
+            self.go_to_screen(Screen::Menu);
+        } else {
+            self.exit_prompt_open = true;
+        }
+    }
+
+    /// Keep the log entry being edited as a draft and leave the editor
+    pub fn keep_draft_and_leave(&mut self) -> Result<()> {
+        self.exit_prompt_open = false;
+        self.write_draft()?;
+        self.draft_file = None;
+        self.draft_last_saved = None;
+        self.drafts = self.storage.load_drafts().unwrap_or_default();
+        self.status_message = Some("Kept as draft".to_string());
+        self.go_to_screen(Screen::Menu);
+        Ok(())
+    }
+
+    /// Discard the log entry being edited and leave the editor
+    pub fn discard_and_leave(&mut self) {
+        self.exit_prompt_open = false;
+        if self.notes_editing.take().is_some() {
+            self.status_message = Some("Notes not saved".to_string());
+            self.go_back();
+            return;
+        }
+        self.discard_draft();
+        self.status_message = Some("Log entry discarded".to_string());
+        self.go_to_screen(Screen::Menu);
+    }
+
+    /// Open the draft picker if there are drafts to resume
+    pub fn open_draft_picker(&mut self) {
+        self.drafts = self.storage.load_drafts().unwrap_or_default();
+        self.draft_selected = 0;
+        self.draft_picker_open = !self.drafts.is_empty();
+        if self.drafts.is_empty() {
+            self.status_message = Some("No drafts".to_string());
+        }
+    }
+
+    /// Resume editing the draft selected in the draft picker
+    pub fn resume_selected_draft(&mut self) {
+        if let Some(draft) = self.drafts.get(self.draft_selected).cloned() {
+            self.start_new_log();
+            self.template_picker_open = false;
+
+            self.current_log.timestamp = draft.timestamp;
+            self.current_log.content = draft.content.clone();
+            self.log_cursor_pos = draft.cursor.min(draft.content.chars().count());
+            self.attachments = draft.attachments.clone();
+            self.draft_file = Some(draft.file_path.clone());
+            self.draft_last_saved = Some(draft);
+
+            self.drafts.remove(self.draft_selected);
+            self.draft_picker_open = false;
+            self.status_message = Some("Draft resumed".to_string());
+        }
+    }
+
+    /// Delete the draft selected in the draft picker
+    pub fn delete_selected_draft(&mut self) -> Result<()> {
+        if self.draft_selected < self.drafts.len() {
+            let draft = self.drafts.remove(self.draft_selected);
+            self.storage.delete_draft(&draft.file_path)?;
+            self.draft_selected = self.draft_selected.min(self.drafts.len().saturating_sub(1));
+            self.draft_picker_open = !self.drafts.is_empty();
+            self.status_message = Some("Draft deleted".to_string());
+        }
+        Ok(())
+    }
+
     /// Start editing the timestamp
     pub fn start_timestamp_edit(&mut self) {
         self.timestamp_editing = true;
@@ -352,7 +1050,7 @@ impl App {
         self.filtered_todos = self
             .todos
             .iter()
-            .filter(|t| self.todo_filter.matches(t))
+            .filter(|t| self.todo_filter.matches(t, &self.projects))
             .cloned()
             .collect();
     }
@@ -365,6 +1063,7 @@ impl App {
             for t in &mut self.todos {
                 if t.log_path == todo.log_path && t.line_number == todo.line_number {
                     t.completed = todo.completed;
+                    t.state = todo.state;
                     break;
                 }
             }
@@ -372,6 +1071,168 @@ impl App {
         Ok(())
     }
 
+    /// Load todos and show them on the board
+    pub fn show_board(&mut self) -> Result<()> {
+        self.todos = self.storage.load_all_todos()?;
+        self.apply_todo_filter();
+        self.board_column = 0;
+        self.board_selected = 0;
+        self.go_to_screen(Screen::Board);
+        Ok(())
+    }
+
+    /// The board columns: a title and the indices of their todos in `filtered_todos`
+    pub fn board_columns(&self) -> Vec<(String, Vec<usize>)> {
+        match self.board_grouping {
+            BoardGrouping::State => TodoState::ALL
+                .iter()
+                .map(|&state| {
+                    let todos = (0..self.filtered_todos.len())
+                        .filter(|&i| self.filtered_todos[i].state == state)
+                        .collect();
+                    (state.label().to_string(), todos)
+                })
+                .collect(),
+            BoardGrouping::Project => {
+                // Known projects in list order, then tags that aren't in projects.yml.
+                // A todo tagged with several projects shows in each of their columns.
+                let mut names: Vec<String> = self
+                    .projects
+                    .iter()
+                    .map(|p| p.name.clone())
+                    .filter(|name| self.filtered_todos.iter().any(|t| t.projects.contains(name)))
+                    .collect();
+                for todo in &self.filtered_todos {
+                    for name in &todo.projects {
+                        if !names.contains(name) {
+                            names.push(name.clone());
+                        }
+                    }
+                }
+
+                let mut columns: Vec<(String, Vec<usize>)> = names
+                    .into_iter()
+                    .map(|name| {
+                        let todos = (0..self.filtered_todos.len())
+                            .filter(|&i| self.filtered_todos[i].projects.contains(&name))
+                            .collect();
+                        (format!("#{}", name), todos)
+                    })
+                    .collect();
+                let untagged: Vec<usize> = (0..self.filtered_todos.len())
+                    .filter(|&i| self.filtered_todos[i].projects.is_empty())
+                    .collect();
+                if !untagged.is_empty() {
+                    columns.push(("(no project)".to_string(), untagged));
+                }
+                columns
+            }
+        }
+    }
+
+    /// Index in `filtered_todos` of the selected card
+    pub fn board_selected_todo(&self) -> Option<usize> {
+        self.board_columns()
+            .get(self.board_column)
+            .and_then(|(_, todos)| todos.get(self.board_selected).copied())
+    }
+
+    /// Keep the focused column and card in range after the columns change
+    pub fn clamp_board_selection(&mut self) {
+        let columns = self.board_columns();
+        self.board_column = self.board_column.min(columns.len().saturating_sub(1));
+        let cards = columns.get(self.board_column).map_or(0, |(_, todos)| todos.len());
+        self.board_selected = self.board_selected.min(cards.saturating_sub(1));
+    }
+
+    /// Focus the column to the left or right
+    pub fn move_board_focus(&mut self, right: bool) {
+        let columns = self.board_columns().len();
+        if right && self.board_column + 1 < columns {
+            self.board_column += 1;
+        } else if !right && self.board_column > 0 {
+            self.board_column -= 1;
+        } else {
+            return;
+        }
+        self.board_selected = 0;
+    }
+
+    /// Move the selected card to the state column to the left or right
+    pub fn move_board_card(&mut self, right: bool) -> Result<()> {
+        if self.board_grouping != BoardGrouping::State {
+            self.status_message = Some("Cards move between states, press g to group by state".to_string());
+            return Ok(());
+        }
+        let Some(idx) = self.board_selected_todo() else {
+            return Ok(());
+        };
+        let current = TodoState::ALL.iter().position(|&s| s == self.filtered_todos[idx].state).unwrap_or(0);
+        let target = match right {
+            true if current + 1 < TodoState::ALL.len() => current + 1,
+            false if current > 0 => current - 1,
+            _ => return Ok(()),
+        };
+        self.set_board_todo_state(idx, TodoState::ALL[target])
+    }
+
+    /// Mark the selected card done, or reopen it
+    pub fn toggle_board_card(&mut self) -> Result<()> {
+        let Some(idx) = self.board_selected_todo() else {
+            return Ok(());
+        };
+        let state = if self.filtered_todos[idx].completed { TodoState::Open } else { TodoState::Done };
+        self.set_board_todo_state(idx, state)
+    }
+
+    /// Rewrite a todo's marker and keep the card selected in its new column
+    fn set_board_todo_state(&mut self, idx: usize, state: TodoState) -> Result<()> {
+        let todo = &mut self.filtered_todos[idx];
+        todo.set_state(state)?;
+        let (path, line) = (todo.log_path.clone(), todo.line_number);
+        for t in &mut self.todos {
+            if t.log_path == path && t.line_number == line {
+                t.state = state;
+                t.completed = state == TodoState::Done;
+                break;
+            }
+        }
+        self.apply_todo_filter();
+
+        // Follow the card unless the filter now hides it (done todos may be hidden)
+        let column = self.board_column;
+        let position = self.filtered_todos.iter().position(|t| t.log_path == path && t.line_number == line);
+        let found = position.and_then(|idx| {
+            let columns = self.board_columns();
+            // Stay in the focused column if the card is still there, as it is when grouped by project
+            let in_column = |c: usize| columns.get(c).and_then(|(_, todos)| todos.iter().position(|&i| i == idx));
+            in_column(column)
+                .map(|row| (column, row))
+                .or_else(|| (0..columns.len()).find_map(|c| in_column(c).map(|row| (c, row))))
+        });
+        match found {
+            Some((column, row)) => {
+                self.board_column = column;
+                self.board_selected = row;
+            }
+            None => {
+                self.status_message = Some(format!("Todo moved to {}, which the filter hides", state.label()));
+                self.clamp_board_selection();
+            }
+        }
+        Ok(())
+    }
+
+    /// View the log the selected card comes from, scrolled to the todo
+    pub fn view_board_todo_log(&mut self) {
+        if let Some(idx) = self.board_selected_todo() {
+            let todo = &self.filtered_todos[idx];
+            let (path, line) = (todo.log_path.clone(), todo.line_number);
+            self.go_to_screen(Screen::ViewLog(path));
+            self.scroll_view_log_to(line);
+        }
+    }
+
     /// Load logs and go to log list screen
     pub fn show_logs(&mut self) -> Result<()> {
         self.logs = self.storage.load_all_logs()?;
@@ -386,7 +1247,7 @@ impl App {
         self.filtered_logs = self
             .logs
             .iter()
-            .filter(|l| self.log_filter.matches(l))
+            .filter(|l| self.log_filter.matches(l, &self.projects))
             .cloned()
             .collect();
     }
@@ -400,6 +1261,103 @@ impl App {
         }
     }
 
+    /// Open the attachment search over the text attachments of the filtered logs,
+    /// plus the notes of the projects the filter covers
+    pub fn open_attachment_search(&mut self) {
+        self.attachment_texts = self
+            .filtered_logs
+            .iter()
+            .flat_map(|log| {
+                log.attachments.iter().filter_map(|path| {
+                    Storage::read_text_attachment(path).map(|content| AttachmentText {
+                        timestamp: log.timestamp,
+                        path: path.clone(),
+                        content,
+                    })
+                })
+            })
+            .collect();
+
+        let filter = &self.log_filter.projects;
+        for project in &self.projects {
+            if !filter.is_empty() && !filter.iter().any(|f| project_within(&self.projects, &project.name, f)) {
+                continue;
+            }
+            let Some(content) = self.storage.load_project_notes(&project.name) else {
+                continue;
+            };
+            // Notes are listed by when they were last edited
+            let path = self.storage.project_notes_file(&project.name);
+            let modified = std::fs::metadata(&path).and_then(|m| m.modified()).map(DateTime::<Local>::from);
+            self.attachment_texts.push(AttachmentText {
+                timestamp: modified.unwrap_or_else(|_| Local::now()),
+                path,
+                content,
+            });
+        }
+        self.update_attachment_search();
+        self.log_filter_panel = LogFilterPanel::AttachmentSearch;
+    }
+
+    /// Recompute the attachment search results for the current query
+    pub fn update_attachment_search(&mut self) {
+        const MAX_RESULTS: usize = 200;
+        const SNIPPET_CONTEXT: usize = 24; // Chars shown before the match
+
+        self.attachment_search_results.clear();
+        self.attachment_search_selected = 0;
+
+        let lower = |c: char| c.to_lowercase().next().unwrap_or(c);
+        let query: Vec<char> = self.attachment_search_query.trim().chars().map(lower).collect();
+        if query.is_empty() {
+            return;
+        }
+
+        'texts: for (text_index, text) in self.attachment_texts.iter().enumerate() {
+            for (line_no, line) in text.content.lines().enumerate() {
+                let chars: Vec<char> = line.chars().collect();
+                let lowered: Vec<char> = chars.iter().map(|&c| lower(c)).collect();
+                let Some(pos) = lowered.windows(query.len()).position(|w| w == query.as_slice()) else {
+                    continue;
+                };
+
+                let start = pos.saturating_sub(SNIPPET_CONTEXT);
+                let mut snippet: String = chars[start..].iter().take(80).collect::<String>().trim_end().to_string();
+                let mut match_start = pos - start;
+                if start > 0 {
+                    snippet.insert(0, '…');
+                    match_start += 1;
+                }
+                self.attachment_search_results.push(AttachmentSearchResult {
+                    text_index,
+                    line: line_no,
+                    snippet,
+                    match_start,
+                    match_len: query.len(),
+                });
+                if self.attachment_search_results.len() >= MAX_RESULTS {
+                    break 'texts;
+                }
+            }
+        }
+    }
+
+    /// Open the attachment of the selected search result, scrolled to the match
+    pub fn open_attachment_search_result(&mut self) {
+        if let Some(result) = self.attachment_search_results.get(self.attachment_search_selected) {
+            let path = self.attachment_texts[result.text_index].path.clone();
+            let line = result.line;
+            self.log_filter_panel = LogFilterPanel::None;
+            self.go_to_screen(Screen::ViewLog(path));
+            self.scroll_view_log_to(line);
+        }
+    }
+
+    /// Scroll the viewed log so a line sits just below the top
+    fn scroll_view_log_to(&mut self, line: usize) {
+        self.view_log_scroll = u16::try_from(line.saturating_sub(2)).unwrap_or(u16::MAX);
+    }
+
     /// View log for the selected todo
     pub fn view_todo_log(&mut self) {
         if let Some(todo) = self.filtered_todos.get(self.todo_selected) {
@@ -422,6 +1380,8 @@ impl App {
     pub fn show_projects(&mut self) -> Result<()> {
         // Reload projects from file to ensure we have the latest data
         self.projects = self.storage.load_projects()?;
+        self.logs = self.storage.load_all_logs()?;
+        self.compute_project_stats();
         self.apply_project_filter();
         self.project_selected = 0;
         self.project_list_scroll = 0;
@@ -429,17 +1389,55 @@ impl App {
         Ok(())
     }
 
+    /// Gather todo counts and activity for every project in one pass over the logs
+    fn compute_project_stats(&mut self) {
+        let now = Local::now();
+        let mut stats: HashMap<String, ProjectStats> = HashMap::new();
+
+        // A tag counts for the project and all of its ancestors
+        let with_ancestors = |tags: &[String]| -> Vec<String> {
+            let mut names = Vec::new();
+            for tag in tags {
+                for name in std::iter::once(tag.clone()).chain(project_ancestors(&self.projects, tag)) {
+                    if !names.contains(&name) {
+                        names.push(name);
+                    }
+                }
+            }
+            names
+        };
+
+        for log in &self.logs {
+            let weeks_ago = (now - log.timestamp).num_weeks();
+            for name in with_ancestors(&log.projects) {
+                let entry = stats.entry(name).or_default();
+                if entry.last_log.is_none_or(|last| last < log.timestamp) {
+                    entry.last_log = Some(log.timestamp);
+                }
+                if (0..ACTIVITY_WEEKS as i64).contains(&weeks_ago) {
+                    entry.weekly_logs[ACTIVITY_WEEKS - 1 - weeks_ago as usize] += 1;
+                }
+            }
+            for todo in &log.todos {
+                for name in with_ancestors(&todo.projects) {
+                    let entry = stats.entry(name).or_default();
+                    if todo.completed {
+                        entry.completed_todos += 1;
+                    } else {
+                        entry.open_todos += 1;
+                    }
+                }
+            }
+        }
+
+        self.project_stats = stats;
+    }
+
     /// Apply the current project filter
     pub fn apply_project_filter(&mut self) {
         self.filtered_projects = self.projects
             .iter()
-            .filter(|p| {
-                if self.project_filter_groups.is_empty() {
-                    true
-                } else {
-                    self.project_filter_groups.contains(&p.group)
-                }
-            })
+            .filter(|p| self.project_filter.matches(p, &self.config))
             .cloned()
             .collect();
 
@@ -447,28 +1445,79 @@ impl App {
         self.sort_filtered_projects_by_display_order();
     }
 
-    /// Sort filtered projects to match the display order (grouped, then sorted by group)
+    /// Sort filtered projects to match the display order: grouped, then as a tree within each group
     fn sort_filtered_projects_by_display_order(&mut self) {
-        self.filtered_projects.sort_by(|a, b| {
-            let group_a = if a.group.is_empty() { "(No group)" } else { a.group.as_str() };
-            let group_b = if b.group.is_empty() { "(No group)" } else { b.group.as_str() };
-
-            // Sort groups alphabetically, with "(No group)" last
-            let group_order = if group_a == "(No group)" && group_b != "(No group)" {
-                std::cmp::Ordering::Greater
-            } else if group_b == "(No group)" && group_a != "(No group)" {
-                std::cmp::Ordering::Less
-            } else {
-                group_a.cmp(group_b)
-            };
+        let projects = std::mem::take(&mut self.filtered_projects);
+        self.filtered_project_depths.clear();
+
+        // Sort groups alphabetically, with "(No group)" last
+        let mut groups: Vec<&str> = Vec::new();
+        for project in &projects {
+            if !groups.contains(&project.group.as_str()) {
+                groups.push(&project.group);
+            }
+        }
+        groups.sort_by_key(|g| (g.is_empty(), g.to_string()));
 
-            // If in the same group, maintain original order
-            if group_order == std::cmp::Ordering::Equal {
-                std::cmp::Ordering::Equal
-            } else {
-                group_order
+        for group in groups {
+            let members: Vec<&Project> = projects.iter().filter(|p| p.group == group).collect();
+
+            // Each project hangs under its nearest ancestor shown in the same group
+            let parent_of = |project: &Project| -> Option<String> {
+                project_ancestors(&self.projects, &project.name)
+                    .into_iter()
+                    .find(|a| members.iter().any(|m| &m.name == a))
+            };
+
+            // Depth-first walk keeping the original order among siblings
+            let mut stack: Vec<(&Project, usize)> =
+                members.iter().rev().filter(|p| parent_of(p).is_none()).map(|p| (*p, 0)).collect();
+            let mut ordered: Vec<(Project, usize)> = Vec::new();
+            while let Some((project, depth)) = stack.pop() {
+                ordered.push((project.clone(), depth));
+                for child in members.iter().rev() {
+                    if parent_of(child).as_deref() == Some(project.name.as_str()) {
+                        stack.push((child, depth + 1));
+                    }
+                }
             }
-        });
+
+            // Projects caught in a parent cycle are shown as roots
+            for project in &members {
+                if !ordered.iter().any(|(p, _)| p.name == project.name) {
+                    ordered.push(((*project).clone(), 0));
+                }
+            }
+
+            // Any sort other than the tree flattens it
+            if self.project_sort != ProjectSort::Tree {
+                ordered.sort_by(|(a, _), (b, _)| self.compare_projects(a, b));
+                for (_, depth) in ordered.iter_mut() {
+                    *depth = 0;
+                }
+            }
+
+            for (project, depth) in ordered {
+                self.filtered_projects.push(project);
+                self.filtered_project_depths.push(depth);
+            }
+        }
+    }
+
+    /// Order two projects by the current project list sort
+    fn compare_projects(&self, a: &Project, b: &Project) -> std::cmp::Ordering {
+        let stats = |p: &Project| self.project_stats.get(&p.name).cloned().unwrap_or_default();
+        match self.project_sort {
+            ProjectSort::Tree => std::cmp::Ordering::Equal,
+            // Most recent first, projects without logs last
+            ProjectSort::LastActivity => stats(b).last_log.cmp(&stats(a).last_log),
+            ProjectSort::Name => a.name.cmp(&b.name),
+            ProjectSort::Status => (self.config.is_terminal_state(&a.status), &a.status)
+                .cmp(&(self.config.is_terminal_state(&b.status), &b.status)),
+            ProjectSort::OpenTodos => stats(b).open_todos.cmp(&stats(a).open_todos),
+            ProjectSort::Deadline => (a.next_deadline().is_none(), a.next_deadline())
+                .cmp(&(b.next_deadline().is_none(), b.next_deadline())),
+        }
     }
 
     /// Get all unique group names from projects
@@ -492,12 +1541,247 @@ impl App {
         // Convert "(No group)" back to empty string
         let group = if group == "(No group)" { "" } else { group };
 
-        if let Some(pos) = self.project_filter_groups.iter().position(|g| g == group) {
-            self.project_filter_groups.remove(pos);
+        if let Some(pos) = self.project_filter.groups.iter().position(|g| g == group) {
+            self.project_filter.groups.remove(pos);
         } else {
-            self.project_filter_groups.push(group.to_string());
+            self.project_filter.groups.push(group.to_string());
+        }
+        self.apply_project_filter();
+    }
+
+    /// Toggle a status in the project filter
+    pub fn toggle_project_filter_status(&mut self, status: &str) {
+        self.project_filter.toggle_status(status, &self.config);
+        self.apply_project_filter();
+    }
+
+    /// The enum and tag-reference field values the project list can be filtered by
+    pub fn project_field_facets(&self) -> Vec<(CustomField, String)> {
+        field_facets(&self.config.fields.projects, self.projects.iter().map(|p| &p.fields))
+    }
+
+    /// Choose or unchoose a field value in the project filter
+    pub fn toggle_project_filter_field(&mut self, idx: usize) {
+        if let Some((field, value)) = self.project_field_facets().get(idx) {
+            toggle_facet(&mut self.project_filter.fields, &field.name, value);
+            self.apply_project_filter();
+        }
+    }
+
+    /// Go back to showing every non-terminal status
+    pub fn reset_project_filter_statuses(&mut self) {
+        self.project_filter.statuses = None;
+        self.apply_project_filter();
+    }
+
+    /// Close the project filter panel and remember the filter for next time
+    pub fn close_project_filter_panel(&mut self) {
+        self.project_filter_panel = ProjectFilterPanel::None;
+        self.project_selected = 0;
+        self.project_list_scroll = 0;
+        self.ui_state.project_filter = self.project_filter.clone();
+        if let Err(e) = self.storage.save_ui_state(&self.ui_state) {
+            self.status_message = Some(format!("Error saving state: {}", e));
+        }
+    }
+
+    /// Open the settings screen on a copy of the current config
+    pub fn show_settings(&mut self) {
+        self.projects = self.storage.load_projects().unwrap_or_default();
+        self.settings_config = self.config.clone();
+        self.settings_section = SettingsSection::States;
+        self.settings_state_selected = 0;
+        self.settings_group_selected = 0;
+        self.settings_input = None;
+        self.settings_input_renaming = None;
+        self.settings_rename_confirm = None;
+        self.settings_project_renames.clear();
+        self.go_to_screen(Screen::Settings);
+    }
+
+    /// The entries of a settings list: states by name, groups in config order
+    pub fn settings_items(&self, section: SettingsSection) -> Vec<String> {
+        match section {
+            SettingsSection::States => self.settings_config.allowed_state_names(),
+            SettingsSection::Groups => self.settings_config.allowed_groups(),
+        }
+    }
+
+    /// The selected entry of the focused settings list
+    pub fn settings_selected_item(&self) -> Option<String> {
+        let selected = match self.settings_section {
+            SettingsSection::States => self.settings_state_selected,
+            SettingsSection::Groups => self.settings_group_selected,
+        };
+        self.settings_items(self.settings_section).get(selected).cloned()
+    }
+
+    /// Select an entry of the focused settings list by name
+    fn settings_select(&mut self, name: &str) {
+        let idx = self.settings_items(self.settings_section).iter().position(|n| n == name).unwrap_or(0);
+        match self.settings_section {
+            SettingsSection::States => self.settings_state_selected = idx,
+            SettingsSection::Groups => self.settings_group_selected = idx,
+        }
+    }
+
+    /// How many projects use a state or group, counting renames they will follow on save
+    pub fn settings_usage(&self, section: SettingsSection, name: &str) -> usize {
+        self.projects
+            .iter()
+            .map(|p| match section {
+                SettingsSection::States => p.status.as_str(),
+                SettingsSection::Groups => p.group.as_str(),
+            })
+            .filter(|value| self.settings_renamed(section, value) == name)
+            .count()
+    }
+
+    /// A project's state or group after the accepted renames
+    fn settings_renamed(&self, section: SettingsSection, value: &str) -> String {
+        let mut value = value.to_string();
+        for rename in self.settings_project_renames.iter().filter(|r| r.section == section) {
+            if value == rename.from {
+                value = rename.to.clone();
+            }
+        }
+        value
+    }
+
+    /// Open the name input, prefilled with the selected entry when renaming
+    pub fn start_settings_input(&mut self, rename: bool) {
+        self.settings_input_renaming = if rename { self.settings_selected_item() } else { None };
+        if rename && self.settings_input_renaming.is_none() {
+            return;
+        }
+        self.settings_input = Some(self.settings_input_renaming.clone().unwrap_or_default());
+    }
+
+    /// Add or rename the entry typed in the settings input
+    pub fn apply_settings_input(&mut self) {
+        let name = self.settings_input.as_deref().unwrap_or("").trim().to_string();
+        let section = self.settings_section;
+        let renaming = self.settings_input_renaming.clone();
+        if name.is_empty() {
+            self.status_message = Some("Error: Name cannot be empty".to_string());
+            return;
+        }
+        if renaming.as_deref() != Some(name.as_str()) && self.settings_items(section).contains(&name) {
+            self.status_message = Some(format!("Error: '{}' already exists", name));
+            return;
+        }
+        self.settings_input = None;
+        self.settings_input_renaming = None;
+
+        match renaming {
+            Some(from) if from == name => {}
+            Some(from) => {
+                match section {
+                    SettingsSection::States => self.settings_config.rename_state(&from, &name),
+                    SettingsSection::Groups => {
+                        for group in self.settings_config.projects.groups.iter_mut() {
+                            if *group == from {
+                                *group = name.clone();
+                            }
+                        }
+                    }
+                }
+                let projects = self.settings_usage(section, &from);
+                if projects > 0 {
+                    self.settings_rename_confirm = Some(SettingsRename { section, from, to: name.clone(), projects });
+                }
+            }
+            None => match section {
+                SettingsSection::States => {
+                    self.settings_config
+                        .projects
+                        .allowed_states
+                        .insert(name.clone(), StateConfig::with_color("gray"));
+                }
+                SettingsSection::Groups => self.settings_config.projects.groups.push(name.clone()),
+            },
+        }
+        self.settings_select(&name);
+    }
+
+    /// Answer the rename prompt: move the projects to the new name on save, or leave them
+    pub fn answer_settings_rename(&mut self, update_projects: bool) {
+        if let Some(rename) = self.settings_rename_confirm.take() {
+            if update_projects {
+                self.settings_project_renames.push(rename);
+            }
+        }
+    }
+
+    /// Remove the selected state or group, unless projects still use it
+    pub fn remove_settings_item(&mut self) {
+        let Some(name) = self.settings_selected_item() else {
+            return;
+        };
+        let used = self.settings_usage(self.settings_section, &name);
+        if used > 0 {
+            self.status_message = Some(format!("Error: {} project(s) use '{}', rename it instead", used, name));
+            return;
+        }
+        match self.settings_section {
+            SettingsSection::States => {
+                if self.settings_config.projects.allowed_states.len() == 1 {
+                    self.status_message = Some("Error: At least one state is needed".to_string());
+                    return;
+                }
+                self.settings_config.remove_state(&name);
+                let len = self.settings_config.projects.allowed_states.len();
+                self.settings_state_selected = self.settings_state_selected.min(len.saturating_sub(1));
+            }
+            SettingsSection::Groups => {
+                self.settings_config.projects.groups.retain(|g| *g != name);
+                let len = self.settings_config.projects.groups.len();
+                self.settings_group_selected = self.settings_group_selected.min(len.saturating_sub(1));
+            }
         }
+    }
+
+    /// Pick the previous or next palette colour for the selected state
+    pub fn cycle_settings_color(&mut self, forward: bool) {
+        if self.settings_section != SettingsSection::States {
+            return;
+        }
+        if let Some(name) = self.settings_selected_item() {
+            self.settings_config.cycle_state_color(&name, forward);
+        }
+    }
+
+    /// Write config.yml, move projects along with accepted renames and apply the config
+    pub fn save_settings(&mut self) -> Result<()> {
+        self.storage.save_config(&self.settings_config)?;
+
+        if !self.settings_project_renames.is_empty() {
+            let mut projects = std::mem::take(&mut self.projects);
+            for project in projects.iter_mut() {
+                project.status = self.settings_renamed(SettingsSection::States, &project.status);
+                project.group = self.settings_renamed(SettingsSection::Groups, &project.group);
+            }
+            self.projects = projects;
+            self.storage.save_projects(&self.projects)?;
+
+            // Keep the project list filter pointing at the renamed entries
+            let mut filter = self.project_filter.clone();
+            for group in filter.groups.iter_mut() {
+                *group = self.settings_renamed(SettingsSection::Groups, group);
+            }
+            for status in filter.statuses.iter_mut().flatten() {
+                *status = self.settings_renamed(SettingsSection::States, status);
+            }
+            self.project_filter = filter;
+            self.ui_state.project_filter = self.project_filter.clone();
+            self.storage.save_ui_state(&self.ui_state)?;
+        }
+
+        self.config = self.settings_config.clone();
         self.apply_project_filter();
+        self.status_message = Some("Settings saved".to_string());
+        self.go_back();
+        Ok(())
     }
 
     /// Show project details for the selected project
@@ -508,12 +1792,24 @@ impl App {
                 // Load all logs
                 self.logs = self.storage.load_all_logs()?;
 
-                // Filter logs that contain this project
+                // Filter logs that contain this project or one of its sub-projects
                 self.project_details_logs = self.logs
                     .iter()
-                    .filter(|log| log.projects.contains(&project.name))
+                    .filter(|log| log.projects.iter().any(|p| project_within(&self.projects, p, &project.name)))
                     .cloned()
                     .collect();
+                self.details_todos = self
+                    .logs
+                    .iter()
+                    .flat_map(|log| &log.todos)
+                    .filter(|t| t.projects.iter().any(|p| project_within(&self.projects, p, &project.name)))
+                    .cloned()
+                    .collect();
+                self.project_details_open_todos = self.details_todos.iter().filter(|t| !t.completed).count();
+                self.details_tab = DetailsTab::Logs;
+                self.details_todo_selected = 0;
+                self.project_details_notes = self.storage.load_project_notes(&project.name);
+                self.project_details_notes_scroll = 0;
 
                 self.project_details_log_selected = 0;
                 self.go_to_screen(Screen::ProjectDetails(idx));
@@ -522,6 +1818,61 @@ impl App {
         Ok(())
     }
 
+    /// Open the notes of the project shown in details in the log editor
+    pub fn start_edit_notes(&mut self) {
+        let Screen::ProjectDetails(idx) = self.screen else {
+            return;
+        };
+        let Some(project) = self.projects.get(idx) else {
+            return;
+        };
+        let notes = self.project_details_notes.clone().unwrap_or_default();
+        self.notes_editing = Some(project.name.clone());
+        self.notes_saved = notes.clone();
+        self.current_log = LogEntry::new();
+        self.current_log.content = notes;
+        self.log_cursor_pos = 0;
+        self.attachments.clear();
+        self.attachments_focused = false;
+        self.attachment_rename = None;
+        self.autocomplete_suggestions.clear();
+        self.autocomplete_active = false;
+        self.draft_file = None;
+        self.exit_prompt_open = false;
+        self.refresh_tag_frecency();
+        self.go_to_screen(Screen::LogEntry);
+    }
+
+    /// Save the notes open in the log editor and return to the project
+    fn save_notes(&mut self) -> Result<()> {
+        let Some(project) = self.notes_editing.take() else {
+            return Ok(());
+        };
+        self.storage.save_project_notes(&project, &self.current_log.content)?;
+        self.project_details_notes = self.storage.load_project_notes(&project);
+        self.status_message = Some(format!("Notes for '{}' saved", project));
+        self.go_back();
+        Ok(())
+    }
+
+    /// Have the main loop open the notes of the project shown in details in $EDITOR
+    pub fn start_external_notes_edit(&mut self) {
+        if let Screen::ProjectDetails(idx) = self.screen {
+            if let Some(project) = self.projects.get(idx) {
+                self.external_edit = Some(self.storage.project_notes_file(&project.name));
+            }
+        }
+    }
+
+    /// Pick up notes changed outside the app
+    pub fn reload_project_notes(&mut self) {
+        if let Screen::ProjectDetails(idx) = self.screen {
+            if let Some(project) = self.projects.get(idx) {
+                self.project_details_notes = self.storage.load_project_notes(&project.name);
+            }
+        }
+    }
+
     /// Start editing a project from the details screen
     pub fn start_edit_project_from_details(&mut self) {
         if let Screen::ProjectDetails(idx) = self.screen {
@@ -531,10 +1882,20 @@ impl App {
                 self.project_edit_jira = project.jira.clone().unwrap_or_default();
                 self.project_edit_status = project.status.clone();
                 self.project_edit_group = project.group.clone();
+                self.project_edit_parent = project.parent.clone().unwrap_or_default();
+                self.project_edit_target_date = project.target_date.map(|d| d.format("%Y-%m-%d").to_string()).unwrap_or_default();
+                self.project_edit_milestones = project.milestones.clone();
+                self.project_edit_milestone_selected = 0;
+                self.project_edit_milestone_input = None;
+                self.project_edit_status_reason = None;
+                self.edit_custom_fields = project.fields.clone();
+                self.custom_field_selected = 0;
+                self.custom_field_input = None;
+                self.edit_errors.clear();
                 self.project_edit_field = 0;
 
                 // Initialize dropdown selected indices
-                let states = self.config.allowed_state_names();
+                let states = self.config.next_states(&project.status);
                 self.project_edit_status_dropdown_selected = states
                     .iter()
                     .position(|s| s == &project.status)
@@ -567,14 +1928,27 @@ impl App {
         self.project_edit_description = String::new();
         self.project_edit_jira = String::new();
 
-        // Initialize with first available status
-        let states = self.config.allowed_state_names();
-        self.project_edit_status = states.first().cloned().unwrap_or_else(|| "open".to_string());
-        self.project_edit_status_dropdown_selected = 0;
+        // Initialize with the first non-terminal status
+        self.project_edit_status = self.config.initial_state();
+        self.project_edit_status_dropdown_selected = self
+            .project_edit_status_options()
+            .iter()
+            .position(|s| s == &self.project_edit_status)
+            .unwrap_or(0);
 
         // Initialize with no group
         self.project_edit_group = String::new();
         self.project_edit_group_dropdown_selected = 0; // "(no group)" is at index 0
+        self.project_edit_parent = String::new();
+        self.project_edit_target_date = String::new();
+        self.project_edit_milestones = Vec::new();
+        self.project_edit_milestone_selected = 0;
+        self.project_edit_milestone_input = None;
+        self.project_edit_status_reason = None;
+        self.edit_custom_fields = BTreeMap::new();
+        self.custom_field_selected = 0;
+        self.custom_field_input = None;
+        self.edit_errors.clear();
 
         self.project_edit_field = 0;
         self.project_edit_status_dropdown_open = false;
@@ -583,12 +1957,175 @@ impl App {
         self.go_to_screen(Screen::ProjectEdit(None));
     }
 
+    /// Build a new project from the edit form fields
+    fn project_from_edit_fields(&self) -> Project {
+        Project {
+            name: self.project_edit_name.clone(),
+            description: if self.project_edit_description.is_empty() {
+                None
+            } else {
+                Some(self.project_edit_description.clone())
+            },
+            jira: if self.project_edit_jira.is_empty() {
+                None
+            } else {
+                Some(self.project_edit_jira.clone())
+            },
+            status: self.project_edit_status.clone(),
+            group: self.project_edit_group.clone(),
+            parent: if self.project_edit_parent.is_empty() {
+                None
+            } else {
+                Some(self.project_edit_parent.clone())
+            },
+            target_date: self.edit_target_date(),
+            milestones: self.project_edit_milestones.clone(),
+            status_history: vec![StatusChange {
+                at: Local::now(),
+                from: String::new(),
+                to: self.project_edit_status.clone(),
+                reason: None,
+            }],
+            archived: false,
+            fields: self.edit_custom_fields.clone(),
+        }
+    }
+
+    /// The states offered by the status dropdown: valid next states when editing, all when creating
+    pub fn project_edit_status_options(&self) -> Vec<String> {
+        match self.screen {
+            Screen::ProjectEdit(Some(idx)) => match self.projects.get(idx) {
+                Some(project) => self.config.next_states(&project.status),
+                None => self.config.allowed_state_names(),
+            },
+            _ => self.config.allowed_state_names(),
+        }
+    }
+
+    /// The target date typed in the project edit form, if it's a valid date
+    fn edit_target_date(&self) -> Option<NaiveDate> {
+        NaiveDate::parse_from_str(self.project_edit_target_date.trim(), "%Y-%m-%d").ok()
+    }
+
+    /// Problems with the project edit form as it stands
+    fn project_edit_errors(&self, editing: Option<usize>) -> FieldErrors {
+        let mut errors = validation::validate_project(&self.project_from_edit_fields(), &self.projects, editing, &self.config);
+        if !self.project_edit_target_date.trim().is_empty() && self.edit_target_date().is_none() {
+            errors.insert(Field::TargetDate, "Must be a date (YYYY-MM-DD)".to_string());
+        }
+        errors
+    }
+
+    /// Problems with the person edit form as it stands
+    fn person_edit_errors(&self, editing: Option<usize>) -> FieldErrors {
+        validation::validate_person(&self.person_from_edit_fields(), &self.people, editing)
+    }
+
+    /// Store the errors found on save and focus the first offending field.
+    /// Returns whether the form is valid.
+    fn accept_edit_errors(&mut self, errors: FieldErrors) -> bool {
+        self.edit_errors = errors;
+        let Some((&field, error)) = self.edit_errors.iter().next() else {
+            return true;
+        };
+        self.status_message = Some(format!("Error: {}", error));
+        if matches!(self.screen, Screen::PersonEdit(_)) {
+            if let Some(idx) = person_edit_field_index(field) {
+                self.person_edit_field = idx;
+            }
+        } else if let Some(idx) = project_edit_field_index(field) {
+            self.project_edit_field = idx;
+            self.project_edit_status_dropdown_open = false;
+            self.project_edit_group_dropdown_open = false;
+        }
+        false
+    }
+
+    /// Re-check an edit form after a failed save, so errors clear as they are fixed
+    pub fn refresh_edit_errors(&mut self) {
+        if self.edit_errors.is_empty() {
+            return;
+        }
+        self.edit_errors = match self.screen {
+            Screen::ProjectEdit(editing) => self.project_edit_errors(editing),
+            Screen::PersonEdit(editing) => self.person_edit_errors(editing),
+            _ => FieldErrors::new(),
+        };
+    }
+
+    /// The error shown on a field of the edit form, if any
+    pub fn edit_error(&self, field: Field) -> Option<&str> {
+        self.edit_errors.get(&field).map(String::as_str)
+    }
+
+    /// Build a new person from the edit form fields
+    fn person_from_edit_fields(&self) -> Person {
+        Person {
+            name: self.person_edit_name.clone(),
+            full_name: if self.person_edit_full_name.is_empty() {
+                None
+            } else {
+                Some(self.person_edit_full_name.clone())
+            },
+            email: if self.person_edit_email.is_empty() {
+                None
+            } else {
+                Some(self.person_edit_email.clone())
+            },
+            tel: if self.person_edit_tel.is_empty() {
+                None
+            } else {
+                Some(self.person_edit_tel.clone())
+            },
+            company: if self.person_edit_company.is_empty() {
+                None
+            } else {
+                Some(self.person_edit_company.clone())
+            },
+            archived: false,
+            fields: self.edit_custom_fields.clone(),
+        }
+    }
+
     /// Save the edited project
     pub fn save_edited_project(&mut self) -> Result<()> {
         if let Screen::ProjectEdit(idx_opt) = self.screen {
+            self.project_edit_name = self.project_edit_name.trim().to_string();
+            self.project_edit_jira = self.project_edit_jira.trim().to_string();
+            self.project_edit_parent = self.project_edit_parent.trim().to_string();
+            if !self.accept_edit_errors(self.project_edit_errors(idx_opt)) {
+                self.project_edit_status_reason = None;
+                return Ok(());
+            }
+
             match idx_opt {
                 Some(idx) => {
+                    // A status change asks for a reason before saving, required by some transitions
+                    let old_status = self.projects.get(idx).map(|p| p.status.clone()).unwrap_or_default();
+                    let status_changed = old_status != self.project_edit_status;
+                    if status_changed && self.project_edit_status_reason.is_none() {
+                        self.project_edit_status_reason = Some(String::new());
+                        return Ok(());
+                    }
+                    let comment_required = self.config.transition_requires_comment(&old_status, &self.project_edit_status);
+                    if comment_required && self.project_edit_status_reason.as_deref().is_some_and(|r| r.trim().is_empty()) {
+                        self.status_message = Some(format!("Error: Moving to '{}' needs a comment", self.project_edit_status));
+                        return Ok(());
+                    }
+                    let reason = self.project_edit_status_reason.take().filter(|r| !r.trim().is_empty());
+
+                    // Keep sub-projects attached when a parent is renamed
+                    let old_name = self.projects.get(idx).map(|p| p.name.clone()).unwrap_or_default();
+                    if old_name != self.project_edit_name {
+                        for child in self.projects.iter_mut() {
+                            if child.parent.as_deref() == Some(old_name.as_str()) {
+                                child.parent = Some(self.project_edit_name.clone());
+                            }
+                        }
+                    }
+
                     // Editing existing project
+                    let target_date = self.edit_target_date();
                     if let Some(project) = self.projects.get_mut(idx) {
                         project.name = self.project_edit_name.clone();
                         project.description = if self.project_edit_description.is_empty() {
@@ -601,30 +2138,37 @@ impl App {
                         } else {
                             Some(self.project_edit_jira.clone())
                         };
-                        project.status = self.project_edit_status.clone();
+                        project.set_status(&self.project_edit_status, reason.clone());
                         project.group = self.project_edit_group.clone();
+                        project.parent = if self.project_edit_parent.is_empty() {
+                            None
+                        } else {
+                            Some(self.project_edit_parent.clone())
+                        };
+                        project.target_date = target_date;
+                        project.milestones = self.project_edit_milestones.clone();
+                        project.fields = self.edit_custom_fields.clone();
 
                         self.storage.save_projects(&self.projects)?;
                         self.status_message = Some("Project saved".to_string());
                     }
+                    if old_name != self.project_edit_name {
+                        self.storage.rename_project_notes(&old_name, &self.project_edit_name)?;
+                    }
+
+                    // The comment also goes into the log, tagged with the project
+                    if let (true, Some(reason)) = (status_changed, reason) {
+                        let content = format!(
+                            "#{} status: {} → {}\n\n{}\n",
+                            self.project_edit_name, old_status, self.project_edit_status, reason.trim()
+                        );
+                        let entry = LogEntry::parse(&content, PathBuf::new());
+                        self.storage.save_log_entry(&entry, &[], self.config.attachments.dedupe)?;
+                    }
                 }
                 None => {
                     // Creating new project
-                    let new_project = Project {
-                        name: self.project_edit_name.clone(),
-                        description: if self.project_edit_description.is_empty() {
-                            None
-                        } else {
-                            Some(self.project_edit_description.clone())
-                        },
-                        jira: if self.project_edit_jira.is_empty() {
-                            None
-                        } else {
-                            Some(self.project_edit_jira.clone())
-                        },
-                        status: self.project_edit_status.clone(),
-                        group: self.project_edit_group.clone(),
-                    };
+                    let new_project = self.project_from_edit_fields();
 
                     self.projects.push(new_project);
                     self.storage.save_projects(&self.projects)?;
@@ -643,17 +2187,312 @@ impl App {
         Ok(())
     }
 
+    /// Open the milestone input, prefilled when editing an existing milestone
+    pub fn start_milestone_input(&mut self, editing: Option<usize>) {
+        let text = editing
+            .and_then(|i| self.project_edit_milestones.get(i))
+            .map(|m| format!("{} {}", m.date.format("%Y-%m-%d"), m.name))
+            .unwrap_or_else(|| format!("{} ", Local::now().format("%Y-%m-%d")));
+        self.project_edit_milestone_editing = editing;
+        self.project_edit_milestone_input = Some(text);
+    }
+
+    /// Add or update the milestone from the input, keeping the list ordered by date
+    pub fn apply_milestone_input(&mut self) {
+        let Some(input) = self.project_edit_milestone_input.as_deref() else {
+            return;
+        };
+        let Some(mut milestone) = Milestone::parse(input) else {
+            self.status_message = Some("Error: Milestone must be 'YYYY-MM-DD name'".to_string());
+            return;
+        };
+
+        match self.project_edit_milestone_editing.and_then(|i| self.project_edit_milestones.get_mut(i)) {
+            Some(existing) => {
+                milestone.done = existing.done;
+                *existing = milestone.clone();
+            }
+            None => self.project_edit_milestones.push(milestone.clone()),
+        }
+        self.project_edit_milestones.sort_by_key(|m| m.date);
+        self.project_edit_milestone_selected = self
+            .project_edit_milestones
+            .iter()
+            .position(|m| m.name == milestone.name && m.date == milestone.date)
+            .unwrap_or(0);
+        self.project_edit_milestone_input = None;
+    }
+
+    /// Toggle the done flag of the selected milestone
+    pub fn toggle_milestone_done(&mut self) {
+        if let Some(milestone) = self.project_edit_milestones.get_mut(self.project_edit_milestone_selected) {
+            milestone.done = !milestone.done;
+        }
+    }
+
+    /// Remove the selected milestone
+    pub fn remove_milestone(&mut self) {
+        if self.project_edit_milestone_selected < self.project_edit_milestones.len() {
+            self.project_edit_milestones.remove(self.project_edit_milestone_selected);
+            self.project_edit_milestone_selected = self
+                .project_edit_milestone_selected
+                .min(self.project_edit_milestones.len().saturating_sub(1));
+        }
+    }
+
+    /// Switch the project list between tree order and nearest deadline first
+    pub fn toggle_project_sort_by_deadline(&mut self) {
+        let sort = if self.project_sort == ProjectSort::Deadline { ProjectSort::Tree } else { ProjectSort::Deadline };
+        self.set_project_sort(sort);
+    }
+
+    /// Change the project list order, keeping the selection at the top
+    pub fn set_project_sort(&mut self, sort: ProjectSort) {
+        self.project_sort = sort;
+        self.apply_project_filter();
+        self.project_selected = 0;
+        self.project_list_scroll = 0;
+    }
+
+    /// Custom fields declared for the entity being edited
+    pub fn custom_field_defs(&self) -> Vec<CustomField> {
+        match self.screen {
+            Screen::ProjectEdit(_) => self.config.fields.projects.clone(),
+            Screen::PersonEdit(_) => self.config.fields.people.clone(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Start typing a value for the selected custom field; enum fields cycle through their options instead
+    pub fn start_custom_field_input(&mut self) {
+        let Some(field) = self.custom_field_defs().get(self.custom_field_selected).cloned() else {
+            return;
+        };
+        if field.kind == CustomFieldKind::Enum {
+            self.cycle_custom_field_option(true);
+            return;
+        }
+        self.custom_field_input = Some(self.edit_custom_fields.get(&field.name).cloned().unwrap_or_default());
+    }
+
+    /// Validate and store the typed custom field value, keeping the input open on errors
+    pub fn apply_custom_field_input(&mut self) {
+        let Some(field) = self.custom_field_defs().get(self.custom_field_selected).cloned() else {
+            self.custom_field_input = None;
+            return;
+        };
+        let input = self.custom_field_input.clone().unwrap_or_default();
+        match field.validate(&input, &self.projects, &self.people) {
+            Ok(value) => {
+                self.set_custom_field(&field.name, value);
+                self.custom_field_input = None;
+            }
+            Err(error) => self.status_message = Some(format!("Error: {}", error)),
+        }
+    }
+
+    /// Apply a custom field value still being typed, returning false if it was rejected
+    pub fn apply_pending_custom_field(&mut self) -> bool {
+        if self.custom_field_input.is_some() {
+            self.apply_custom_field_input();
+        }
+        self.custom_field_input.is_none()
+    }
+
+    /// Step the selected enum field to its next or previous option, passing through empty
+    pub fn cycle_custom_field_option(&mut self, forward: bool) {
+        let Some(field) = self.custom_field_defs().get(self.custom_field_selected).cloned() else {
+            return;
+        };
+        if field.kind != CustomFieldKind::Enum || field.options.is_empty() {
+            return;
+        }
+        let mut choices = vec![String::new()];
+        choices.extend(field.options.iter().cloned());
+        let current = self.edit_custom_fields.get(&field.name).cloned().unwrap_or_default();
+        let pos = choices.iter().position(|c| c == &current).unwrap_or(0);
+        let next = if forward {
+            (pos + 1) % choices.len()
+        } else {
+            (pos + choices.len() - 1) % choices.len()
+        };
+        self.set_custom_field(&field.name, choices[next].clone());
+    }
+
+    /// Clear the selected custom field
+    pub fn clear_custom_field(&mut self) {
+        if let Some(field) = self.custom_field_defs().get(self.custom_field_selected) {
+            self.edit_custom_fields.remove(&field.name);
+        }
+    }
+
+    fn set_custom_field(&mut self, name: &str, value: String) {
+        if value.is_empty() {
+            self.edit_custom_fields.remove(name);
+        } else {
+            self.edit_custom_fields.insert(name.to_string(), value);
+        }
+    }
+
     /// Show people and go to people list screen
     pub fn show_people(&mut self) -> Result<()> {
+        self.apply_people_filter();
         self.person_selected = 0;
         self.person_list_scroll = 0;
         self.go_to_screen(Screen::PeopleList);
         Ok(())
     }
 
+    /// Refresh the people list, hiding archived people unless asked for
+    pub fn apply_people_filter(&mut self) {
+        self.filtered_people = self
+            .people
+            .iter()
+            .filter(|p| !p.archived || self.people_show_archived)
+            .filter(|p| self.people_filter.matches(p))
+            .cloned()
+            .collect();
+    }
+
+    /// The enum and tag-reference field values the people list can be filtered by
+    pub fn people_field_facets(&self) -> Vec<(CustomField, String)> {
+        field_facets(&self.config.fields.people, self.people.iter().map(|p| &p.fields))
+    }
+
+    /// Choose or unchoose a field value in the people filter
+    pub fn toggle_people_filter_field(&mut self, idx: usize) {
+        if let Some((field, value)) = self.people_field_facets().get(idx) {
+            toggle_facet(&mut self.people_filter.fields, &field.name, value);
+            self.apply_people_filter();
+        }
+    }
+
+    /// Close the people filter panel and remember the filter for next time
+    pub fn close_people_filter_panel(&mut self) {
+        self.people_filter_panel = PeopleFilterPanel::None;
+        self.person_selected = 0;
+        self.person_list_scroll = 0;
+        self.ui_state.people_filter = self.people_filter.clone();
+        if let Err(e) = self.storage.save_ui_state(&self.ui_state) {
+            self.status_message = Some(format!("Error saving state: {}", e));
+        }
+    }
+
+    /// Show or hide archived people in the people list
+    pub fn toggle_people_show_archived(&mut self) {
+        self.people_show_archived = !self.people_show_archived;
+        self.apply_people_filter();
+        self.person_selected = 0;
+        self.person_list_scroll = 0;
+    }
+
+    /// Show or hide archived projects in the project list
+    pub fn toggle_projects_show_archived(&mut self) {
+        self.project_filter.show_archived = !self.project_filter.show_archived;
+        self.apply_project_filter();
+        self.close_project_filter_panel();
+    }
+
+    /// Archive or restore the project or person shown on the details screen
+    pub fn toggle_archived(&mut self) -> Result<()> {
+        match self.screen {
+            Screen::ProjectDetails(idx) => {
+                if let Some(project) = self.projects.get_mut(idx) {
+                    project.archived = !project.archived;
+                    self.status_message = Some(if project.archived {
+                        format!("Archived project '{}'", project.name)
+                    } else {
+                        format!("Restored project '{}'", project.name)
+                    });
+                    self.storage.save_projects(&self.projects)?;
+                    self.apply_project_filter();
+                }
+            }
+            Screen::PersonDetails(idx) => {
+                if let Some(person) = self.people.get_mut(idx) {
+                    person.archived = !person.archived;
+                    self.status_message = Some(if person.archived {
+                        format!("Archived person '{}'", person.name)
+                    } else {
+                        format!("Restored person '{}'", person.name)
+                    });
+                    self.storage.save_people(&self.people)?;
+                    self.apply_people_filter();
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Ask before deleting the project or person on the details screen, counting what still uses the tag
+    pub fn start_delete(&mut self) {
+        let (name, is_project) = match self.screen {
+            Screen::ProjectDetails(idx) => match self.projects.get(idx) {
+                Some(p) => (p.name.clone(), true),
+                None => return,
+            },
+            Screen::PersonDetails(idx) => match self.people.get(idx) {
+                Some(p) => (p.name.clone(), false),
+                None => return,
+            },
+            _ => return,
+        };
+
+        let tags = |projects: &[String], people: &[String]| {
+            if is_project {
+                projects.contains(&name)
+            } else {
+                people.contains(&name)
+            }
+        };
+        let logs = self.logs.iter().filter(|l| tags(&l.projects, &l.people)).count();
+        let todos = self
+            .logs
+            .iter()
+            .flat_map(|l| &l.todos)
+            .filter(|t| tags(&t.projects, &t.people))
+            .count();
+        self.delete_confirm = Some(DeleteConfirm { name, logs, todos });
+    }
+
+    /// Delete the project or person awaiting confirmation and return to its list
+    pub fn confirm_delete(&mut self) -> Result<()> {
+        let Some(confirm) = self.delete_confirm.take() else {
+            return Ok(());
+        };
+        match self.screen {
+            Screen::ProjectDetails(idx) => {
+                self.projects.remove(idx);
+                // Sub-projects move up rather than pointing at a missing parent
+                for child in self.projects.iter_mut() {
+                    if child.parent.as_deref() == Some(confirm.name.as_str()) {
+                        child.parent = None;
+                    }
+                }
+                self.storage.save_projects(&self.projects)?;
+                self.storage.delete_project_notes(&confirm.name)?;
+                self.show_projects()?;
+                self.status_message = Some(format!("Deleted project '{}'", confirm.name));
+            }
+            Screen::PersonDetails(idx) => {
+                self.people.remove(idx);
+                self.storage.save_people(&self.people)?;
+                self.show_people()?;
+                self.status_message = Some(format!("Deleted person '{}'", confirm.name));
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
     /// Show person details for the selected person
     pub fn show_person_details(&mut self) -> Result<()> {
-        if let Some(person) = self.people.get(self.person_selected).cloned() {
+        if let Some(person) = self.filtered_people.get(self.person_selected).cloned() {
+            let Some(idx) = self.people.iter().position(|p| p.name == person.name) else {
+                return Ok(());
+            };
+
             // Load all logs
             self.logs = self.storage.load_all_logs()?;
 
@@ -663,9 +2502,18 @@ impl App {
                 .filter(|log| log.people.contains(&person.name))
                 .cloned()
                 .collect();
+            self.details_todos = self
+                .logs
+                .iter()
+                .flat_map(|log| &log.todos)
+                .filter(|t| t.people.contains(&person.name))
+                .cloned()
+                .collect();
+            self.details_tab = DetailsTab::Logs;
+            self.details_todo_selected = 0;
 
             self.person_details_log_selected = 0;
-            self.go_to_screen(Screen::PersonDetails(self.person_selected));
+            self.go_to_screen(Screen::PersonDetails(idx));
         }
         Ok(())
     }
@@ -679,6 +2527,10 @@ impl App {
                 self.person_edit_email = person.email.clone().unwrap_or_default();
                 self.person_edit_tel = person.tel.clone().unwrap_or_default();
                 self.person_edit_company = person.company.clone().unwrap_or_default();
+                self.edit_custom_fields = person.fields.clone();
+                self.custom_field_selected = 0;
+                self.custom_field_input = None;
+                self.edit_errors.clear();
                 self.person_edit_field = 0;
 
                 self.go_to_screen(Screen::PersonEdit(Some(idx)));
@@ -693,6 +2545,10 @@ impl App {
         self.person_edit_email = String::new();
         self.person_edit_tel = String::new();
         self.person_edit_company = String::new();
+        self.edit_custom_fields = BTreeMap::new();
+        self.custom_field_selected = 0;
+        self.custom_field_input = None;
+        self.edit_errors.clear();
         self.person_edit_field = 0;
 
         self.go_to_screen(Screen::PersonEdit(None));
@@ -701,24 +2557,14 @@ impl App {
     /// Save the edited person
     pub fn save_edited_person(&mut self) -> Result<()> {
         if let Screen::PersonEdit(idx_opt) = self.screen {
-            // Validate name is not empty
-            if self.person_edit_name.trim().is_empty() {
-                self.status_message = Some("Error: Name cannot be empty".to_string());
+            self.person_edit_name = self.person_edit_name.trim().to_string();
+            self.person_edit_email = self.person_edit_email.trim().to_string();
+            if !self.accept_edit_errors(self.person_edit_errors(idx_opt)) {
                 return Ok(());
             }
 
             match idx_opt {
                 Some(idx) => {
-                    // Editing existing person - check for duplicate name (excluding self)
-                    let name_exists = self.people.iter().enumerate().any(|(i, p)| {
-                        i != idx && p.name == self.person_edit_name
-                    });
-
-                    if name_exists {
-                        self.status_message = Some(format!("Error: Person '{}' already exists", self.person_edit_name));
-                        return Ok(());
-                    }
-
                     if let Some(person) = self.people.get_mut(idx) {
                         person.name = self.person_edit_name.clone();
                         person.full_name = if self.person_edit_full_name.is_empty() {
@@ -741,43 +2587,15 @@ impl App {
                         } else {
                             Some(self.person_edit_company.clone())
                         };
+                        person.fields = self.edit_custom_fields.clone();
 
                         self.storage.save_people(&self.people)?;
                         self.status_message = Some("Person saved".to_string());
                     }
                 }
                 None => {
-                    // Creating new person - check for duplicate name
-                    let name_exists = self.people.iter().any(|p| p.name == self.person_edit_name);
-
-                    if name_exists {
-                        self.status_message = Some(format!("Error: Person '{}' already exists", self.person_edit_name));
-                        return Ok(());
-                    }
-
-                    let new_person = Person {
-                        name: self.person_edit_name.clone(),
-                        full_name: if self.person_edit_full_name.is_empty() {
-                            None
-                        } else {
-                            Some(self.person_edit_full_name.clone())
-                        },
-                        email: if self.person_edit_email.is_empty() {
-                            None
-                        } else {
-                            Some(self.person_edit_email.clone())
-                        },
-                        tel: if self.person_edit_tel.is_empty() {
-                            None
-                        } else {
-                            Some(self.person_edit_tel.clone())
-                        },
-                        company: if self.person_edit_company.is_empty() {
-                            None
-                        } else {
-                            Some(self.person_edit_company.clone())
-                        },
-                    };
+                    // Creating new person
+                    let new_person = self.person_from_edit_fields();
 
                     self.people.push(new_person);
                     self.storage.save_people(&self.people)?;
@@ -787,10 +2605,58 @@ impl App {
 
             // Reload people from file to ensure consistency
             self.people = self.storage.load_people()?;
+            self.apply_people_filter();
 
             self.go_back();
         }
-        Ok(())
+        Ok(())
+    }
+
+    /// Todos shown in the current details tab, with their index in `details_todos`
+    pub fn details_tab_todos(&self) -> Vec<(usize, &Todo)> {
+        let completed = match self.details_tab {
+            DetailsTab::Logs => return Vec::new(),
+            DetailsTab::OpenTodos => false,
+            DetailsTab::CompletedTodos => true,
+        };
+        self.details_todos
+            .iter()
+            .enumerate()
+            .filter(|(_, t)| t.completed == completed)
+            .collect()
+    }
+
+    /// Switch to the next tab of the details screen
+    pub fn next_details_tab(&mut self) {
+        self.set_details_tab(self.details_tab.next());
+    }
+
+    pub fn set_details_tab(&mut self, tab: DetailsTab) {
+        self.details_tab = tab;
+        self.details_todo_selected = 0;
+    }
+
+    /// Toggle the selected todo of the details screen; it moves to the other todo tab
+    pub fn toggle_details_todo(&mut self) -> Result<()> {
+        let Some(&(idx, _)) = self.details_tab_todos().get(self.details_todo_selected) else {
+            return Ok(());
+        };
+        let todo = &mut self.details_todos[idx];
+        todo.toggle()?;
+        self.status_message = Some(if todo.completed { "Todo completed" } else { "Todo reopened" }.to_string());
+        self.project_details_open_todos = self.details_todos.iter().filter(|t| !t.completed).count();
+        self.details_todo_selected = self.details_todo_selected.min(self.details_tab_todos().len().saturating_sub(1));
+        Ok(())
+    }
+
+    /// View the log the selected todo of the details screen comes from, scrolled to it
+    pub fn view_details_todo_log(&mut self) {
+        if let Some((_, todo)) = self.details_tab_todos().get(self.details_todo_selected) {
+            let path = todo.log_path.clone();
+            let line = todo.line_number;
+            self.go_to_screen(Screen::ViewLog(path));
+            self.scroll_view_log_to(line);
+        }
     }
 
     /// View log from person details screen
@@ -802,6 +2668,46 @@ impl App {
         }
     }
 
+    /// Recompute how recently and often each tag is used, for ranking autocomplete
+    pub fn refresh_tag_frecency(&mut self) {
+        let logs = self.storage.load_all_logs().unwrap_or_default();
+        let now = chrono::Local::now();
+        self.tag_frecency.clear();
+
+        for log in &logs {
+            // A use counts fully today and half as much after two weeks
+            let age_days = (now - log.timestamp).num_days().max(0) as f64;
+            let weight = 1.0 / (1.0 + age_days / 14.0);
+
+            let tags = log
+                .projects
+                .iter()
+                .map(|p| format!("#{}", p))
+                .chain(log.people.iter().map(|p| format!("@{}", p)));
+            for tag in tags {
+                *self.tag_frecency.entry(tag).or_insert(0.0) += weight;
+            }
+        }
+    }
+
+    /// Rank a candidate tag against the typed query, or None if it doesn't match
+    fn rank_suggestion(&self, query: &str, tag: &str, name: &str, secondary: Option<&str>, long_text: Option<&str>) -> Option<f64> {
+        let name_score = fuzzy::score(query, name).map(|s| s as f64);
+        // Matches on the full name count for less than matches on the tag itself
+        let secondary_score = secondary.and_then(|t| fuzzy::score(query, t)).map(|s| s as f64 * 0.6);
+        let text_score = long_text
+            .filter(|t| fuzzy::word_prefix_match(query, t))
+            .map(|_| 1.0);
+
+        let match_score = [name_score, secondary_score, text_score]
+            .into_iter()
+            .flatten()
+            .fold(None, |best: Option<f64>, s| Some(best.map_or(s, |b| b.max(s))))?;
+
+        let frecency = self.tag_frecency.get(tag).copied().unwrap_or(0.0);
+        Some(match_score + 10.0 * frecency.ln_1p())
+    }
+
     /// Update autocomplete suggestions based on current input
     pub fn update_autocomplete(&mut self) {
         let content = &self.current_log.content;
@@ -814,41 +2720,82 @@ impl App {
         let before_cursor: String = content.chars().take(self.log_cursor_pos).collect();
 
         // Find the last word being typed
-        let last_word_start = before_cursor
-            .rfind(|c: char| c.is_whitespace())
-            .map(|i| i + 1)
-            .unwrap_or(0);
-
-        let current_word: String = before_cursor.chars().skip(last_word_start).collect();
+        let current_word = before_cursor.rsplit(char::is_whitespace).next().unwrap_or("").to_string();
 
+        let mut ranked: Vec<(f64, AutocompleteSuggestion)> = Vec::new();
         if current_word.starts_with('#') && current_word.len() > 1 {
             // Project autocomplete
-            let prefix = &current_word[1..].to_lowercase();
-            self.autocomplete_suggestions = self
-                .projects
-                .iter()
-                .filter(|p| p.name.to_lowercase().starts_with(prefix))
-                .map(|p| p.name.clone())
-                .collect();
+            let query = &current_word[1..];
+            for project in self.projects.iter().filter(|p| !p.archived) {
+                let tag = format!("#{}", project.name);
+                if let Some(score) = self.rank_suggestion(query, &tag, &project.name, None, project.description.as_deref()) {
+                    ranked.push((score, AutocompleteSuggestion {
+                        name: project.name.clone(),
+                        detail: project.description.clone().unwrap_or_default(),
+                        status: Some(project.status.clone()),
+                        inactive: self.config.is_terminal_state(&project.status),
+                        create: false,
+                    }));
+                }
+            }
             self.autocomplete_type = AutocompleteType::Project;
-            self.autocomplete_active = !self.autocomplete_suggestions.is_empty();
-            self.autocomplete_index = 0;
         } else if current_word.starts_with('@') && current_word.len() > 1 {
             // Person autocomplete
-            let prefix = &current_word[1..].to_lowercase();
-            self.autocomplete_suggestions = self
-                .people
-                .iter()
-                .filter(|p| p.name.to_lowercase().starts_with(prefix))
-                .map(|p| p.name.clone())
-                .collect();
+            let query = &current_word[1..];
+            for person in self.people.iter().filter(|p| !p.archived) {
+                let tag = format!("@{}", person.name);
+                if let Some(score) = self.rank_suggestion(query, &tag, &person.name, person.full_name.as_deref(), None) {
+                    ranked.push((score, AutocompleteSuggestion {
+                        name: person.name.clone(),
+                        detail: person.full_name.clone().unwrap_or_default(),
+                        status: None,
+                        inactive: false,
+                        create: false,
+                    }));
+                }
+            }
             self.autocomplete_type = AutocompleteType::Person;
-            self.autocomplete_active = !self.autocomplete_suggestions.is_empty();
-            self.autocomplete_index = 0;
         } else {
             self.autocomplete_active = false;
             self.autocomplete_type = AutocompleteType::None;
+            return;
+        }
+
+        // Active tags first, then best score, then alphabetical
+        ranked.sort_by(|(score_a, a), (score_b, b)| {
+            a.inactive
+                .cmp(&b.inactive)
+                .then(score_b.total_cmp(score_a))
+                .then_with(|| a.name.cmp(&b.name))
+        });
+        self.autocomplete_suggestions = ranked.into_iter().map(|(_, s)| s).collect();
+
+        // Offer to create the tag if it isn't registered yet
+        let name = current_word[1..].trim_matches(|c: char| !c.is_alphanumeric() && c != '-' && c != '_');
+        let exists = match self.autocomplete_type {
+            AutocompleteType::Project => self.projects.iter().any(|p| p.name == name),
+            _ => self.people.iter().any(|p| p.name == name),
+        };
+        if !name.is_empty() && !exists {
+            self.autocomplete_suggestions.push(AutocompleteSuggestion {
+                name: name.to_string(),
+                detail: String::new(),
+                status: None,
+                inactive: false,
+                create: true,
+            });
         }
+
+        self.autocomplete_active = !self.autocomplete_suggestions.is_empty();
+        self.autocomplete_index = 0;
+        self.autocomplete_picked = false;
+    }
+
+    /// Whether Enter should accept the highlighted suggestion. The create entry is only
+    /// taken on Enter once picked explicitly, so Enter after an unknown tag is a newline.
+    pub fn enter_accepts_autocomplete(&self) -> bool {
+        self.autocomplete_picked
+            || !self.autocomplete_suggestions.get(self.autocomplete_index).is_some_and(|s| s.create)
     }
 
     /// Accept the current autocomplete suggestion
@@ -858,15 +2805,18 @@ impl App {
         }
 
         let suggestion = self.autocomplete_suggestions[self.autocomplete_index].clone();
-        let content = &self.current_log.content;
-        let before_cursor: String = content.chars().take(self.log_cursor_pos).collect();
+        self.autocomplete_active = false;
+        self.autocomplete_suggestions.clear();
 
-        // Find the start of the current tag
-        let last_word_start = before_cursor
-            .rfind(|c: char| c.is_whitespace())
-            .map(|i| i + 1)
-            .unwrap_or(0);
+        if suggestion.create {
+            self.start_inline_create(self.autocomplete_type.clone(), &suggestion.name);
+        } else {
+            self.replace_current_tag(&suggestion.name);
+        }
+    }
 
+    /// Replace the tag being typed before the cursor with a complete tag
+    fn replace_current_tag(&mut self, name: &str) {
         // Get the prefix character (# or @)
         let prefix = match self.autocomplete_type {
             AutocompleteType::Project => "#",
@@ -874,16 +2824,120 @@ impl App {
             AutocompleteType::None => return,
         };
 
+        // Find the start of the current tag (in chars)
+        let content = &self.current_log.content;
+        let before_cursor: Vec<char> = content.chars().take(self.log_cursor_pos).collect();
+        let last_word_start = before_cursor
+            .iter()
+            .rposition(|c| c.is_whitespace())
+            .map(|i| i + 1)
+            .unwrap_or(0);
+
         // Build new content
-        let before: String = content.chars().take(last_word_start).collect();
+        let before: String = before_cursor[..last_word_start].iter().collect();
         let after: String = content.chars().skip(self.log_cursor_pos).collect();
-        let new_word = format!("{}{} ", prefix, suggestion);
+        let new_word = format!("{}{} ", prefix, name);
 
         self.current_log.content = format!("{}{}{}", before, new_word, after);
-        self.log_cursor_pos = last_word_start + new_word.len();
+        self.log_cursor_pos = last_word_start + new_word.chars().count();
+    }
 
-        self.autocomplete_active = false;
-        self.autocomplete_suggestions.clear();
+    /// Open the compact project/person form over the editor for a new tag
+    pub fn start_inline_create(&mut self, kind: AutocompleteType, name: &str) {
+        self.edit_custom_fields = BTreeMap::new();
+        match kind {
+            AutocompleteType::Project => {
+                self.project_edit_name = name.to_string();
+                self.project_edit_description = String::new();
+                self.project_edit_jira = String::new();
+                self.project_edit_status = self.config.initial_state();
+                self.project_edit_group = String::new();
+                self.project_edit_parent = String::new();
+                self.project_edit_target_date = String::new();
+                self.project_edit_milestones = Vec::new();
+            }
+            AutocompleteType::Person => {
+                self.person_edit_name = name.to_string();
+                self.person_edit_full_name = String::new();
+                self.person_edit_email = String::new();
+                self.person_edit_tel = String::new();
+                self.person_edit_company = String::new();
+            }
+            AutocompleteType::None => return,
+        }
+        self.inline_create_field = 0;
+        self.inline_create = Some(kind);
+    }
+
+    /// Close the inline create form without saving
+    pub fn cancel_inline_create(&mut self) {
+        self.inline_create = None;
+    }
+
+    /// The text field focused in the inline create form (None for the group selector)
+    pub fn inline_create_input(&mut self) -> Option<&mut String> {
+        match (&self.inline_create, self.inline_create_field) {
+            (Some(AutocompleteType::Project), 0) => Some(&mut self.project_edit_name),
+            (Some(AutocompleteType::Project), 1) => Some(&mut self.project_edit_description),
+            (Some(AutocompleteType::Person), 0) => Some(&mut self.person_edit_name),
+            (Some(AutocompleteType::Person), 1) => Some(&mut self.person_edit_full_name),
+            (Some(AutocompleteType::Person), 2) => Some(&mut self.person_edit_email),
+            _ => None,
+        }
+    }
+
+    /// Cycle the group of the project being created inline
+    pub fn cycle_inline_create_group(&mut self, forward: bool) {
+        let mut groups = self.config.allowed_groups();
+        groups.insert(0, String::new()); // No group
+        let current = groups.iter().position(|g| g == &self.project_edit_group).unwrap_or(0);
+        let next = if forward {
+            (current + 1) % groups.len()
+        } else {
+            (current + groups.len() - 1) % groups.len()
+        };
+        self.project_edit_group = groups[next].clone();
+    }
+
+    /// Save the project/person from the inline create form and complete the tag
+    pub fn save_inline_create(&mut self) -> Result<()> {
+        let kind = match self.inline_create.clone() {
+            Some(kind) => kind,
+            None => return Ok(()),
+        };
+
+        self.project_edit_name = self.project_edit_name.trim().to_string();
+        self.person_edit_name = self.person_edit_name.trim().to_string();
+        self.person_edit_email = self.person_edit_email.trim().to_string();
+        let (name, errors) = match kind {
+            AutocompleteType::Project => (self.project_edit_name.clone(), self.project_edit_errors(None)),
+            _ => (self.person_edit_name.clone(), self.person_edit_errors(None)),
+        };
+        if let Some(error) = errors.values().next() {
+            self.status_message = Some(format!("Error: {}", error));
+            return Ok(());
+        }
+
+        match kind {
+            AutocompleteType::Project => {
+                let project = self.project_from_edit_fields();
+                self.projects.push(project);
+                self.storage.save_projects(&self.projects)?;
+                self.status_message = Some(format!("Project '{}' created", name));
+            }
+            AutocompleteType::Person => {
+                let person = self.person_from_edit_fields();
+                self.people.push(person);
+                self.storage.save_people(&self.people)?;
+                self.status_message = Some(format!("Person '{}' created", name));
+            }
+            AutocompleteType::None => return Ok(()),
+        }
+
+        self.inline_create = None;
+        self.autocomplete_type = kind;
+        self.replace_current_tag(&name);
+        Ok(())
     }
 
     /// Insert a character at the current cursor position
@@ -895,6 +2949,51 @@ impl App {
         self.update_autocomplete();
     }
 
+    /// Insert a block of text at the cursor in one go (used for pastes)
+    pub fn insert_str(&mut self, text: &str) {
+        let before: String = self.current_log.content.chars().take(self.log_cursor_pos).collect();
+        let after: String = self.current_log.content.chars().skip(self.log_cursor_pos).collect();
+        self.current_log.content = format!("{}{}{}", before, text, after);
+        self.log_cursor_pos += text.chars().count();
+
+        // Don't pop up autocomplete for pasted text
+        self.autocomplete_active = false;
+        self.autocomplete_suggestions.clear();
+    }
+
+    /// Paste text into the editor, offering to attach it instead if it is a list of file paths
+    pub fn paste_into_log(&mut self, text: &str) {
+        let text = normalize_newlines(text);
+        let files = pasted_file_paths(&text);
+
+        // Notes don't take attachments
+        if files.is_empty() || self.notes_editing.is_some() {
+            self.insert_str(&text);
+        } else {
+            self.paste_pending_text = text;
+            self.paste_pending_files = files;
+        }
+    }
+
+    /// Attach the files from a pending paste
+    pub fn attach_pasted_files(&mut self) {
+        let files = std::mem::take(&mut self.paste_pending_files);
+        self.paste_pending_text.clear();
+
+        let count = files.len();
+        for file in files {
+            self.add_attachment(file);
+        }
+        self.status_message = Some(format!("Added {} attachment(s)", count));
+    }
+
+    /// Insert a pending paste as plain text
+    pub fn insert_pasted_text(&mut self) {
+        let text = std::mem::take(&mut self.paste_pending_text);
+        self.paste_pending_files.clear();
+        self.insert_str(&text);
+    }
+
     /// Delete character before cursor
     pub fn delete_char(&mut self) {
         if self.log_cursor_pos > 0 {
@@ -982,25 +3081,90 @@ impl App {
         self.log_cursor_pos = chars_before_next_line + target_col;
     }
 
-    /// Add an attachment
-    #[allow(dead_code)] // Planned feature for file attachments
-    pub fn add_attachment(&mut self, path: PathBuf) {
-        if path.exists() {
-            self.attachments.push(path);
-            self.status_message = Some("Attachment added".to_string());
-        } else {
+    /// Add an attachment, ignoring files that are already attached
+    ///
+    /// Returns the attachment's name, made unique so text references stay unambiguous.
+    pub fn add_attachment(&mut self, path: PathBuf) -> Option<String> {
+        if !path.exists() {
             self.status_message = Some("File not found".to_string());
+            return None;
+        }
+        if let Some(existing) = self.attachments.iter().find(|a| a.source == path) {
+            return Some(existing.name.clone());
+        }
+
+        let mut attachment = Attachment::new(path);
+        let base = std::path::Path::new(&attachment.name).to_path_buf();
+        let stem = base.file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default();
+        let ext = base.extension().map(|e| format!(".{}", e.to_string_lossy())).unwrap_or_default();
+        let mut n = 2;
+        while attachment.name == "log.txt" || self.attachments.iter().any(|a| a.name == attachment.name) {
+            attachment.name = format!("{}-{}{}", stem, n, ext);
+            n += 1;
         }
+
+        self.status_message = Some(format!("Added attachment: {}", attachment.name));
+        let name = attachment.name.clone();
+        self.attachments.push(attachment);
+        Some(name)
     }
 
-    /// Get all unique project names from loaded data
+    /// Get all unique project names from loaded data, leaving out archived ones not already in a filter
     pub fn all_project_names(&self) -> Vec<String> {
-        self.projects.iter().map(|p| p.name.clone()).collect()
+        self.projects
+            .iter()
+            .filter(|p| !p.archived || self.todo_filter.projects.contains(&p.name) || self.log_filter.projects.contains(&p.name))
+            .map(|p| p.name.clone())
+            .collect()
     }
 
-    /// Get all unique people names from loaded data
+    /// Get all unique people names from loaded data, leaving out archived ones not already in a filter
     pub fn all_people_names(&self) -> Vec<String> {
-        self.people.iter().map(|p| p.name.clone()).collect()
+        self.people
+            .iter()
+            .filter(|p| !p.archived || self.todo_filter.people.contains(&p.name) || self.log_filter.people.contains(&p.name))
+            .map(|p| p.name.clone())
+            .collect()
+    }
+
+    /// Forget the clickable regions from the previous render
+    pub fn clear_click_areas(&self) {
+        self.click_areas.borrow_mut().clear();
+    }
+
+    /// Record a clickable region; later registrations sit on top of earlier ones
+    pub fn register_click(&self, area: Rect, target: ClickTarget) {
+        self.click_areas.borrow_mut().push((area, target));
+    }
+
+    /// Record one clickable row per visible item of a single-line list
+    pub fn register_list_rows(&self, area: Rect, list: ClickList, offset: usize, len: usize) {
+        for row in 0..area.height {
+            let idx = offset + row as usize;
+            if idx >= len {
+                break;
+            }
+            self.register_click(Rect::new(area.x, area.y + row, area.width, 1), ClickTarget::Row(list, idx));
+        }
+    }
+
+    /// Find the topmost clickable region at a screen position
+    pub fn click_target_at(&self, x: u16, y: u16) -> Option<(Rect, ClickTarget)> {
+        self.click_areas
+            .borrow()
+            .iter()
+            .rev()
+            .find(|(area, _)| area.contains(Position::new(x, y)))
+            .cloned()
+    }
+
+    /// Move the editor cursor to a line and column, clamped to the content
+    pub fn set_cursor_line_col(&mut self, line: usize, col: usize) {
+        let lines: Vec<&str> = self.current_log.content.split('\n').collect();
+        let line = line.min(lines.len().saturating_sub(1));
+        let chars_before: usize = lines[..line].iter().map(|l| l.chars().count() + 1).sum();
+        self.log_cursor_pos = chars_before + col.min(lines[line].chars().count());
+        self.autocomplete_active = false;
     }
 
     /// Clear status message
@@ -1088,6 +3252,9 @@ impl App {
     pub fn open_file_browser(&mut self) {
         self.file_browser_open = true;
         self.file_browser_selected = 0;
+        self.file_browser_filter.clear();
+        self.file_browser_marked.clear();
+        self.file_browser_recent_open = false;
         self.load_directory_contents();
     }
 
@@ -1096,37 +3263,25 @@ impl App {
         self.file_browser_open = false;
     }
 
-    /// Load the contents of the current directory
-    pub fn load_directory_contents(&mut self) {
+    /// Read the entries of a directory, directories first, each group sorted by name
+    fn read_dir_entries(dir: &std::path::Path, show_hidden: bool) -> Vec<FileEntry> {
         use std::fs;
 
-        self.file_browser_entries.clear();
-
-        // Add parent directory entry if not at root
-        if let Some(parent) = self.file_browser_dir.parent() {
-            self.file_browser_entries.push(FileEntry {
-                name: "..".to_string(),
-                path: parent.to_path_buf(),
-                is_dir: true,
-                size: 0,
-            });
-        }
-
-        // Read directory contents
-        if let Ok(entries) = fs::read_dir(&self.file_browser_dir) {
-            let mut dirs: Vec<FileEntry> = Vec::new();
-            let mut files: Vec<FileEntry> = Vec::new();
+        let mut dirs: Vec<FileEntry> = Vec::new();
+        let mut files: Vec<FileEntry> = Vec::new();
 
+        if let Ok(entries) = fs::read_dir(dir) {
             for entry in entries.flatten() {
                 let path = entry.path();
                 let name = entry.file_name().to_string_lossy().to_string();
 
-                // Skip hidden files
-                if name.starts_with('.') {
+                // Skip hidden files unless toggled on
+                if name.starts_with('.') && !show_hidden {
                     continue;
                 }
 
-                let metadata = entry.metadata().ok();
+                // Follow symlinks so linked directories can be entered
+                let metadata = fs::metadata(&path).ok();
                 let is_dir = metadata.as_ref().map(|m| m.is_dir()).unwrap_or(false);
                 let size = metadata.as_ref().map(|m| m.len()).unwrap_or(0);
 
@@ -1143,32 +3298,251 @@ impl App {
                     files.push(entry);
                 }
             }
+        }
+
+        // Sort directories and files alphabetically
+        dirs.sort_by_key(|a| a.name.to_lowercase());
+        files.sort_by_key(|a| a.name.to_lowercase());
+
+        dirs.extend(files);
+        dirs
+    }
+
+    /// Load the contents of the current directory
+    pub fn load_directory_contents(&mut self) {
+        self.file_browser_all.clear();
+
+        // Add parent directory entry if not at root
+        if let Some(parent) = self.file_browser_dir.parent() {
+            self.file_browser_all.push(FileEntry {
+                name: "..".to_string(),
+                path: parent.to_path_buf(),
+                is_dir: true,
+                size: 0,
+            });
+        }
+
+        let entries = Self::read_dir_entries(&self.file_browser_dir, self.file_browser_show_hidden);
+        self.file_browser_all.extend(entries);
+        self.apply_file_browser_filter();
+    }
+
+    /// Whether the file browser input is a path rather than a filter
+    pub fn file_browser_path_mode(&self) -> bool {
+        self.file_browser_filter.starts_with('/') || self.file_browser_filter.starts_with('~')
+    }
+
+    /// The typed path with ~ expanded
+    fn file_browser_typed_path(&self) -> PathBuf {
+        match self.file_browser_filter.strip_prefix('~') {
+            Some(rest) => dirs::home_dir()
+                .map(|home| home.join(rest.trim_start_matches('/')))
+                .unwrap_or_else(|| PathBuf::from(&self.file_browser_filter)),
+            None => PathBuf::from(&self.file_browser_filter),
+        }
+    }
+
+    /// Split the typed path into the directory to list and the partial name being typed
+    fn file_browser_path_parts(&self) -> (PathBuf, String) {
+        let path = self.file_browser_typed_path();
+        if self.file_browser_filter.ends_with('/') || self.file_browser_filter == "~" {
+            (path, String::new())
+        } else {
+            let partial = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+            (path.parent().map(|p| p.to_path_buf()).unwrap_or(path), partial)
+        }
+    }
+
+    /// Recompute the shown entries from the filter or typed path
+    pub fn apply_file_browser_filter(&mut self) {
+        self.file_browser_selected = 0;
+
+        if self.file_browser_path_mode() {
+            // Path completion: entries of the typed directory starting with the partial name
+            let (dir, partial) = self.file_browser_path_parts();
+            let show_hidden = self.file_browser_show_hidden || partial.starts_with('.');
+            let partial = partial.to_lowercase();
+            self.file_browser_entries = Self::read_dir_entries(&dir, show_hidden)
+                .into_iter()
+                .filter(|e| e.name.to_lowercase().starts_with(&partial))
+                .collect();
+        } else if self.file_browser_filter.is_empty() {
+            self.file_browser_entries = self.file_browser_all.clone();
+        } else {
+            let mut ranked: Vec<(i64, FileEntry)> = self
+                .file_browser_all
+                .iter()
+                .filter(|e| e.name != "..")
+                .filter_map(|e| fuzzy::score(&self.file_browser_filter, &e.name).map(|score| (score, e.clone())))
+                .collect();
+            ranked.sort_by(|(a, _), (b, _)| b.cmp(a));
+            self.file_browser_entries = ranked.into_iter().map(|(_, e)| e).collect();
+        }
+    }
+
+    /// Type a character into the file browser filter
+    pub fn file_browser_input(&mut self, text: &str) {
+        self.file_browser_filter.push_str(text);
+        self.apply_file_browser_filter();
+    }
+
+    /// Delete from the filter, or go to the parent directory if it is empty
+    pub fn file_browser_backspace(&mut self) {
+        if self.file_browser_filter.pop().is_some() {
+            self.apply_file_browser_filter();
+        } else {
+            self.file_browser_go_up();
+        }
+    }
+
+    /// Complete the typed path as far as it is unambiguous
+    pub fn file_browser_complete(&mut self) {
+        if !self.file_browser_path_mode() {
+            // In filter mode Tab takes the selected name as a path to keep typing
+            if let Some(entry) = self.file_browser_entries.get(self.file_browser_selected) {
+                let mut path = entry.path.to_string_lossy().to_string();
+                if entry.is_dir {
+                    path.push('/');
+                }
+                self.file_browser_filter = path;
+                self.apply_file_browser_filter();
+            }
+            return;
+        }
+
+        let names: Vec<&FileEntry> = self.file_browser_entries.iter().collect();
+        let Some(first) = names.first() else {
+            return;
+        };
+        // Entries match the partial name case-insensitively, so the common prefix does too
+        let mut common: String = first.name.clone();
+        for entry in &names[1..] {
+            let len = common
+                .chars()
+                .zip(entry.name.chars())
+                .take_while(|(a, b)| a.to_lowercase().eq(b.to_lowercase()))
+                .count();
+            common = common.chars().take(len).collect();
+        }
+
+        // Replace the partial name with the common prefix, unless that adds nothing
+        if self.file_browser_filter == "~" {
+            self.file_browser_filter.push('/');
+        }
+        let (_, partial) = self.file_browser_path_parts();
+        if names.len() > 1 && common.chars().count() <= partial.chars().count() {
+            return;
+        }
+        let cut = self.file_browser_filter.rfind('/').map(|i| i + 1).unwrap_or(self.file_browser_filter.len());
+        self.file_browser_filter.truncate(cut);
+        self.file_browser_filter.push_str(&common);
+        if names.len() == 1 && first.is_dir {
+            self.file_browser_filter.push('/');
+        }
+        self.apply_file_browser_filter();
+    }
 
-            // Sort directories and files alphabetically
-            dirs.sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()));
-            files.sort_by(|a, b| a.name.to_lowercase().cmp(&b.name.to_lowercase()));
+    /// Toggle whether hidden files are listed
+    pub fn toggle_file_browser_hidden(&mut self) {
+        self.file_browser_show_hidden = !self.file_browser_show_hidden;
+        self.load_directory_contents();
+    }
 
-            // Add directories first, then files
-            self.file_browser_entries.extend(dirs);
-            self.file_browser_entries.extend(files);
+    /// Mark or unmark the selected file for attaching
+    pub fn toggle_file_browser_mark(&mut self) {
+        if let Some(entry) = self.file_browser_entries.get(self.file_browser_selected) {
+            if entry.is_dir {
+                return;
+            }
+            if let Some(pos) = self.file_browser_marked.iter().position(|p| p == &entry.path) {
+                self.file_browser_marked.remove(pos);
+            } else {
+                self.file_browser_marked.push(entry.path.clone());
+            }
+            if self.file_browser_selected + 1 < self.file_browser_entries.len() {
+                self.file_browser_selected += 1;
+            }
         }
     }
 
+    /// Go to a directory, clearing the filter
+    fn file_browser_go_to(&mut self, dir: PathBuf) {
+        self.file_browser_dir = dir;
+        self.file_browser_filter.clear();
+        self.file_browser_selected = 0;
+        self.load_directory_contents();
+    }
+
     /// Navigate into a directory or select a file
     pub fn file_browser_enter(&mut self) {
+        // A complete typed path is used as is
+        if self.file_browser_path_mode() {
+            let path = self.file_browser_typed_path();
+            if path.is_dir() && self.file_browser_filter.ends_with('/') {
+                self.file_browser_go_to(path);
+                return;
+            } else if path.is_file() {
+                self.attach_from_file_browser(Some(path));
+                return;
+            }
+        }
+
         if let Some(entry) = self.file_browser_entries.get(self.file_browser_selected).cloned() {
             if entry.is_dir {
                 // Navigate into directory
-                self.file_browser_dir = entry.path;
-                self.file_browser_selected = 0;
-                self.load_directory_contents();
+                self.file_browser_go_to(entry.path);
+            } else if self.file_browser_marked.is_empty() {
+                self.attach_from_file_browser(Some(entry.path));
             } else {
-                // Select file as attachment
-                if !self.attachments.contains(&entry.path) {
-                    self.attachments.push(entry.path);
-                    self.status_message = Some(format!("Added attachment: {}", entry.name));
-                }
-                self.close_file_browser();
+                // With files marked, Enter attaches exactly those
+                self.attach_from_file_browser(None);
+            }
+        } else if !self.file_browser_marked.is_empty() {
+            self.attach_from_file_browser(None);
+        }
+    }
+
+    /// Attach the marked files plus an optional selected one, referencing them at the cursor
+    fn attach_from_file_browser(&mut self, selected: Option<PathBuf>) {
+        let mut files = std::mem::take(&mut self.file_browser_marked);
+        if let Some(path) = selected {
+            if !files.contains(&path) {
+                files.push(path);
+            }
+        }
+        self.close_file_browser();
+
+        let mut refs = Vec::new();
+        for file in &files {
+            if let Some(name) = self.add_attachment(file.clone()) {
+                refs.push(AttachmentRef::syntax_for(&name));
+            }
+        }
+        if !refs.is_empty() {
+            self.insert_str(&refs.join(" "));
+        }
+        if files.len() > 1 {
+            self.status_message = Some(format!("Added {} attachments", refs.len()));
+        }
+
+        // Remember where the files came from for next time
+        if let Some(dir) = files.last().and_then(|f| f.parent()) {
+            self.ui_state.remember_dir(dir.to_path_buf());
+            self.file_browser_dir = dir.to_path_buf();
+            if let Err(e) = self.storage.save_ui_state(&self.ui_state) {
+                self.status_message = Some(format!("Error saving state: {}", e));
+            }
+        }
+    }
+
+    /// Jump to the directory selected in the recent directories list
+    pub fn file_browser_open_recent(&mut self) {
+        if let Some(dir) = self.ui_state.recent_dirs.get(self.file_browser_recent_selected).cloned() {
+            self.file_browser_recent_open = false;
+            if dir.is_dir() {
+                self.file_browser_go_to(dir);
+            } else {
+                self.status_message = Some(format!("Directory no longer exists: {}", dir.display()));
             }
         }
     }
@@ -1176,20 +3550,165 @@ impl App {
     /// Navigate to parent directory
     pub fn file_browser_go_up(&mut self) {
         if let Some(parent) = self.file_browser_dir.parent() {
-            self.file_browser_dir = parent.to_path_buf();
-            self.file_browser_selected = 0;
-            self.load_directory_contents();
+            self.file_browser_go_to(parent.to_path_buf());
         }
     }
 
     /// Remove an attachment by index
-    #[allow(dead_code)] // Planned feature for file attachments
     pub fn remove_attachment(&mut self, index: usize) {
         if index < self.attachments.len() {
             let removed = self.attachments.remove(index);
-            if let Some(name) = removed.file_name() {
-                self.status_message = Some(format!("Removed: {}", name.to_string_lossy()));
+            self.status_message = Some(format!("Removed: {}", removed.name));
+            self.attachment_selected = self.attachment_selected.min(self.attachments.len().saturating_sub(1));
+            if self.attachments.is_empty() {
+                self.attachments_focused = false;
             }
         }
     }
+
+    /// Start renaming the selected attachment
+    pub fn start_attachment_rename(&mut self) {
+        if let Some(attachment) = self.attachments.get(self.attachment_selected) {
+            self.attachment_rename = Some(attachment.name.clone());
+        }
+    }
+
+    /// Apply the typed name to the selected attachment
+    pub fn apply_attachment_rename(&mut self) {
+        let name = match self.attachment_rename.take() {
+            Some(name) => name.trim().to_string(),
+            None => return,
+        };
+        if name.is_empty() || name.contains(['/', '\\']) || name == "log.txt" {
+            self.status_message = Some("Error: Invalid file name".to_string());
+            return;
+        }
+        let taken = self
+            .attachments
+            .iter()
+            .enumerate()
+            .any(|(i, a)| i != self.attachment_selected && a.name == name);
+        if taken {
+            self.status_message = Some(format!("Error: Another attachment is named '{}'", name));
+            return;
+        }
+        if let Some(attachment) = self.attachments.get_mut(self.attachment_selected) {
+            let old_name = std::mem::replace(&mut attachment.name, name.clone());
+
+            // Keep references in the text pointing at the file, and the cursor after
+            // the same text: only references before it move it
+            let renames = HashMap::from([(old_name, name)]);
+            let content = &self.current_log.content;
+            let cursor = content.char_indices().nth(self.log_cursor_pos).map(|(i, _)| i).unwrap_or(content.len());
+            let before = AttachmentRef::rename_all(&content[..cursor], &renames);
+            let updated = AttachmentRef::rename_all(content, &renames);
+            self.log_cursor_pos = before.chars().count().min(updated.chars().count());
+            self.current_log.content = updated;
+        }
+    }
+
+    /// Open the selected attachment of the viewed log with the system opener
+    pub fn open_view_log_attachment(&mut self) {
+        if let Some(name) = self
+            .view_log_attachment_selected
+            .checked_sub(self.view_log_attachments.len())
+            .and_then(|i| self.view_log_missing_refs.get(i))
+        {
+            self.status_message = Some(format!("Error: '{}' is not in this entry's directory", name));
+        } else if let Some(path) = self.view_log_attachments.get(self.view_log_attachment_selected).cloned() {
+            self.status_message = Some(match open_path(&path) {
+                Ok(()) => format!("Opened {}", path.file_name().unwrap_or_default().to_string_lossy()),
+                Err(e) => format!("Error: Could not open file: {}", e),
+            });
+        }
+    }
+}
+
+/// Open a file with the platform's default application
+pub fn open_path(path: &std::path::Path) -> std::io::Result<()> {
+    use std::process::{Command, Stdio};
+
+    let mut command = if cfg!(target_os = "macos") {
+        Command::new("open")
+    } else if cfg!(target_os = "windows") {
+        let mut c = Command::new("cmd");
+        c.args(["/C", "start", ""]);
+        c
+    } else {
+        Command::new("xdg-open")
+    };
+    command
+        .arg(path)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .map(|_| ())
+}
+
+/// Convert CRLF and lone CR line endings to LF
+pub fn normalize_newlines(text: &str) -> String {
+    text.replace("\r\n", "\n").replace('\r', "\n")
+}
+
+/// Interpret pasted text as a list of existing files, one per line
+///
+/// Returns an empty list unless every non-empty line is a file. Handles quoting,
+/// `file://` URLs, backslash-escaped spaces and `~/` as produced by terminals and
+/// file managers when files are dropped or copied.
+fn pasted_file_paths(text: &str) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    for line in text.lines().map(|l| l.trim()).filter(|l| !l.is_empty()) {
+        let line = line.trim_matches(|c| c == '\'' || c == '"');
+        let unescaped = match line.strip_prefix("file://") {
+            Some(url_path) => url_path.replace("%20", " "),
+            None => line.replace("\\ ", " "),
+        };
+
+        let path = match unescaped.strip_prefix("~/") {
+            Some(rest) => match dirs::home_dir() {
+                Some(home) => home.join(rest),
+                None => return Vec::new(),
+            },
+            None => PathBuf::from(&unescaped),
+        };
+
+        if !path.is_absolute() || !path.is_file() {
+            return Vec::new();
+        }
+        files.push(path);
+    }
+    files
+}
+
+/// The project edit form field showing a validation error
+fn project_edit_field_index(field: Field) -> Option<usize> {
+    match field {
+        Field::Name => Some(0),
+        Field::Jira => Some(2),
+        Field::Status => Some(3),
+        Field::Group => Some(4),
+        Field::Parent => Some(5),
+        Field::TargetDate => Some(6),
+        Field::Email => None,
+    }
+}
+
+/// The person edit form field showing a validation error
+fn person_edit_field_index(field: Field) -> Option<usize> {
+    match field {
+        Field::Name => Some(0),
+        Field::Email => Some(2),
+        _ => None,
+    }
+}
+
+/// One row per value each declared field can be filtered by, in declaration order
+fn field_facets<'a>(
+    defs: &[CustomField],
+    entries: impl Iterator<Item = &'a BTreeMap<String, String>> + Clone,
+) -> Vec<(CustomField, String)> {
+    defs.iter()
+        .flat_map(|field| field.facet_values(entries.clone()).into_iter().map(move |value| (field.clone(), value)))
+        .collect()
 }
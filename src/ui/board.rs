@@ -0,0 +1,166 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, ListState, Paragraph},
+    Frame,
+};
+
+use super::app::{App, BoardGrouping, ClickList, TodoFilterPanel};
+use super::todo_list::{marker_style, render_filter_popup, render_filters_summary};
+use crate::models::TodoState;
+
+const MIN_COLUMN_WIDTH: u16 = 28;
+
+pub fn render(frame: &mut Frame, app: &mut App, area: Rect) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints([
+            Constraint::Length(3),  // Title
+            Constraint::Length(5),  // Filters summary
+            Constraint::Min(10),    // Columns
+            Constraint::Length(3),  // Help bar
+        ])
+        .split(area);
+
+    // Title
+    let grouping = match app.board_grouping {
+        BoardGrouping::State => "by state",
+        BoardGrouping::Project => "by project",
+    };
+    let title = Paragraph::new(format!("Todo board {} ({} items)", grouping, app.filtered_todos.len()))
+        .style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD))
+        .block(Block::default().borders(Borders::ALL));
+    frame.render_widget(title, chunks[0]);
+
+    render_filters_summary(frame, app, chunks[1]);
+    render_columns(frame, app, chunks[2]);
+
+    // Help bar
+    let help_text = if app.todo_filter_panel == TodoFilterPanel::None {
+        let mut help = vec![
+            Span::styled("←→", Style::default().fg(Color::Yellow)),
+            Span::raw(" Column  "),
+            Span::styled("↑↓", Style::default().fg(Color::Yellow)),
+            Span::raw(" Card  "),
+        ];
+        if app.board_grouping == BoardGrouping::State {
+            help.push(Span::styled("Shift+←→/</>", Style::default().fg(Color::Yellow)));
+            help.push(Span::raw(" Move card  "));
+        }
+        help.extend([
+            Span::styled("x", Style::default().fg(Color::Yellow)),
+            Span::raw(" Toggle done  "),
+            Span::styled("Enter", Style::default().fg(Color::Yellow)),
+            Span::raw(" View log  "),
+            Span::styled("g", Style::default().fg(Color::Yellow)),
+            Span::raw(" Group  "),
+            Span::styled("c/p/h", Style::default().fg(Color::Yellow)),
+            Span::raw(" Filters  "),
+            Span::styled("ESC", Style::default().fg(Color::Yellow)),
+            Span::raw(" List"),
+        ]);
+        help
+    } else {
+        vec![
+            Span::styled("ESC", Style::default().fg(Color::Yellow)),
+            Span::raw(" Close filter"),
+        ]
+    };
+    let help = Paragraph::new(Line::from(help_text))
+        .block(Block::default().borders(Borders::ALL));
+    frame.render_widget(help, chunks[3]);
+
+    render_filter_popup(frame, app, area);
+}
+
+/// As many columns as fit, scrolled so the focused one is visible
+fn render_columns(frame: &mut Frame, app: &App, area: Rect) {
+    let columns = app.board_columns();
+    if columns.is_empty() {
+        let empty = Paragraph::new("No todos match the filter")
+            .style(Style::default().fg(Color::DarkGray))
+            .block(Block::default().borders(Borders::ALL));
+        frame.render_widget(empty, area);
+        return;
+    }
+
+    let visible = ((area.width / MIN_COLUMN_WIDTH).max(1) as usize).min(columns.len());
+    let first = (app.board_column + 1).saturating_sub(visible);
+    let areas = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints(vec![Constraint::Ratio(1, visible as u32); visible])
+        .split(area);
+
+    for (offset, column_area) in areas.iter().enumerate() {
+        let column = first + offset;
+        let (title, todos) = &columns[column];
+        let focused = column == app.board_column;
+
+        let items: Vec<ListItem> = todos
+            .iter()
+            .enumerate()
+            .map(|(row, &idx)| {
+                let todo = &app.filtered_todos[idx];
+                let style = if focused && row == app.board_selected {
+                    Style::default().bg(Color::DarkGray).fg(Color::White)
+                } else if todo.completed {
+                    Style::default().fg(Color::Gray).add_modifier(Modifier::CROSSED_OUT)
+                } else {
+                    Style::default()
+                };
+
+                // The column already says the state when grouped by state, and the project when by project
+                let mut spans = Vec::new();
+                if app.board_grouping == BoardGrouping::Project {
+                    spans.push(Span::styled(todo.state.marker(), marker_style(todo.state)));
+                    spans.push(Span::raw(" "));
+                }
+                spans.push(Span::styled(todo.text.clone(), style));
+                if app.board_grouping == BoardGrouping::State {
+                    for project in &todo.projects {
+                        spans.push(Span::styled(format!(" #{}", project), Style::default().fg(Color::Green)));
+                    }
+                }
+                for person in &todo.people {
+                    spans.push(Span::styled(format!(" @{}", person), Style::default().fg(Color::Blue)));
+                }
+                ListItem::new(Line::from(spans))
+            })
+            .collect();
+
+        let hidden = app.board_grouping == BoardGrouping::State
+            && TodoState::ALL[column] == TodoState::Done
+            && !app.todo_filter.show_completed;
+        let mut title = if hidden {
+            format!("{} (hidden, c to show)", title)
+        } else {
+            format!("{} ({})", title, todos.len())
+        };
+        if offset == 0 && first > 0 {
+            title.insert_str(0, "← ");
+        }
+        if offset == visible - 1 && column + 1 < columns.len() {
+            title.push_str(" →");
+        }
+        let border_style = if focused {
+            Style::default().fg(Color::Yellow)
+        } else {
+            Style::default()
+        };
+
+        let mut state = ListState::default();
+        if focused && !todos.is_empty() {
+            state.select(Some(app.board_selected));
+        }
+        frame.render_stateful_widget(
+            List::new(items).block(Block::default().borders(Borders::ALL).title(title).border_style(border_style)),
+            *column_area,
+            &mut state,
+        );
+
+        let inner = column_area.inner(ratatui::layout::Margin { vertical: 1, horizontal: 1 });
+        app.register_list_rows(inner, ClickList::BoardCards(column), state.offset(), todos.len());
+    }
+}
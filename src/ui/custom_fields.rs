@@ -0,0 +1,215 @@
+use std::collections::BTreeMap;
+
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph, Wrap},
+    Frame,
+};
+
+use super::app::{App, ClickList, ClickTarget};
+use crate::models::{CustomField, CustomFieldKind, FieldFacets};
+
+/// Rows needed to show the custom field list of an edit screen, borders included
+pub fn editor_height(app: &App) -> u16 {
+    let defs = app.custom_field_defs();
+    if defs.is_empty() {
+        0
+    } else {
+        defs.len() as u16 + 2
+    }
+}
+
+/// The custom fields of the entity being edited, one row per field declared in config.yml
+pub fn render_editor(frame: &mut Frame, app: &App, area: Rect, focused: bool, number: usize) {
+    let defs = app.custom_field_defs();
+    if defs.is_empty() || area.height == 0 {
+        return;
+    }
+
+    let label_width = defs.iter().map(|f| f.label().chars().count()).max().unwrap_or(0);
+    let items: Vec<ListItem> = defs
+        .iter()
+        .enumerate()
+        .map(|(i, field)| {
+            let value = app.edit_custom_fields.get(&field.name);
+            let style = if focused && i == app.custom_field_selected {
+                Style::default().bg(Color::DarkGray).fg(Color::White)
+            } else {
+                Style::default()
+            };
+            let value_span = match value {
+                Some(value) => Span::styled(field.display(value), style.fg(Color::Cyan)),
+                None => Span::styled(format!("({})", field.hint()), style.fg(Color::DarkGray)),
+            };
+            ListItem::new(Line::from(vec![
+                Span::styled(format!("{:width$}  ", field.label(), width = label_width), style.add_modifier(Modifier::BOLD)),
+                value_span,
+            ]))
+        })
+        .collect();
+
+    let title = if focused {
+        format!("[{}] Fields (Enter edit, ←→ choose, d clear)", number)
+    } else {
+        format!("[{}] Fields", number)
+    };
+    let border_style = if focused {
+        Style::default().fg(Color::Yellow)
+    } else {
+        Style::default()
+    };
+
+    let mut state = ListState::default();
+    if focused {
+        state.select(Some(app.custom_field_selected));
+    }
+    let list = List::new(items).block(Block::default().borders(Borders::ALL).title(title).border_style(border_style));
+    frame.render_stateful_widget(list, area, &mut state);
+
+    let inner = area.inner(ratatui::layout::Margin { vertical: 1, horizontal: 1 });
+    app.register_list_rows(inner, ClickList::CustomFields, state.offset(), defs.len());
+}
+
+/// Popup for typing a custom field value
+pub fn render_input(frame: &mut Frame, app: &App, area: Rect) {
+    let Some(input) = app.custom_field_input.as_deref() else {
+        return;
+    };
+    let Some(field) = app.custom_field_defs().get(app.custom_field_selected).cloned() else {
+        return;
+    };
+
+    let popup_width = 60u16.min(area.width);
+    let popup_area = Rect::new(
+        area.x + (area.width.saturating_sub(popup_width)) / 2,
+        area.y + (area.height.saturating_sub(3)) / 2,
+        popup_width,
+        3,
+    );
+
+    frame.render_widget(Clear, popup_area);
+    let title = format!("{}: {} (Enter save, ESC cancel)", field.label(), field.hint());
+    let paragraph = Paragraph::new(input).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(title)
+            .border_style(Style::default().fg(Color::Yellow)),
+    );
+    frame.render_widget(paragraph, popup_area);
+    app.register_click(area, ClickTarget::Blocker);
+
+    frame.set_cursor_position((popup_area.x + 1 + input.chars().count() as u16, popup_area.y + 1));
+}
+
+/// Custom field values on a details screen, skipping fields that aren't set
+pub fn render_values(frame: &mut Frame, defs: &[CustomField], values: &BTreeMap<String, String>, area: Rect) {
+    let mut spans = Vec::new();
+    for field in defs {
+        let Some(value) = values.get(&field.name) else {
+            continue;
+        };
+        if !spans.is_empty() {
+            spans.push(Span::styled("  │  ", Style::default().fg(Color::DarkGray)));
+        }
+        let value_color = match field.kind {
+            CustomFieldKind::Url => Color::Blue,
+            CustomFieldKind::Person | CustomFieldKind::Project => Color::Cyan,
+            _ => Color::White,
+        };
+        spans.push(Span::styled(format!("{}: ", field.label()), Style::default().add_modifier(Modifier::BOLD)));
+        spans.push(Span::styled(field.display(value), Style::default().fg(value_color)));
+    }
+    if spans.is_empty() {
+        spans.push(Span::styled("(no fields set)", Style::default().fg(Color::DarkGray)));
+    }
+
+    let paragraph = Paragraph::new(Line::from(spans))
+        .block(Block::default().borders(Borders::ALL).title("Fields"))
+        .wrap(Wrap { trim: true });
+    frame.render_widget(paragraph, area);
+}
+
+/// The chosen field values as `Label: a, b`, or None when nothing is chosen
+pub fn facet_summary(defs: &[CustomField], chosen: &FieldFacets) -> Option<String> {
+    let parts: Vec<String> = chosen
+        .iter()
+        .map(|(name, values)| match defs.iter().find(|f| &f.name == name) {
+            Some(field) => format!(
+                "{}: {}",
+                field.label(),
+                values.iter().map(|v| field.display(v)).collect::<Vec<_>>().join(", ")
+            ),
+            None => format!("{}: {}", name, values.join(", ")),
+        })
+        .collect();
+    (!parts.is_empty()).then(|| parts.join("; "))
+}
+
+/// Popup choosing the enum and tag-reference field values a list is filtered by
+pub fn render_facet_popup(
+    frame: &mut Frame,
+    app: &App,
+    area: Rect,
+    facets: &[(CustomField, String)],
+    chosen: &FieldFacets,
+    selected: usize,
+    click_list: ClickList,
+) {
+    let popup_width = 50u16.min(area.width);
+    let popup_height = 15u16.min(area.height.saturating_sub(4));
+    let popup_area = Rect::new(
+        area.x + (area.width.saturating_sub(popup_width)) / 2,
+        area.y + (area.height.saturating_sub(popup_height)) / 2,
+        popup_width,
+        popup_height,
+    );
+
+    frame.render_widget(Clear, popup_area);
+
+    let items: Vec<ListItem> = if facets.is_empty() {
+        vec![ListItem::new(Span::styled(
+            "(no enum, person or project fields to filter by)",
+            Style::default().fg(Color::DarkGray),
+        ))]
+    } else {
+        facets
+            .iter()
+            .enumerate()
+            .map(|(i, (field, value))| {
+                let is_chosen = chosen.get(&field.name).is_some_and(|values| values.contains(value));
+                let checkbox = if is_chosen { "[x]" } else { "[ ]" };
+                let style = if i == selected {
+                    Style::default().bg(Color::DarkGray).fg(Color::White)
+                } else if is_chosen {
+                    Style::default().fg(Color::Green)
+                } else {
+                    Style::default()
+                };
+                ListItem::new(Line::from(vec![
+                    Span::styled(checkbox, if is_chosen { Style::default().fg(Color::Green) } else { Style::default() }),
+                    Span::raw(" "),
+                    Span::styled(format!("{}: {}", field.label(), field.display(value)), style),
+                ]))
+            })
+            .collect()
+    };
+
+    let mut state = ListState::default();
+    state.select(Some(selected));
+
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title("Fields (↑↓ navigate, x toggle, ESC close)")
+                .border_style(Style::default().fg(Color::Yellow)),
+        )
+        .highlight_style(Style::default().add_modifier(Modifier::BOLD));
+
+    frame.render_stateful_widget(list, popup_area, &mut state);
+    app.register_click(area, ClickTarget::Blocker);
+    let inner = popup_area.inner(ratatui::layout::Margin { vertical: 1, horizontal: 1 });
+    app.register_list_rows(inner, click_list, state.offset(), facets.len());
+}
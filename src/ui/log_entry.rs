@@ -6,22 +6,27 @@ use ratatui::{
     Frame,
 };
 
-use super::app::{App, AutocompleteType};
+use super::app::{App, AutocompleteType, ClickList, ClickTarget};
+use crate::models::{AttachmentRef, TemplateFieldKind};
 
 pub fn render(frame: &mut Frame, app: &App, area: Rect) {
+    // Project notes are edited here too, without a timestamp or attachments
+    let notes = app.notes_editing.as_deref();
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .margin(1)
         .constraints([
             Constraint::Length(3),  // Header with timestamp
             Constraint::Min(10),    // Editor area
-            Constraint::Length(5),  // Attachments
+            Constraint::Length(if notes.is_some() { 0 } else { 5 }), // Attachments
             Constraint::Length(3),  // Help/status bar
         ])
         .split(area);
 
     // Header with timestamp
-    let header_content = if app.timestamp_editing {
+    let header_content = if let Some(project) = notes {
+        format!("Notes - #{}", project)
+    } else if app.timestamp_editing {
         format!("Edit Timestamp: {}", app.timestamp_edit_input)
     } else {
         let timestamp = app.current_log.timestamp.format("%Y-%m-%d %H:%M:%S").to_string();
@@ -41,10 +46,59 @@ pub fn render(frame: &mut Frame, app: &App, area: Rect) {
     render_editor(frame, app, chunks[1]);
 
     // Attachments
-    render_attachments(frame, app, chunks[2]);
+    if notes.is_none() {
+        render_attachments(frame, app, chunks[2]);
+    }
 
     // Help bar
-    let help_text = if app.file_browser_open {
+    let help_text = if app.template_picker_open {
+        vec![
+            Span::styled("↑↓", Style::default().fg(Color::Yellow)),
+            Span::raw(" Navigate  "),
+            Span::styled("Enter", Style::default().fg(Color::Yellow)),
+            Span::raw(" Use template  "),
+            Span::styled("ESC", Style::default().fg(Color::Yellow)),
+            Span::raw(" Cancel"),
+        ]
+    } else if app.template_pending.is_some() {
+        vec![
+            Span::styled("↑↓", Style::default().fg(Color::Yellow)),
+            Span::raw(" Suggestions  "),
+            Span::styled("Enter", Style::default().fg(Color::Yellow)),
+            Span::raw(" Next field  "),
+            Span::styled("ESC", Style::default().fg(Color::Yellow)),
+            Span::raw(" Back to templates"),
+        ]
+    } else if app.attachment_rename.is_some() {
+        vec![
+            Span::styled("Enter", Style::default().fg(Color::Yellow)),
+            Span::raw(" Rename  "),
+            Span::styled("ESC", Style::default().fg(Color::Yellow)),
+            Span::raw(" Cancel"),
+        ]
+    } else if app.attachments_focused {
+        vec![
+            Span::styled("↑↓", Style::default().fg(Color::Yellow)),
+            Span::raw(" Select  "),
+            Span::styled("r", Style::default().fg(Color::Yellow)),
+            Span::raw(" Rename  "),
+            Span::styled("d", Style::default().fg(Color::Yellow)),
+            Span::raw(" Remove  "),
+            Span::styled("ESC", Style::default().fg(Color::Yellow)),
+            Span::raw(" Back to text"),
+        ]
+    } else if app.inline_create.is_some() {
+        vec![
+            Span::styled("Tab", Style::default().fg(Color::Yellow)),
+            Span::raw(" Next field  "),
+            Span::styled("←→", Style::default().fg(Color::Yellow)),
+            Span::raw(" Change group  "),
+            Span::styled("Enter", Style::default().fg(Color::Yellow)),
+            Span::raw(" Create  "),
+            Span::styled("ESC", Style::default().fg(Color::Yellow)),
+            Span::raw(" Cancel"),
+        ]
+    } else if app.file_browser_open {
         vec![
             Span::styled("↑↓", Style::default().fg(Color::Yellow)),
             Span::raw(" Navigate  "),
@@ -66,12 +120,25 @@ pub fn render(frame: &mut Frame, app: &App, area: Rect) {
             Span::styled("Format:", Style::default().fg(Color::Cyan)),
             Span::raw(" YYYY-MM-DD HH:MM:SS"),
         ]
+    } else if notes.is_some() {
+        vec![
+            Span::styled("Ctrl+S", Style::default().fg(Color::Yellow)),
+            Span::raw(" Save notes  "),
+            Span::styled("ESC", Style::default().fg(Color::Yellow)),
+            Span::raw(" Cancel  "),
+            Span::styled("#", Style::default().fg(Color::Green)),
+            Span::raw("project  "),
+            Span::styled("@", Style::default().fg(Color::Blue)),
+            Span::raw("person"),
+        ]
     } else {
         vec![
             Span::styled("Ctrl+S", Style::default().fg(Color::Yellow)),
             Span::raw(" Save  "),
             Span::styled("Ctrl+A", Style::default().fg(Color::Yellow)),
             Span::raw(" Attach  "),
+            Span::styled("Ctrl+F", Style::default().fg(Color::Yellow)),
+            Span::raw(" Files  "),
             Span::styled("Ctrl+T", Style::default().fg(Color::Yellow)),
             Span::raw(" Edit time  "),
             Span::styled("ESC", Style::default().fg(Color::Yellow)),
@@ -81,7 +148,9 @@ pub fn render(frame: &mut Frame, app: &App, area: Rect) {
             Span::styled("@", Style::default().fg(Color::Blue)),
             Span::raw("person  "),
             Span::styled("[]", Style::default().fg(Color::Magenta)),
-            Span::raw(" todo"),
+            Span::raw(" todo "),
+            Span::styled("[/] [w]", Style::default().fg(Color::Cyan)),
+            Span::raw(" doing/waiting"),
         ]
     };
     let help = Paragraph::new(Line::from(help_text))
@@ -98,6 +167,28 @@ pub fn render(frame: &mut Frame, app: &App, area: Rect) {
         render_file_browser(frame, app, area);
     }
 
+    // Render the unsaved changes prompt if open
+    if app.exit_prompt_open {
+        render_exit_prompt(frame, app, area);
+    }
+
+    // Render the pasted files prompt if open
+    if !app.paste_pending_files.is_empty() {
+        render_paste_prompt(frame, app, area);
+    }
+
+    // Render the inline project/person form if open
+    if app.inline_create.is_some() {
+        render_inline_create(frame, app, area);
+    }
+
+    // Render template picker or field prompt if open
+    if app.template_picker_open {
+        render_template_picker(frame, app, area);
+    } else if app.template_pending.is_some() {
+        render_template_field_prompt(frame, app, area);
+    }
+
     // Set cursor position for timestamp editing
     if app.timestamp_editing {
         let inner = chunks[0].inner(ratatui::layout::Margin { vertical: 1, horizontal: 1 });
@@ -110,6 +201,12 @@ pub fn render(frame: &mut Frame, app: &App, area: Rect) {
 }
 
 fn render_attachments(frame: &mut Frame, app: &App, area: Rect) {
+    let border_style = if app.attachments_focused {
+        Style::default().fg(Color::Yellow)
+    } else {
+        Style::default()
+    };
+
     if app.attachments.is_empty() {
         let attachments = Paragraph::new("No attachments (Ctrl+A to add)")
             .style(Style::default().fg(Color::DarkGray))
@@ -119,32 +216,60 @@ fn render_attachments(frame: &mut Frame, app: &App, area: Rect) {
         let items: Vec<ListItem> = app
             .attachments
             .iter()
-            .map(|path| {
-                let name = path
-                    .file_name()
-                    .map(|n| n.to_string_lossy().to_string())
-                    .unwrap_or_else(|| path.to_string_lossy().to_string());
-
+            .enumerate()
+            .map(|(i, attachment)| {
                 // Get file size if available
-                let size_str = std::fs::metadata(path)
+                let size_str = std::fs::metadata(&attachment.source)
                     .map(|m| format_size(m.len()))
                     .unwrap_or_default();
 
-                ListItem::new(Line::from(vec![
-                    Span::styled("📎 ", Style::default()),
-                    Span::styled(name, Style::default().fg(Color::Cyan)),
-                    Span::styled(format!(" ({})", size_str), Style::default().fg(Color::DarkGray)),
-                ]))
+                let selected = app.attachments_focused && i == app.attachment_selected;
+                let name_style = if selected {
+                    Style::default().bg(Color::DarkGray).fg(Color::White)
+                } else {
+                    Style::default().fg(Color::Cyan)
+                };
+
+                let mut spans = vec![Span::styled("📎 ", Style::default())];
+                match &app.attachment_rename {
+                    Some(input) if selected => {
+                        spans.push(Span::styled(format!("{}_", input), Style::default().fg(Color::Yellow)));
+                    }
+                    _ => spans.push(Span::styled(attachment.name.as_str(), name_style)),
+                }
+                spans.push(Span::styled(format!(" ({})", size_str), Style::default().fg(Color::DarkGray)));
+
+                // Show where a renamed file comes from
+                let source_name = attachment.source.file_name().map(|n| n.to_string_lossy().to_string());
+                if source_name.as_deref() != Some(attachment.name.as_str()) {
+                    spans.push(Span::styled(
+                        format!(" ← {}", attachment.source.display()),
+                        Style::default().fg(Color::DarkGray),
+                    ));
+                }
+                ListItem::new(Line::from(spans))
             })
             .collect();
 
-        let list = List::new(items)
-            .block(Block::default().borders(Borders::ALL).title(format!("Attachments ({})", app.attachments.len())));
-        frame.render_widget(list, area);
+        let mut state = ListState::default();
+        if app.attachments_focused {
+            state.select(Some(app.attachment_selected));
+        }
+
+        let list = List::new(items).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(format!("Attachments ({})", app.attachments.len()))
+                .border_style(border_style),
+        );
+        frame.render_stateful_widget(list, area, &mut state);
+
+        let inner = area.inner(ratatui::layout::Margin { vertical: 1, horizontal: 1 });
+        app.register_list_rows(inner, ClickList::Attachments, state.offset(), app.attachments.len());
     }
 }
 
-fn format_size(bytes: u64) -> String {
+pub fn format_size(bytes: u64) -> String {
     const KB: u64 = 1024;
     const MB: u64 = KB * 1024;
     const GB: u64 = MB * 1024;
@@ -161,8 +286,8 @@ fn format_size(bytes: u64) -> String {
 }
 
 fn render_file_browser(frame: &mut Frame, app: &App, area: Rect) {
-    let popup_width = 60u16.min(area.width.saturating_sub(4));
-    let popup_height = 20u16.min(area.height.saturating_sub(4));
+    let popup_width = 70u16.min(area.width.saturating_sub(4));
+    let popup_height = 24u16.min(area.height.saturating_sub(4));
     let popup_x = (area.width.saturating_sub(popup_width)) / 2 + area.x;
     let popup_y = (area.height.saturating_sub(popup_height)) / 2 + area.y;
     let popup_area = Rect::new(popup_x, popup_y, popup_width, popup_height);
@@ -173,24 +298,54 @@ fn render_file_browser(frame: &mut Frame, app: &App, area: Rect) {
         .direction(Direction::Vertical)
         .margin(1)
         .constraints([
-            Constraint::Length(2),  // Current path
+            Constraint::Length(1),  // Current path
+            Constraint::Length(2),  // Filter / path input
             Constraint::Min(5),     // File list
+            Constraint::Length(1),  // Keys
         ])
         .split(popup_area);
 
     // Outer border
+    let title = if app.file_browser_marked.is_empty() {
+        "Select File".to_string()
+    } else {
+        format!("Select Files ({} marked)", app.file_browser_marked.len())
+    };
     let outer_block = Block::default()
         .borders(Borders::ALL)
-        .title("Select File (↑↓ navigate, Enter select, Backspace parent)")
+        .title(title)
         .border_style(Style::default().fg(Color::Yellow));
     frame.render_widget(outer_block, popup_area);
 
     // Current path
-    let path_str = app.file_browser_dir.to_string_lossy();
-    let path_display = Paragraph::new(path_str.as_ref())
-        .style(Style::default().fg(Color::Cyan))
-        .block(Block::default().borders(Borders::BOTTOM));
-    frame.render_widget(path_display, chunks[0]);
+    let mut path_spans = vec![Span::styled(app.file_browser_dir.to_string_lossy(), Style::default().fg(Color::Cyan))];
+    if app.file_browser_show_hidden {
+        path_spans.push(Span::styled("  (hidden shown)", Style::default().fg(Color::DarkGray)));
+    }
+    frame.render_widget(Paragraph::new(Line::from(path_spans)), chunks[0]);
+
+    // Filter / path input
+    let label = if app.file_browser_path_mode() { "Path: " } else { "Filter: " };
+    let input = Paragraph::new(Line::from(vec![
+        Span::styled(label, Style::default().fg(Color::Yellow)),
+        Span::raw(app.file_browser_filter.as_str()),
+    ]))
+    .block(Block::default().borders(Borders::BOTTOM));
+    frame.render_widget(input, chunks[1]);
+
+    let key_style = Style::default().fg(Color::Yellow);
+    if app.file_browser_recent_open {
+        render_recent_dirs(frame, app, chunks[2]);
+        let keys = Paragraph::new(Line::from(vec![
+            Span::styled("Enter", key_style),
+            Span::raw(" Go to  "),
+            Span::styled("ESC", key_style),
+            Span::raw(" Back to files"),
+        ]));
+        frame.render_widget(keys, chunks[3]);
+        app.register_click(area, ClickTarget::Blocker);
+        return;
+    }
 
     // File list
     let items: Vec<ListItem> = app
@@ -204,9 +359,12 @@ fn render_file_browser(frame: &mut Frame, app: &App, area: Rect) {
             } else {
                 format!(" ({})", format_size(entry.size))
             };
+            let marked = app.file_browser_marked.contains(&entry.path);
 
             let style = if i == app.file_browser_selected {
                 Style::default().bg(Color::DarkGray).fg(Color::White)
+            } else if marked {
+                Style::default().fg(Color::Green)
             } else if entry.is_dir {
                 Style::default().fg(Color::Blue)
             } else {
@@ -214,6 +372,7 @@ fn render_file_browser(frame: &mut Frame, app: &App, area: Rect) {
             };
 
             ListItem::new(Line::from(vec![
+                Span::styled(if marked { "✓ " } else { "  " }, Style::default().fg(Color::Green)),
                 Span::raw(icon),
                 Span::styled(&entry.name, style),
                 Span::styled(size_str, Style::default().fg(Color::DarkGray)),
@@ -227,7 +386,365 @@ fn render_file_browser(frame: &mut Frame, app: &App, area: Rect) {
     let list = List::new(items)
         .highlight_style(Style::default().add_modifier(Modifier::BOLD));
 
-    frame.render_stateful_widget(list, chunks[1], &mut state);
+    frame.render_stateful_widget(list, chunks[2], &mut state);
+
+    let keys = Paragraph::new(Line::from(vec![
+        Span::styled("Space", key_style),
+        Span::raw(" Mark  "),
+        Span::styled("Tab", key_style),
+        Span::raw(" Complete  "),
+        Span::styled("Ctrl+D", key_style),
+        Span::raw(" Hidden  "),
+        Span::styled("Ctrl+R", key_style),
+        Span::raw(" Recent  "),
+        Span::styled("/ ~", key_style),
+        Span::raw(" Path"),
+    ]));
+    frame.render_widget(keys, chunks[3]);
+
+    app.register_click(area, ClickTarget::Blocker);
+    app.register_list_rows(chunks[2], ClickList::FileBrowser, state.offset(), app.file_browser_entries.len());
+
+    // Cursor at the end of the input
+    let input_len = (label.chars().count() + app.file_browser_filter.chars().count()) as u16;
+    frame.set_cursor_position(((chunks[1].x + input_len).min(chunks[1].right().saturating_sub(1)), chunks[1].y));
+}
+
+fn render_recent_dirs(frame: &mut Frame, app: &App, area: Rect) {
+    let items: Vec<ListItem> = app
+        .ui_state
+        .recent_dirs
+        .iter()
+        .enumerate()
+        .map(|(i, dir)| {
+            let style = if i == app.file_browser_recent_selected {
+                Style::default().bg(Color::DarkGray).fg(Color::White)
+            } else if dir.is_dir() {
+                Style::default().fg(Color::Blue)
+            } else {
+                Style::default().fg(Color::DarkGray)
+            };
+            ListItem::new(Line::from(vec![Span::raw("🕘 "), Span::styled(dir.to_string_lossy(), style)]))
+        })
+        .collect();
+
+    let mut state = ListState::default();
+    state.select(Some(app.file_browser_recent_selected));
+
+    let list = List::new(items).block(Block::default().title("Recent directories"));
+    frame.render_stateful_widget(list, area, &mut state);
+
+    let inner = Rect { y: area.y + 1, height: area.height.saturating_sub(1), ..area };
+    app.register_list_rows(inner, ClickList::RecentDirs, state.offset(), app.ui_state.recent_dirs.len());
+}
+
+fn render_exit_prompt(frame: &mut Frame, app: &App, area: Rect) {
+    let popup_width = 44u16.min(area.width.saturating_sub(4));
+    let popup_height = 7u16;
+    let popup_x = (area.width.saturating_sub(popup_width)) / 2 + area.x;
+    let popup_y = (area.height.saturating_sub(popup_height)) / 2 + area.y;
+    let popup_area = Rect::new(popup_x, popup_y, popup_width, popup_height);
+
+    frame.render_widget(Clear, popup_area);
+
+    let key_style = Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD);
+    // Notes are saved or discarded, they have no drafts
+    let mut choices = vec![Span::styled("s", key_style), Span::raw(" Save  ")];
+    if app.notes_editing.is_none() {
+        choices.extend([Span::styled("d", key_style), Span::raw(" Keep as draft  ")]);
+    }
+    choices.extend([Span::styled("x", key_style), Span::raw(" Discard")]);
+    let lines = vec![
+        Line::from(if app.notes_editing.is_some() {
+            "These notes have not been saved."
+        } else {
+            "This log entry has not been saved."
+        }),
+        Line::from(""),
+        Line::from(choices),
+        Line::from(vec![
+            Span::styled("ESC", key_style),
+            Span::raw(" Keep editing"),
+        ]),
+    ];
+
+    let prompt = Paragraph::new(lines).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title("Leave editor?")
+            .border_style(Style::default().fg(Color::Yellow)),
+    );
+    frame.render_widget(prompt, popup_area);
+    app.register_click(area, ClickTarget::Blocker);
+}
+
+fn render_inline_create(frame: &mut Frame, app: &App, area: Rect) {
+    let popup_width = 56u16.min(area.width.saturating_sub(4));
+    let popup_height = 11u16;
+    let popup_x = (area.width.saturating_sub(popup_width)) / 2 + area.x;
+    let popup_y = (area.height.saturating_sub(popup_height)) / 2 + area.y;
+    let popup_area = Rect::new(popup_x, popup_y, popup_width, popup_height);
+
+    frame.render_widget(Clear, popup_area);
+
+    let (title, fields) = match app.inline_create {
+        Some(AutocompleteType::Project) => {
+            let group = if app.project_edit_group.is_empty() {
+                "(no group)".to_string()
+            } else {
+                format!("< {} >", app.project_edit_group)
+            };
+            (
+                "New Project",
+                [
+                    ("Name *", app.project_edit_name.clone()),
+                    ("Description", app.project_edit_description.clone()),
+                    ("Group", group),
+                ],
+            )
+        }
+        _ => (
+            "New Person",
+            [
+                ("Name *", app.person_edit_name.clone()),
+                ("Full name", app.person_edit_full_name.clone()),
+                ("Email", app.person_edit_email.clone()),
+            ],
+        ),
+    };
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(title)
+        .border_style(Style::default().fg(Color::Yellow));
+    let inner = block.inner(popup_area);
+    frame.render_widget(block, popup_area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(3), Constraint::Length(3), Constraint::Length(3)])
+        .split(inner);
+
+    for (i, (label, value)) in fields.iter().enumerate() {
+        let style = if app.inline_create_field == i {
+            Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)
+        } else {
+            Style::default()
+        };
+        let field = Paragraph::new(value.as_str())
+            .style(style)
+            .block(Block::default().borders(Borders::ALL).title(*label));
+        frame.render_widget(field, chunks[i]);
+    }
+
+    app.register_click(area, ClickTarget::Blocker);
+
+    // Cursor at the end of the focused text field
+    if let Some(AutocompleteType::Project) = app.inline_create {
+        if app.inline_create_field == 2 {
+            return;
+        }
+    }
+    let field = chunks[app.inline_create_field];
+    let text_len = fields[app.inline_create_field].1.chars().count() as u16;
+    frame.set_cursor_position((
+        (field.x + 1 + text_len).min(field.right().saturating_sub(2)),
+        field.y + 1,
+    ));
+}
+
+fn render_paste_prompt(frame: &mut Frame, app: &App, area: Rect) {
+    let popup_width = 60u16.min(area.width.saturating_sub(4));
+    let popup_height = (app.paste_pending_files.len() as u16 + 5).min(15).min(area.height.saturating_sub(4));
+    let popup_x = (area.width.saturating_sub(popup_width)) / 2 + area.x;
+    let popup_y = (area.height.saturating_sub(popup_height)) / 2 + area.y;
+    let popup_area = Rect::new(popup_x, popup_y, popup_width, popup_height);
+
+    frame.render_widget(Clear, popup_area);
+
+    let key_style = Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD);
+    let mut lines: Vec<Line> = app
+        .paste_pending_files
+        .iter()
+        .map(|path| {
+            let size_str = std::fs::metadata(path)
+                .map(|m| format_size(m.len()))
+                .unwrap_or_default();
+            Line::from(vec![
+                Span::raw("📎 "),
+                Span::styled(path.to_string_lossy().to_string(), Style::default().fg(Color::Cyan)),
+                Span::styled(format!(" ({})", size_str), Style::default().fg(Color::DarkGray)),
+            ])
+        })
+        .collect();
+    lines.push(Line::from(""));
+    lines.push(Line::from(vec![
+        Span::styled("a", key_style),
+        Span::raw(" Attach  "),
+        Span::styled("t", key_style),
+        Span::raw(" Paste as text  "),
+        Span::styled("ESC", key_style),
+        Span::raw(" Cancel"),
+    ]));
+
+    let prompt = Paragraph::new(lines).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title("Pasted file paths")
+            .border_style(Style::default().fg(Color::Yellow)),
+    );
+    frame.render_widget(prompt, popup_area);
+    app.register_click(area, ClickTarget::Blocker);
+}
+
+fn render_template_picker(frame: &mut Frame, app: &App, area: Rect) {
+    let popup_width = 70u16.min(area.width.saturating_sub(4));
+    let popup_height = 20u16.min(area.height.saturating_sub(4));
+    let popup_x = (area.width.saturating_sub(popup_width)) / 2 + area.x;
+    let popup_y = (area.height.saturating_sub(popup_height)) / 2 + area.y;
+    let popup_area = Rect::new(popup_x, popup_y, popup_width, popup_height);
+
+    frame.render_widget(Clear, popup_area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage(40), // Template list
+            Constraint::Percentage(60), // Preview
+        ])
+        .split(popup_area);
+
+    // Template list, with a blank entry first
+    let names = std::iter::once("(blank entry)").chain(app.templates.iter().map(|t| t.name.as_str()));
+    let items: Vec<ListItem> = names
+        .enumerate()
+        .map(|(i, name)| {
+            let style = if i == app.template_selected {
+                Style::default().bg(Color::DarkGray).fg(Color::White)
+            } else if i == 0 {
+                Style::default().fg(Color::DarkGray)
+            } else {
+                Style::default()
+            };
+            ListItem::new(Line::from(Span::styled(name, style)))
+        })
+        .collect();
+
+    let mut state = ListState::default();
+    state.select(Some(app.template_selected));
+
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title("Templates")
+                .border_style(Style::default().fg(Color::Yellow)),
+        )
+        .highlight_style(Style::default().add_modifier(Modifier::BOLD));
+    frame.render_stateful_widget(list, chunks[0], &mut state);
+
+    app.register_click(area, ClickTarget::Blocker);
+    let inner = chunks[0].inner(ratatui::layout::Margin { vertical: 1, horizontal: 1 });
+    app.register_list_rows(inner, ClickList::Templates, state.offset(), app.templates.len() + 1);
+
+    // Preview of the raw template
+    let preview_text = app
+        .template_selected
+        .checked_sub(1)
+        .and_then(|i| app.templates.get(i))
+        .map(|t| t.content.as_str())
+        .unwrap_or("Start with an empty log entry");
+    let preview = Paragraph::new(preview_text)
+        .style(Style::default().fg(Color::Gray))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title("Preview")
+                .border_style(Style::default().fg(Color::Yellow)),
+        )
+        .wrap(Wrap { trim: false });
+    frame.render_widget(preview, chunks[1]);
+}
+
+fn render_template_field_prompt(frame: &mut Frame, app: &App, area: Rect) {
+    let field = match app.current_template_field() {
+        Some(field) => field,
+        None => return,
+    };
+    let suggestions = app.template_field_suggestions();
+
+    let popup_width = 50u16.min(area.width.saturating_sub(4));
+    let popup_height = if field.kind == TemplateFieldKind::Text { 5 } else { 14u16.min(area.height.saturating_sub(4)) };
+    let popup_x = (area.width.saturating_sub(popup_width)) / 2 + area.x;
+    let popup_y = (area.height.saturating_sub(popup_height)) / 2 + area.y;
+    let popup_area = Rect::new(popup_x, popup_y, popup_width, popup_height);
+
+    frame.render_widget(Clear, popup_area);
+
+    let template_name = app.template_pending.as_ref().map(|t| t.name.as_str()).unwrap_or("");
+    let outer_block = Block::default()
+        .borders(Borders::ALL)
+        .title(format!(
+            "{} ({}/{})",
+            template_name,
+            app.template_field_index + 1,
+            app.template_fields.len()
+        ))
+        .border_style(Style::default().fg(Color::Yellow));
+    frame.render_widget(outer_block, popup_area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints([
+            Constraint::Length(3), // Input
+            Constraint::Min(0),    // Suggestions
+        ])
+        .split(popup_area);
+
+    let (prefix, prefix_color) = match field.kind {
+        TemplateFieldKind::Person => ("@", Color::Blue),
+        TemplateFieldKind::Project => ("#", Color::Green),
+        TemplateFieldKind::Text => ("", Color::White),
+    };
+    let input = Paragraph::new(Line::from(vec![
+        Span::styled(prefix, Style::default().fg(prefix_color).add_modifier(Modifier::BOLD)),
+        Span::raw(app.template_field_input.as_str()),
+    ]))
+    .block(Block::default().borders(Borders::ALL).title(field.label.as_str()));
+    frame.render_widget(input, chunks[0]);
+
+    if field.kind != TemplateFieldKind::Text {
+        let items: Vec<ListItem> = suggestions
+            .iter()
+            .enumerate()
+            .map(|(i, s)| {
+                let style = if i == app.template_field_suggestion {
+                    Style::default().bg(Color::DarkGray).fg(Color::White)
+                } else {
+                    Style::default()
+                };
+                ListItem::new(format!("{}{}", prefix, s)).style(style)
+            })
+            .collect();
+
+        let mut state = ListState::default();
+        state.select(Some(app.template_field_suggestion));
+
+        let list = List::new(items)
+            .highlight_style(Style::default().add_modifier(Modifier::BOLD));
+        frame.render_stateful_widget(list, chunks[1], &mut state);
+        app.register_click(area, ClickTarget::Blocker);
+        app.register_list_rows(chunks[1], ClickList::TemplateSuggestions, state.offset(), suggestions.len());
+    } else {
+        app.register_click(area, ClickTarget::Blocker);
+    }
+
+    let inner = chunks[0].inner(ratatui::layout::Margin { vertical: 1, horizontal: 1 });
+    frame.set_cursor_position((
+        inner.x + (prefix.len() + app.template_field_input.chars().count()) as u16,
+        inner.y,
+    ));
 }
 
 fn render_editor(frame: &mut Frame, app: &App, area: Rect) {
@@ -236,47 +753,33 @@ fn render_editor(frame: &mut Frame, app: &App, area: Rect) {
     // Process content line by line for proper multi-line support
     let lines: Vec<Line> = content
         .split('\n')
-        .map(|line_str| {
-            let mut spans: Vec<Span> = Vec::new();
-            let mut current_word = String::new();
-
-            for c in line_str.chars() {
-                if c.is_whitespace() {
-                    // Flush current word
-                    if !current_word.is_empty() {
-                        let style = get_word_style(&current_word);
-                        spans.push(Span::styled(current_word.clone(), style));
-                        current_word.clear();
-                    }
-                    spans.push(Span::raw(c.to_string()));
-                } else {
-                    current_word.push(c);
-                }
-            }
-
-            // Flush remaining word
-            if !current_word.is_empty() {
-                let style = get_word_style(&current_word);
-                spans.push(Span::styled(current_word, style));
-            }
-
-            Line::from(spans)
-        })
+        .map(|line_str| highlight_line(line_str, |name| app.attachments.iter().any(|a| a.name == name)))
         .collect();
 
     let editor = Paragraph::new(lines)
         .block(
             Block::default()
                 .borders(Borders::ALL)
-                .title("Log Content (type your log entry)")
+                .title(if app.notes_editing.is_some() {
+                    "Notes (Markdown)"
+                } else {
+                    "Log Content (type your log entry)"
+                })
                 .border_style(Style::default().fg(Color::Cyan)),
         )
         .wrap(Wrap { trim: false });
 
     frame.render_widget(editor, area);
 
-    // Only show cursor if file browser is not open
-    if !app.file_browser_open {
+    // Only show cursor if no popup is open
+    let popup_open = app.file_browser_open
+        || app.exit_prompt_open
+        || app.template_picker_open
+        || app.template_pending.is_some()
+        || app.inline_create.is_some()
+        || app.attachments_focused
+        || !app.paste_pending_files.is_empty();
+    if !popup_open {
         // Calculate cursor position accounting for newlines
         let inner = area.inner(ratatui::layout::Margin { vertical: 1, horizontal: 1 });
 
@@ -290,6 +793,53 @@ fn render_editor(frame: &mut Frame, app: &App, area: Rect) {
             inner.x + cursor_x.min(inner.width.saturating_sub(1)),
             inner.y + cursor_y.min(inner.height.saturating_sub(1)),
         ));
+        app.register_click(inner, ClickTarget::Editor);
+    }
+}
+
+/// Highlight tags, todos and attachment references in one line of log text
+///
+/// References for which `ref_exists` returns false are flagged in red.
+pub fn highlight_line<'a>(line: &'a str, ref_exists: impl Fn(&str) -> bool) -> Line<'a> {
+    let mut spans: Vec<Span> = Vec::new();
+    let mut pos = 0;
+
+    for reference in AttachmentRef::find_all(line) {
+        push_words(&mut spans, &line[pos..reference.start]);
+        let style = if ref_exists(&reference.name) {
+            Style::default().fg(Color::Cyan).add_modifier(Modifier::UNDERLINED)
+        } else {
+            Style::default().fg(Color::Red).add_modifier(Modifier::UNDERLINED)
+        };
+        spans.push(Span::styled(&line[reference.start..reference.end], style));
+        pos = reference.end;
+    }
+    push_words(&mut spans, &line[pos..]);
+
+    Line::from(spans)
+}
+
+fn push_words<'a>(spans: &mut Vec<Span<'a>>, text: &'a str) {
+    let mut current_word = String::new();
+
+    for c in text.chars() {
+        if c.is_whitespace() {
+            // Flush current word
+            if !current_word.is_empty() {
+                let style = get_word_style(&current_word);
+                spans.push(Span::styled(current_word.clone(), style));
+                current_word.clear();
+            }
+            spans.push(Span::raw(c.to_string()));
+        } else {
+            current_word.push(c);
+        }
+    }
+
+    // Flush remaining word
+    if !current_word.is_empty() {
+        let style = get_word_style(&current_word);
+        spans.push(Span::styled(current_word, style));
     }
 }
 
@@ -302,6 +852,8 @@ fn get_word_style(word: &str) -> Style {
         Style::default().fg(Color::Magenta)
     } else if word.starts_with("[x]") || word.starts_with("[X]") {
         Style::default().fg(Color::Gray).add_modifier(Modifier::CROSSED_OUT)
+    } else if word.starts_with("[/]") || word.starts_with("[w]") || word.starts_with("[W]") {
+        Style::default().fg(Color::Cyan)
     } else {
         Style::default()
     }
@@ -314,7 +866,7 @@ fn render_autocomplete(frame: &mut Frame, app: &App, editor_area: Rect) {
 
     // Calculate popup position (below cursor, roughly)
     let popup_height = (app.autocomplete_suggestions.len() + 2).min(10) as u16;
-    let popup_width = 30u16;
+    let popup_width = 50u16.min(editor_area.width);
 
     // Position near cursor
     let inner = editor_area.inner(ratatui::layout::Margin { vertical: 1, horizontal: 1 });
@@ -340,15 +892,44 @@ fn render_autocomplete(frame: &mut Frame, app: &App, editor_area: Rect) {
         .iter()
         .enumerate()
         .map(|(i, s)| {
-            let style = if i == app.autocomplete_index {
+            let name_style = if i == app.autocomplete_index {
                 Style::default().bg(Color::DarkGray).fg(Color::White)
+            } else if s.inactive {
+                Style::default().fg(Color::DarkGray)
             } else {
                 Style::default()
             };
-            ListItem::new(s.as_str()).style(style)
+
+            if s.create {
+                let kind = match app.autocomplete_type {
+                    AutocompleteType::Project => "project",
+                    _ => "person",
+                };
+                return ListItem::new(Line::from(vec![
+                    Span::styled("+ ", Style::default().fg(Color::Green)),
+                    Span::styled(format!("Create {} '{}'", kind, s.name), name_style.fg(Color::Green)),
+                ]));
+            }
+
+            // Name, then status and full name/description as secondary info
+            let mut spans = vec![Span::styled(s.name.as_str(), name_style.add_modifier(Modifier::BOLD))];
+            if let Some(status) = &s.status {
+                spans.push(Span::raw(" "));
+                spans.push(Span::styled(
+                    format!("[{}]", status),
+                    Style::default().fg(app.config.get_state_color(status)),
+                ));
+            }
+            if !s.detail.is_empty() {
+                spans.push(Span::styled(format!(" {}", s.detail), Style::default().fg(Color::DarkGray)));
+            }
+            ListItem::new(Line::from(spans))
         })
         .collect();
 
+    let mut state = ListState::default();
+    state.select(Some(app.autocomplete_index));
+
     let list = List::new(items)
         .block(
             Block::default()
@@ -357,5 +938,8 @@ fn render_autocomplete(frame: &mut Frame, app: &App, editor_area: Rect) {
                 .border_style(Style::default().fg(Color::Yellow)),
         );
 
-    frame.render_widget(list, popup_area);
+    frame.render_stateful_widget(list, popup_area, &mut state);
+
+    let inner = popup_area.inner(ratatui::layout::Margin { vertical: 1, horizontal: 1 });
+    app.register_list_rows(inner, ClickList::Autocomplete, state.offset(), app.autocomplete_suggestions.len());
 }
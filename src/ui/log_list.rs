@@ -6,7 +6,8 @@ use ratatui::{
     Frame,
 };
 
-use super::app::{App, LogFilterPanel};
+use super::app::{App, ClickList, ClickTarget, LogFilterPanel};
+use super::log_entry::format_size;
 
 pub fn render(frame: &mut Frame, app: &mut App, area: Rect) {
     let chunks = Layout::default()
@@ -47,9 +48,20 @@ pub fn render(frame: &mut Frame, app: &mut App, area: Rect) {
             Span::raw(" Projects  "),
             Span::styled("h", Style::default().fg(Color::Yellow)),
             Span::raw(" People  "),
+            Span::styled("/", Style::default().fg(Color::Yellow)),
+            Span::raw(" Search attachments/notes  "),
             Span::styled("ESC", Style::default().fg(Color::Yellow)),
             Span::raw(" Back"),
         ]
+    } else if app.log_filter_panel == LogFilterPanel::AttachmentSearch {
+        vec![
+            Span::styled("↑↓", Style::default().fg(Color::Yellow)),
+            Span::raw(" Navigate  "),
+            Span::styled("Enter", Style::default().fg(Color::Yellow)),
+            Span::raw(" Open file  "),
+            Span::styled("ESC", Style::default().fg(Color::Yellow)),
+            Span::raw(" Close search"),
+        ]
     } else {
         vec![
             Span::styled("ESC", Style::default().fg(Color::Yellow)),
@@ -72,6 +84,9 @@ pub fn render(frame: &mut Frame, app: &mut App, area: Rect) {
         LogFilterPanel::People => {
             render_people_filter_popup(frame, app, area);
         }
+        LogFilterPanel::AttachmentSearch => {
+            render_attachment_search_popup(frame, app, area);
+        }
     }
 }
 
@@ -133,6 +148,10 @@ fn render_filters_summary(frame: &mut Frame, app: &App, area: Rect) {
         .style(people_style)
         .block(Block::default().borders(Borders::ALL).title("People"));
     frame.render_widget(people, filter_chunks[2]);
+
+    for (i, chunk) in filter_chunks.iter().enumerate() {
+        app.register_click(*chunk, ClickTarget::Row(ClickList::LogFilterPanels, i));
+    }
 }
 
 fn render_date_filter_popup(frame: &mut Frame, app: &App, area: Rect) {
@@ -196,6 +215,8 @@ fn render_date_filter_popup(frame: &mut Frame, app: &App, area: Rect) {
         inner.x + input.len() as u16,
         inner.y,
     ));
+
+    app.register_click(area, ClickTarget::Blocker);
 }
 
 fn render_project_filter_popup(frame: &mut Frame, app: &App, area: Rect) {
@@ -242,6 +263,88 @@ fn render_project_filter_popup(frame: &mut Frame, app: &App, area: Rect) {
         .highlight_style(Style::default().add_modifier(Modifier::BOLD));
 
     frame.render_stateful_widget(list, popup_area, &mut state);
+    app.register_click(area, ClickTarget::Blocker);
+    let inner = popup_area.inner(ratatui::layout::Margin { vertical: 1, horizontal: 1 });
+    app.register_list_rows(inner, ClickList::LogFilterProjects, state.offset(), all_projects.len());
+}
+
+fn render_attachment_search_popup(frame: &mut Frame, app: &App, area: Rect) {
+    let popup_width = (area.width * 4 / 5).max(40).min(area.width);
+    let popup_height = (area.height * 3 / 4).max(10).min(area.height);
+    let popup_x = (area.width.saturating_sub(popup_width)) / 2 + area.x;
+    let popup_y = (area.height.saturating_sub(popup_height)) / 2 + area.y;
+    let popup_area = Rect::new(popup_x, popup_y, popup_width, popup_height);
+
+    frame.render_widget(Clear, popup_area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(3), Constraint::Min(3)])
+        .split(popup_area);
+
+    // Query input
+    let input = Paragraph::new(app.attachment_search_query.as_str()).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(format!("Search attachments and notes ({} text files)", app.attachment_texts.len()))
+            .border_style(Style::default().fg(Color::Yellow)),
+    );
+    frame.render_widget(input, chunks[0]);
+    frame.set_cursor_position((
+        (chunks[0].x + 1 + app.attachment_search_query.chars().count() as u16).min(chunks[0].right().saturating_sub(2)),
+        chunks[0].y + 1,
+    ));
+
+    // Results: entry, file and line, then the snippet with the match highlighted
+    let items: Vec<ListItem> = app
+        .attachment_search_results
+        .iter()
+        .enumerate()
+        .map(|(i, result)| {
+            let text = &app.attachment_texts[result.text_index];
+            let file_name = text.path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+            let base = if i == app.attachment_search_selected {
+                Style::default().bg(Color::DarkGray).fg(Color::White)
+            } else {
+                Style::default()
+            };
+
+            let chars: Vec<char> = result.snippet.chars().collect();
+            let match_start = result.match_start.min(chars.len());
+            let match_end = (result.match_start + result.match_len).min(chars.len());
+            ListItem::new(Line::from(vec![
+                Span::styled(text.timestamp.format("%Y-%m-%d %H:%M ").to_string(), Style::default().fg(Color::Cyan)),
+                Span::styled(format!("{}:{} ", file_name, result.line + 1), Style::default().fg(Color::Green)),
+                Span::styled(chars[..match_start].iter().collect::<String>(), base),
+                Span::styled(
+                    chars[match_start..match_end].iter().collect::<String>(),
+                    base.fg(Color::Yellow).add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(chars[match_end..].iter().collect::<String>(), base),
+            ]))
+        })
+        .collect();
+
+    let title = if app.attachment_search_query.trim().is_empty() {
+        "Results".to_string()
+    } else {
+        format!("Results ({})", app.attachment_search_results.len())
+    };
+
+    let mut state = ListState::default();
+    state.select(Some(app.attachment_search_selected));
+
+    let list = List::new(items).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(title)
+            .border_style(Style::default().fg(Color::Yellow)),
+    );
+    frame.render_stateful_widget(list, chunks[1], &mut state);
+
+    app.register_click(area, ClickTarget::Blocker);
+    let inner = chunks[1].inner(ratatui::layout::Margin { vertical: 1, horizontal: 1 });
+    app.register_list_rows(inner, ClickList::AttachmentSearchResults, state.offset(), app.attachment_search_results.len());
 }
 
 fn render_people_filter_popup(frame: &mut Frame, app: &App, area: Rect) {
@@ -288,6 +391,9 @@ fn render_people_filter_popup(frame: &mut Frame, app: &App, area: Rect) {
         .highlight_style(Style::default().add_modifier(Modifier::BOLD));
 
     frame.render_stateful_widget(list, popup_area, &mut state);
+    app.register_click(area, ClickTarget::Blocker);
+    let inner = popup_area.inner(ratatui::layout::Margin { vertical: 1, horizontal: 1 });
+    app.register_list_rows(inner, ClickList::LogFilterPeople, state.offset(), all_people.len());
 }
 
 fn render_log_list(frame: &mut Frame, app: &mut App, area: Rect) {
@@ -315,12 +421,19 @@ fn render_log_list(frame: &mut Frame, app: &mut App, area: Rect) {
                 Style::default()
             };
 
-            let line = Line::from(vec![
+            let mut spans = vec![
                 Span::styled(timestamp, Style::default().fg(Color::Cyan)),
                 Span::raw(" | "),
                 Span::styled(preview, style),
                 Span::styled(tags, Style::default().fg(Color::Green)),
-            ]);
+            ];
+            if !log.attachments.is_empty() {
+                spans.push(Span::styled(
+                    format!(" 📎{}", log.attachments.len()),
+                    Style::default().fg(Color::DarkGray),
+                ));
+            }
+            let line = Line::from(spans);
 
             ListItem::new(line)
         })
@@ -334,15 +447,24 @@ fn render_log_list(frame: &mut Frame, app: &mut App, area: Rect) {
         .highlight_style(Style::default().add_modifier(Modifier::BOLD));
 
     frame.render_stateful_widget(list, area, &mut state);
+    let inner = area.inner(ratatui::layout::Margin { vertical: 1, horizontal: 1 });
+    app.register_list_rows(inner, ClickList::Logs, state.offset(), app.filtered_logs.len());
 }
 
-pub fn render_view_log(frame: &mut Frame, app: &App, area: Rect, path: &std::path::PathBuf) {
+pub fn render_view_log(frame: &mut Frame, app: &App, area: Rect, path: &std::path::Path) {
+    let attachment_count = app.view_log_attachments.len() + app.view_log_missing_refs.len();
+    let attachments_height = if attachment_count == 0 {
+        0
+    } else {
+        attachment_count.min(5) as u16 + 2
+    };
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .margin(1)
         .constraints([
             Constraint::Length(3),  // Title
             Constraint::Min(10),    // Content
+            Constraint::Length(attachments_height),  // Attachments
             Constraint::Length(3),  // Help bar
         ])
         .split(area);
@@ -353,75 +475,105 @@ pub fn render_view_log(frame: &mut Frame, app: &App, area: Rect, path: &std::pat
         .and_then(|p| p.file_name())
         .and_then(|n| n.to_str())
         .unwrap_or("Log Entry");
+    let title_text = match path.file_name().and_then(|n| n.to_str()) {
+        Some(file) if file != "log.txt" => format!("{} / {}", title_text, file),
+        _ => title_text.to_string(),
+    };
     let title = Paragraph::new(format!("Viewing: {}", title_text))
         .style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD))
         .block(Block::default().borders(Borders::ALL));
     frame.render_widget(title, chunks[0]);
 
     // Content
-    let content = if let Ok(text) = std::fs::read_to_string(path) {
-        text
-    } else {
-        "Error reading log file".to_string()
-    };
-
-    // Syntax highlight the content
-    let lines: Vec<Line> = content
-        .lines()
-        .map(|line| {
-            let mut spans: Vec<Span> = Vec::new();
-            let mut current_word = String::new();
-
-            for c in line.chars() {
-                if c.is_whitespace() {
-                    if !current_word.is_empty() {
-                        let style = word_style(&current_word);
-                        spans.push(Span::styled(current_word.clone(), style));
-                        current_word.clear();
-                    }
-                    spans.push(Span::raw(c.to_string()));
-                } else {
-                    current_word.push(c);
-                }
-            }
-
-            if !current_word.is_empty() {
-                let style = word_style(&current_word);
-                spans.push(Span::styled(current_word, style));
-            }
-
-            Line::from(spans)
-        })
-        .collect();
-
-    let log_content = Paragraph::new(lines)
+    let log_content = Paragraph::new(app.view_log_lines.clone())
         .block(Block::default().borders(Borders::ALL).title("Content"))
         .wrap(Wrap { trim: false })
         .scroll((app.view_log_scroll, 0));
     frame.render_widget(log_content, chunks[1]);
 
+    // Attachments
+    if attachment_count > 0 {
+        render_view_log_attachments(frame, app, chunks[2]);
+    }
+
     // Help bar
-    let help_text = vec![
-        Span::styled("↑↓", Style::default().fg(Color::Yellow)),
-        Span::raw(" Scroll  "),
-        Span::styled("ESC", Style::default().fg(Color::Yellow)),
-        Span::raw(" Back"),
-    ];
+    let help_text = if app.view_log_attachments_focused {
+        vec![
+            Span::styled("↑↓", Style::default().fg(Color::Yellow)),
+            Span::raw(" Select  "),
+            Span::styled("Enter", Style::default().fg(Color::Yellow)),
+            Span::raw(" Open  "),
+            Span::styled("Tab/ESC", Style::default().fg(Color::Yellow)),
+            Span::raw(" Back to content"),
+        ]
+    } else {
+        let mut spans = vec![
+            Span::styled("↑↓", Style::default().fg(Color::Yellow)),
+            Span::raw(" Scroll  "),
+        ];
+        if attachment_count > 0 {
+            spans.push(Span::styled("Tab", Style::default().fg(Color::Yellow)));
+            spans.push(Span::raw(" Attachments  "));
+        }
+        spans.push(Span::styled("ESC", Style::default().fg(Color::Yellow)));
+        spans.push(Span::raw(" Back"));
+        spans
+    };
     let help = Paragraph::new(Line::from(help_text))
         .block(Block::default().borders(Borders::ALL));
-    frame.render_widget(help, chunks[2]);
+    frame.render_widget(help, chunks[3]);
 }
 
-fn word_style(word: &str) -> Style {
-    if word.starts_with('#') {
-        Style::default().fg(Color::Green).add_modifier(Modifier::BOLD)
-    } else if word.starts_with('@') {
-        Style::default().fg(Color::Blue).add_modifier(Modifier::BOLD)
-    } else if word.starts_with("[]") {
-        Style::default().fg(Color::Magenta)
-    } else if word.starts_with("[x]") || word.starts_with("[X]") {
-        Style::default().fg(Color::Gray).add_modifier(Modifier::CROSSED_OUT)
+fn render_view_log_attachments(frame: &mut Frame, app: &App, area: Rect) {
+    let selected_style = Style::default().bg(Color::DarkGray).fg(Color::White);
+    let is_selected = |i: usize| app.view_log_attachments_focused && i == app.view_log_attachment_selected;
+
+    let mut items: Vec<ListItem> = app
+        .view_log_attachments
+        .iter()
+        .enumerate()
+        .map(|(i, path)| {
+            let name = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+            let size_str = std::fs::metadata(path).map(|m| format_size(m.len())).unwrap_or_default();
+            let style = if is_selected(i) { selected_style } else { Style::default().fg(Color::Cyan) };
+            ListItem::new(Line::from(vec![
+                Span::raw("📎 "),
+                Span::styled(name, style),
+                Span::styled(format!(" ({})", size_str), Style::default().fg(Color::DarkGray)),
+            ]))
+        })
+        .collect();
+
+    // References in the text without a matching file
+    let offset = app.view_log_attachments.len();
+    items.extend(app.view_log_missing_refs.iter().enumerate().map(|(i, name)| {
+        let style = if is_selected(offset + i) { selected_style } else { Style::default().fg(Color::Red) };
+        ListItem::new(Line::from(vec![
+            Span::raw("⚠ "),
+            Span::styled(name.as_str(), style),
+            Span::styled(" (missing)", Style::default().fg(Color::Red)),
+        ]))
+    }));
+    let count = items.len();
+
+    let mut state = ListState::default();
+    if app.view_log_attachments_focused {
+        state.select(Some(app.view_log_attachment_selected));
+    }
+
+    let border_style = if app.view_log_attachments_focused {
+        Style::default().fg(Color::Yellow)
     } else {
         Style::default()
-    }
+    };
+    let list = List::new(items).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(format!("Attachments ({})", app.view_log_attachments.len()))
+            .border_style(border_style),
+    );
+    frame.render_stateful_widget(list, area, &mut state);
+
+    let inner = area.inner(ratatui::layout::Margin { vertical: 1, horizontal: 1 });
+    app.register_list_rows(inner, ClickList::ViewLogAttachments, state.offset(), count);
 }
@@ -2,11 +2,11 @@ use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Paragraph},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph},
     Frame,
 };
 
-use super::app::App;
+use super::app::{App, ClickList, ClickTarget};
 
 pub fn render(frame: &mut Frame, app: &App, area: Rect) {
     let chunks = Layout::default()
@@ -33,6 +33,7 @@ pub fn render(frame: &mut Frame, app: &App, area: Rect) {
         "Show logs by project/person",
         "View projects",
         "View people",
+        "Settings",
     ];
 
     let mut menu_items = Vec::new();
@@ -51,15 +52,95 @@ pub fn render(frame: &mut Frame, app: &App, area: Rect) {
         menu_items.push(Line::from(""));
     }
 
+    if !app.drafts.is_empty() {
+        menu_items.push(Line::from(vec![
+            Span::styled("d. ", Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)),
+            Span::styled(format!("Resume a draft ({})", app.drafts.len()), Style::default().fg(Color::Gray)),
+        ]));
+    }
+
     let menu = Paragraph::new(menu_items)
         .block(Block::default().borders(Borders::ALL).title("Menu (↑↓ to navigate, Enter to select, ESC to quit)"))
         .alignment(Alignment::Left);
     frame.render_widget(menu, chunks[1]);
 
+    // Each option is followed by a blank line
+    let inner = chunks[1].inner(ratatui::layout::Margin { vertical: 1, horizontal: 1 });
+    for i in 0..menu_options.len() as u16 {
+        if 2 * i < inner.height {
+            app.register_click(
+                Rect::new(inner.x, inner.y + 2 * i, inner.width, 1),
+                ClickTarget::Row(ClickList::Menu, i as usize),
+            );
+        }
+    }
+
     // Status bar
     let status_text = app.status_message.as_deref().unwrap_or("Ready");
     let status = Paragraph::new(status_text)
         .style(Style::default().fg(Color::Gray))
         .block(Block::default().borders(Borders::ALL));
     frame.render_widget(status, chunks[2]);
+
+    // Offer to resume drafts left over from a previous session
+    if app.draft_picker_open {
+        render_draft_picker(frame, app, area);
+    }
+}
+
+fn render_draft_picker(frame: &mut Frame, app: &App, area: Rect) {
+    let popup_width = 70u16.min(area.width.saturating_sub(4));
+    let popup_height = (app.drafts.len() as u16 + 2).clamp(5, 15).min(area.height.saturating_sub(4));
+    let popup_x = (area.width.saturating_sub(popup_width)) / 2 + area.x;
+    let popup_y = (area.height.saturating_sub(popup_height)) / 2 + area.y;
+    let popup_area = Rect::new(popup_x, popup_y, popup_width, popup_height);
+
+    frame.render_widget(Clear, popup_area);
+
+    let items: Vec<ListItem> = app
+        .drafts
+        .iter()
+        .enumerate()
+        .map(|(i, draft)| {
+            let timestamp = draft.timestamp.format("%Y-%m-%d %H:%M").to_string();
+            let first_line = draft.content.lines().find(|l| !l.trim().is_empty()).unwrap_or("(empty)");
+            let preview: String = first_line.chars().take(45).collect();
+            let style = if i == app.draft_selected {
+                Style::default().bg(Color::DarkGray).fg(Color::White)
+            } else {
+                Style::default()
+            };
+
+            let mut spans = vec![
+                Span::styled(timestamp, Style::default().fg(Color::Cyan)),
+                Span::raw(" | "),
+                Span::styled(preview, style),
+            ];
+            if !draft.attachments.is_empty() {
+                spans.push(Span::styled(
+                    format!(" 📎{}", draft.attachments.len()),
+                    Style::default().fg(Color::DarkGray),
+                ));
+            }
+            ListItem::new(Line::from(spans))
+        })
+        .collect();
+
+    let mut state = ListState::default();
+    state.select(Some(app.draft_selected));
+
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title("Unsaved drafts (Enter resume, x delete, ESC later)")
+                .border_style(Style::default().fg(Color::Yellow)),
+        )
+        .highlight_style(Style::default().add_modifier(Modifier::BOLD));
+
+    frame.render_stateful_widget(list, popup_area, &mut state);
+
+    app.register_click(area, ClickTarget::Blocker);
+    let inner = popup_area.inner(ratatui::layout::Margin { vertical: 1, horizontal: 1 });
+    app.register_list_rows(inner, ClickList::Drafts, state.offset(), app.drafts.len());
 }
@@ -1,4 +1,6 @@
 pub mod app;
+pub mod board;
+pub mod custom_fields;
 pub mod log_entry;
 pub mod log_list;
 pub mod menu;
@@ -8,4 +10,22 @@ pub mod person_edit;
 pub mod project_details;
 pub mod project_edit;
 pub mod project_list;
+pub mod settings;
 pub mod todo_list;
+
+use ratatui::{
+    style::{Color, Style},
+    text::Line,
+    widgets::{Block, Borders},
+};
+
+/// Bordered block for an edit form field, with its validation error in red along the bottom
+pub fn field_block<'a>(title: &'a str, error: Option<&'a str>) -> Block<'a> {
+    let block = Block::default().borders(Borders::ALL).title(title);
+    match error {
+        Some(error) => block
+            .border_style(Style::default().fg(Color::Red))
+            .title_bottom(Line::styled(format!(" {} ", error), Style::default().fg(Color::Red))),
+        None => block,
+    }
+}
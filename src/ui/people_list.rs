@@ -6,7 +6,7 @@ use ratatui::{
     Frame,
 };
 
-use super::app::App;
+use super::app::{App, ClickList, ClickTarget, PeopleFilterPanel};
 
 pub fn render(frame: &mut Frame, app: &mut App, area: Rect) {
     let chunks = Layout::default()
@@ -14,34 +14,111 @@ pub fn render(frame: &mut Frame, app: &mut App, area: Rect) {
         .margin(1)
         .constraints([
             Constraint::Length(3),  // Title
+            Constraint::Length(3),  // Filter summary
             Constraint::Min(10),    // People list
             Constraint::Length(3),  // Help bar
         ])
         .split(area);
 
     // Title
-    let title = Paragraph::new(format!("People ({} total)", app.people.len()))
+    let archived_note = if app.people_show_archived { ", archived shown" } else { "" };
+    let title = Paragraph::new(format!("People ({} total{})", app.filtered_people.len(), archived_note))
         .style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD))
         .block(Block::default().borders(Borders::ALL));
     frame.render_widget(title, chunks[0]);
 
+    // Filter summary
+    render_filter_summary(frame, app, chunks[1]);
+
     // People list
-    render_people_list(frame, app, chunks[1]);
+    render_people_list(frame, app, chunks[2]);
 
     // Help bar
-    let help_text = vec![
-        Span::styled("↑↓", Style::default().fg(Color::Yellow)),
-        Span::raw(" Navigate  "),
-        Span::styled("Enter", Style::default().fg(Color::Yellow)),
-        Span::raw(" View  "),
-        Span::styled("n", Style::default().fg(Color::Yellow)),
-        Span::raw(" New  "),
-        Span::styled("ESC", Style::default().fg(Color::Yellow)),
-        Span::raw(" Back"),
-    ];
+    let help_text = match app.people_filter_panel {
+        PeopleFilterPanel::None => vec![
+            Span::styled("↑↓", Style::default().fg(Color::Yellow)),
+            Span::raw(" Navigate  "),
+            Span::styled("Enter", Style::default().fg(Color::Yellow)),
+            Span::raw(" View  "),
+            Span::styled("n", Style::default().fg(Color::Yellow)),
+            Span::raw(" New  "),
+            Span::styled("/", Style::default().fg(Color::Yellow)),
+            Span::raw(" Search  "),
+            Span::styled("f", Style::default().fg(Color::Yellow)),
+            Span::raw(" Fields  "),
+            Span::styled("A", Style::default().fg(Color::Yellow)),
+            Span::raw(" Show archived  "),
+            Span::styled("ESC", Style::default().fg(Color::Yellow)),
+            Span::raw(" Back"),
+        ],
+        PeopleFilterPanel::Search => vec![
+            Span::raw("Type to filter by name, email, company or field:value  "),
+            Span::styled("Enter", Style::default().fg(Color::Yellow)),
+            Span::raw(" Keep  "),
+            Span::styled("ESC", Style::default().fg(Color::Yellow)),
+            Span::raw(" Clear"),
+        ],
+        PeopleFilterPanel::Fields => vec![
+            Span::styled("ESC", Style::default().fg(Color::Yellow)),
+            Span::raw(" Close filter"),
+        ],
+    };
     let help = Paragraph::new(Line::from(help_text))
         .block(Block::default().borders(Borders::ALL));
-    frame.render_widget(help, chunks[2]);
+    frame.render_widget(help, chunks[3]);
+
+    if app.people_filter_panel == PeopleFilterPanel::Fields {
+        super::custom_fields::render_facet_popup(
+            frame,
+            app,
+            area,
+            &app.people_field_facets(),
+            &app.people_filter.fields,
+            app.people_filter_field_selected,
+            ClickList::PeopleFilterFields,
+        );
+    }
+}
+
+fn render_filter_summary(frame: &mut Frame, app: &App, area: Rect) {
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(area);
+
+    let search_text = if app.people_filter.text.is_empty() && app.people_filter_panel != PeopleFilterPanel::Search {
+        "[/] Search".to_string()
+    } else {
+        format!("[/] {}", app.people_filter.text)
+    };
+    let fields_text = match super::custom_fields::facet_summary(&app.config.fields.people, &app.people_filter.fields) {
+        Some(summary) => format!("[f] {}", summary),
+        None => "[f] Fields".to_string(),
+    };
+
+    let panels = [
+        (search_text, PeopleFilterPanel::Search, "Filter"),
+        (fields_text, PeopleFilterPanel::Fields, ""),
+    ];
+    for (i, (text, panel, title)) in panels.into_iter().enumerate() {
+        let style = if app.people_filter_panel == panel {
+            Style::default().fg(Color::Yellow)
+        } else {
+            Style::default()
+        };
+        let paragraph = Paragraph::new(text)
+            .style(style)
+            .block(Block::default().borders(Borders::ALL).title(title));
+        frame.render_widget(paragraph, columns[i]);
+        app.register_click(columns[i], ClickTarget::Row(ClickList::PeopleFilterPanels, i));
+    }
+
+    if app.people_filter_panel == PeopleFilterPanel::Search {
+        frame.set_cursor_position((
+            columns[0].x + 5 + app.people_filter.text.chars().count() as u16,
+            columns[0].y + 1,
+        ));
+    }
 }
 
 fn render_people_list(frame: &mut Frame, app: &mut App, area: Rect) {
@@ -50,7 +127,7 @@ fn render_people_list(frame: &mut Frame, app: &mut App, area: Rect) {
     let fixed_overhead = 10; // bullet + spacing + padding
 
     // Find the longest name to determine name column width
-    let max_name_len = app.people.iter()
+    let max_name_len = app.filtered_people.iter()
         .map(|p| p.name.len())
         .max()
         .unwrap_or(15)
@@ -65,7 +142,7 @@ fn render_people_list(frame: &mut Frame, app: &mut App, area: Rect) {
     let mut items: Vec<ListItem> = Vec::new();
     let visual_items_before_scroll = app.person_list_scroll;
 
-    for (i, person) in app.people.iter().enumerate() {
+    for (i, person) in app.filtered_people.iter().enumerate() {
         let is_selected = i == app.person_selected;
         let text_style = if is_selected {
             Style::default().bg(Color::DarkGray).fg(Color::White)
@@ -77,8 +154,7 @@ fn render_people_list(frame: &mut Frame, app: &mut App, area: Rect) {
         let name_formatted = format!("{:width$}", person.name, width = max_name_len);
 
         // Format full name
-        let full_name_raw = person.full_name.as_ref()
-            .map(|f| f.as_str())
+        let full_name_raw = person.full_name.as_deref()
             .unwrap_or("(no full name)");
 
         // Truncate or pad full name to exact width
@@ -93,6 +169,7 @@ fn render_people_list(frame: &mut Frame, app: &mut App, area: Rect) {
             Span::styled(format!("• {}", name_formatted), text_style.add_modifier(Modifier::BOLD)),
             Span::raw(" - "),
             Span::styled(full_name, text_style),
+            Span::styled(if person.archived { " (archived)" } else { "" }, Style::default().fg(Color::DarkGray)),
         ])));
     }
 
@@ -107,4 +184,6 @@ fn render_people_list(frame: &mut Frame, app: &mut App, area: Rect) {
     }
 
     frame.render_stateful_widget(list, area, &mut state);
+    let inner = area.inner(ratatui::layout::Margin { vertical: 1, horizontal: 1 });
+    app.register_list_rows(inner, ClickList::People, state.offset(), app.filtered_people.len());
 }
@@ -6,14 +6,16 @@ use ratatui::{
     Frame,
 };
 
-use super::app::App;
+use super::app::{App, ClickList, DetailsTab};
+use super::project_details::{details_tab_help, details_tabs_title, render_todo_tab};
 
 pub fn render(frame: &mut Frame, app: &mut App, area: Rect, person_idx: usize) {
+    let info_height = if app.config.fields.people.is_empty() { 12 } else { 15 };
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .margin(1)
         .constraints([
-            Constraint::Length(12), // Person info
+            Constraint::Length(info_height), // Person info
             Constraint::Min(10),    // Log list
             Constraint::Length(3),  // Help bar
         ])
@@ -21,29 +23,38 @@ pub fn render(frame: &mut Frame, app: &mut App, area: Rect, person_idx: usize) {
 
     // Render person info
     if let Some(person) = app.people.get(person_idx) {
-        render_person_info(frame, person, chunks[0]);
+        render_person_info(frame, app, person, chunks[0]);
     }
 
-    // Render log list
-    render_log_list(frame, app, chunks[1]);
+    // Render logs or todos
+    if app.details_tab == DetailsTab::Logs {
+        render_log_list(frame, app, chunks[1]);
+    } else {
+        render_todo_tab(frame, app, chunks[1]);
+    }
 
     // Help bar
-    let help_text = vec![
-        Span::styled("↑↓", Style::default().fg(Color::Yellow)),
-        Span::raw(" Navigate  "),
-        Span::styled("Enter", Style::default().fg(Color::Yellow)),
-        Span::raw(" View log  "),
+    let mut help_text = details_tab_help(app);
+    help_text.extend([
         Span::styled("e", Style::default().fg(Color::Yellow)),
         Span::raw(" Edit person  "),
+        Span::styled("a", Style::default().fg(Color::Yellow)),
+        Span::raw(if app.people.get(person_idx).is_some_and(|p| p.archived) { " Restore  " } else { " Archive  " }),
+        Span::styled("d", Style::default().fg(Color::Yellow)),
+        Span::raw(" Delete  "),
         Span::styled("ESC", Style::default().fg(Color::Yellow)),
         Span::raw(" Back"),
-    ];
+    ]);
     let help = Paragraph::new(Line::from(help_text))
         .block(Block::default().borders(Borders::ALL));
     frame.render_widget(help, chunks[2]);
+
+    if app.delete_confirm.is_some() {
+        super::project_details::render_delete_confirm(frame, app, area, "person");
+    }
 }
 
-fn render_person_info(frame: &mut Frame, person: &crate::models::Person, area: Rect) {
+fn render_person_info(frame: &mut Frame, app: &App, person: &crate::models::Person, area: Rect) {
     let info_chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
@@ -51,26 +62,25 @@ fn render_person_info(frame: &mut Frame, person: &crate::models::Person, area: R
             Constraint::Length(3), // Full name
             Constraint::Length(3), // Email
             Constraint::Length(3), // Tel and Company (side by side)
+            Constraint::Length(3), // Custom fields
         ])
         .split(area);
 
     // Name
     let name = Paragraph::new(&person.name as &str)
         .style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD))
-        .block(Block::default().borders(Borders::ALL).title("Name"));
+        .block(Block::default().borders(Borders::ALL).title(if person.archived { "Name (archived)" } else { "Name" }));
     frame.render_widget(name, info_chunks[0]);
 
     // Full name
-    let full_name_text = person.full_name.as_ref()
-        .map(|f| f.as_str())
+    let full_name_text = person.full_name.as_deref()
         .unwrap_or("(no full name)");
     let full_name = Paragraph::new(full_name_text)
         .block(Block::default().borders(Borders::ALL).title("Full Name"));
     frame.render_widget(full_name, info_chunks[1]);
 
     // Email
-    let email_text = person.email.as_ref()
-        .map(|e| e.as_str())
+    let email_text = person.email.as_deref()
         .unwrap_or("(no email)");
     let email = Paragraph::new(email_text)
         .style(Style::default().fg(Color::Blue))
@@ -86,19 +96,21 @@ fn render_person_info(frame: &mut Frame, person: &crate::models::Person, area: R
         ])
         .split(info_chunks[3]);
 
-    let tel_text = person.tel.as_ref()
-        .map(|t| t.as_str())
+    let tel_text = person.tel.as_deref()
         .unwrap_or("(no tel)");
     let tel = Paragraph::new(tel_text)
         .block(Block::default().borders(Borders::ALL).title("Tel"));
     frame.render_widget(tel, bottom_row[0]);
 
-    let company_text = person.company.as_ref()
-        .map(|c| c.as_str())
+    let company_text = person.company.as_deref()
         .unwrap_or("(no company)");
     let company = Paragraph::new(company_text)
         .block(Block::default().borders(Borders::ALL).title("Company"));
     frame.render_widget(company, bottom_row[1]);
+
+    if !app.config.fields.people.is_empty() {
+        super::custom_fields::render_values(frame, &app.config.fields.people, &person.fields, info_chunks[4]);
+    }
 }
 
 fn render_log_list(frame: &mut Frame, app: &mut App, area: Rect) {
@@ -140,9 +152,12 @@ fn render_log_list(frame: &mut Frame, app: &mut App, area: Rect) {
     let mut state = ListState::default();
     state.select(Some(app.person_details_log_selected));
 
+    let title = details_tabs_title(app, area, app.person_details_logs.len());
     let list = List::new(items)
-        .block(Block::default().borders(Borders::ALL).title(format!("Log Entries ({} total)", app.person_details_logs.len())))
+        .block(Block::default().borders(Borders::ALL).title(title))
         .highlight_style(Style::default().add_modifier(Modifier::BOLD));
 
     frame.render_stateful_widget(list, area, &mut state);
+    let inner = area.inner(ratatui::layout::Margin { vertical: 1, horizontal: 1 });
+    app.register_list_rows(inner, ClickList::PersonDetailsLogs, state.offset(), app.person_details_logs.len());
 }
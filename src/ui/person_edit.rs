@@ -6,7 +6,9 @@ use ratatui::{
     Frame,
 };
 
-use super::app::App;
+use super::app::{App, ClickList, ClickTarget};
+use super::field_block;
+use crate::validation::Field;
 
 pub fn render(frame: &mut Frame, app: &App, area: Rect) {
     let chunks = Layout::default()
@@ -19,6 +21,7 @@ pub fn render(frame: &mut Frame, app: &App, area: Rect) {
             Constraint::Length(3),  // Email field
             Constraint::Length(3),  // Tel field
             Constraint::Length(3),  // Company field
+            Constraint::Length(super::custom_fields::editor_height(app)), // Custom fields
             Constraint::Min(1),     // Spacer
             Constraint::Length(3),  // Help bar
         ])
@@ -42,7 +45,7 @@ pub fn render(frame: &mut Frame, app: &App, area: Rect) {
     };
     let name_field = Paragraph::new(app.person_edit_name.as_str())
         .style(name_style)
-        .block(Block::default().borders(Borders::ALL).title("[1] Name *"));
+        .block(field_block("[1] Name *", app.edit_error(Field::Name)));
     frame.render_widget(name_field, chunks[1]);
 
     // Full name field (field 1)
@@ -64,7 +67,7 @@ pub fn render(frame: &mut Frame, app: &App, area: Rect) {
     };
     let email_field = Paragraph::new(app.person_edit_email.as_str())
         .style(email_style)
-        .block(Block::default().borders(Borders::ALL).title("[3] Email"));
+        .block(field_block("[3] Email", app.edit_error(Field::Email)));
     frame.render_widget(email_field, chunks[3]);
 
     // Tel field (field 3)
@@ -93,7 +96,7 @@ pub fn render(frame: &mut Frame, app: &App, area: Rect) {
     let help_text = vec![
         Span::styled("Tab", Style::default().fg(Color::Yellow)),
         Span::raw(" Next field  "),
-        Span::styled("1-5", Style::default().fg(Color::Yellow)),
+        Span::styled("1-6", Style::default().fg(Color::Yellow)),
         Span::raw(" Jump to field  "),
         Span::styled("Ctrl+S", Style::default().fg(Color::Yellow)),
         Span::raw(" Save  "),
@@ -102,7 +105,21 @@ pub fn render(frame: &mut Frame, app: &App, area: Rect) {
     ];
     let help = Paragraph::new(Line::from(help_text))
         .block(Block::default().borders(Borders::ALL));
-    frame.render_widget(help, chunks[7]);
+    frame.render_widget(help, chunks[8]);
+
+    for (i, chunk) in chunks[1..=6].iter().enumerate() {
+        app.register_click(*chunk, ClickTarget::Row(ClickList::PersonEditFields, i));
+    }
+
+    // Custom fields (field 5)
+    super::custom_fields::render_editor(frame, app, chunks[6], app.person_edit_field == 5, 6);
+    if app.custom_field_input.is_some() {
+        super::custom_fields::render_input(frame, app, area);
+        return;
+    }
+    if app.person_edit_field == 5 {
+        return;
+    }
 
     // Set cursor position for active field
     let (field_area, input) = match app.person_edit_field {
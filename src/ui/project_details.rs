@@ -2,18 +2,32 @@ use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, List, ListItem, ListState, Paragraph},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph},
     Frame,
 };
 
-use super::app::App;
+use super::app::{App, ClickList, ClickTarget, DetailsTab, Screen};
 
 pub fn render(frame: &mut Frame, app: &mut App, area: Rect, project_idx: usize) {
+    // Make room for the milestone timeline when there is one
+    let mut info_height = match app.projects.get(project_idx) {
+        Some(p) if p.target_date.is_some() || !p.milestones.is_empty() => 13,
+        _ => 10,
+    };
+    if !app.config.fields.projects.is_empty() {
+        info_height += 3;
+    }
+    // Notes get up to 12 rows and scroll past that
+    let notes_height = app
+        .project_details_notes
+        .as_ref()
+        .map_or(3, |notes| (notes.lines().count() as u16 + 2).clamp(3, 12));
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .margin(1)
         .constraints([
-            Constraint::Length(10), // Project info
+            Constraint::Length(info_height),  // Project info
+            Constraint::Length(notes_height), // Notes
             Constraint::Min(10),    // Log list
             Constraint::Length(3),  // Help bar
         ])
@@ -23,33 +37,101 @@ pub fn render(frame: &mut Frame, app: &mut App, area: Rect, project_idx: usize)
     if let Some(project) = app.projects.get(project_idx) {
         render_project_info(frame, app, project, chunks[0]);
     }
+    render_notes(frame, app, chunks[1]);
 
-    // Render log list
-    render_log_list(frame, app, chunks[1]);
+    // Render log list, with the status history beside it
+    let history = app.projects.get(project_idx).map(|p| p.status_history.clone()).unwrap_or_default();
+    if history.is_empty() {
+        render_details_tab(frame, app, chunks[2]);
+    } else {
+        let columns = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(65), Constraint::Percentage(35)])
+            .split(chunks[2]);
+        render_details_tab(frame, app, columns[0]);
+        render_status_history(frame, app, &history, columns[1]);
+    }
 
     // Help bar
-    let help_text = vec![
-        Span::styled("↑↓", Style::default().fg(Color::Yellow)),
-        Span::raw(" Navigate  "),
-        Span::styled("Enter", Style::default().fg(Color::Yellow)),
-        Span::raw(" View log  "),
+    let mut help_text = details_tab_help(app);
+    help_text.extend([
         Span::styled("e", Style::default().fg(Color::Yellow)),
-        Span::raw(" Edit project  "),
+        Span::raw(" Edit  "),
+        Span::styled("n/N", Style::default().fg(Color::Yellow)),
+        Span::raw(" Notes  "),
+        Span::styled("PgUp/PgDn", Style::default().fg(Color::Yellow)),
+        Span::raw(" Scroll notes  "),
+        Span::styled("a", Style::default().fg(Color::Yellow)),
+        Span::raw(if app.projects.get(project_idx).is_some_and(|p| p.archived) { " Restore  " } else { " Archive  " }),
+        Span::styled("d", Style::default().fg(Color::Yellow)),
+        Span::raw(" Delete  "),
         Span::styled("ESC", Style::default().fg(Color::Yellow)),
         Span::raw(" Back"),
-    ];
+    ]);
     let help = Paragraph::new(Line::from(help_text))
         .block(Block::default().borders(Borders::ALL));
-    frame.render_widget(help, chunks[2]);
+    frame.render_widget(help, chunks[3]);
+
+    if app.delete_confirm.is_some() {
+        render_delete_confirm(frame, app, area, "project");
+    }
+}
+
+/// Ask to confirm deleting a project or person, warning about tags left in logs and todos
+pub fn render_delete_confirm(frame: &mut Frame, app: &App, area: Rect, kind: &str) {
+    let Some(confirm) = &app.delete_confirm else {
+        return;
+    };
+
+    let popup_width = 60u16.min(area.width);
+    let popup_height = 7u16;
+    let popup_area = Rect::new(
+        area.x + (area.width.saturating_sub(popup_width)) / 2,
+        area.y + (area.height.saturating_sub(popup_height)) / 2,
+        popup_width,
+        popup_height,
+    );
+    frame.render_widget(Clear, popup_area);
+
+    let references = if confirm.logs == 0 && confirm.todos == 0 {
+        Line::from("Nothing references it.")
+    } else {
+        Line::from(Span::styled(
+            format!("Still referenced by {} log(s) and {} todo(s).", confirm.logs, confirm.todos),
+            Style::default().fg(Color::Red),
+        ))
+    };
+    let lines = vec![
+        Line::from(format!("Delete {} '{}'?", kind, confirm.name)),
+        references,
+        Line::from("Consider archiving it instead (a)."),
+        Line::from(""),
+        Line::from(vec![
+            Span::styled("y", Style::default().fg(Color::Yellow)),
+            Span::raw(" Delete  "),
+            Span::styled("any other key", Style::default().fg(Color::Yellow)),
+            Span::raw(" Cancel"),
+        ]),
+    ];
+    let paragraph = Paragraph::new(lines).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title("Confirm delete")
+            .border_style(Style::default().fg(Color::Red)),
+    );
+    frame.render_widget(paragraph, popup_area);
+    app.register_click(area, ClickTarget::Blocker);
 }
 
 fn render_project_info(frame: &mut Frame, app: &App, project: &crate::models::Project, area: Rect) {
+    let fields_height = if app.config.fields.projects.is_empty() { 0 } else { 3 };
     let info_chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
-            Constraint::Length(3), // Name/status and Group (side by side)
-            Constraint::Length(3), // Jira
-            Constraint::Min(3),    // Description
+            Constraint::Length(3),             // Name/status and Group (side by side)
+            Constraint::Length(3),             // Jira
+            Constraint::Length(fields_height), // Custom fields
+            Constraint::Min(3),                // Description and timeline
         ])
         .split(area);
 
@@ -71,38 +153,302 @@ fn render_project_info(frame: &mut Frame, app: &App, project: &crate::models::Pr
             format!("[{}]", project.status),
             Style::default().fg(status_color),
         ),
+        Span::styled(if project.archived { " (archived)" } else { "" }, Style::default().fg(Color::DarkGray)),
     ]);
     let name = Paragraph::new(name_line)
         .block(Block::default().borders(Borders::ALL).title("Project"));
     frame.render_widget(name, top_row[0]);
 
-    // Group
-    let group_text = if project.group.is_empty() {
+    // Group, plus the parent for sub-projects
+    let mut group_text = if project.group.is_empty() {
         "(no group)".to_string()
     } else {
         project.group.clone()
     };
+    if let Some(parent) = project.parent_name() {
+        group_text = format!("{} · ↑ {}", group_text, parent);
+    }
     let group = Paragraph::new(group_text)
         .block(Block::default().borders(Borders::ALL).title("Group"));
     frame.render_widget(group, top_row[1]);
 
     // Jira
-    let jira_text = project.jira.as_ref()
-        .map(|j| j.as_str())
+    let jira_text = project.jira.as_deref()
         .unwrap_or("(no jira link)");
     let jira = Paragraph::new(jira_text)
         .style(Style::default().fg(Color::Blue))
-        .block(Block::default().borders(Borders::ALL).title("Jira"));
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title("Jira")
+                .title_top(Line::from(format!(" Open todos (incl. sub-projects): {} ", app.project_details_open_todos)).right_aligned()),
+        );
     frame.render_widget(jira, info_chunks[1]);
 
+    if fields_height > 0 {
+        super::custom_fields::render_values(frame, &app.config.fields.projects, &project.fields, info_chunks[2]);
+    }
+
     // Description
-    let desc_text = project.description.as_ref()
-        .map(|d| d.as_str())
+    let desc_text = project.description.as_deref()
         .unwrap_or("(no description)");
     let description = Paragraph::new(desc_text)
         .block(Block::default().borders(Borders::ALL).title("Description"))
         .wrap(ratatui::widgets::Wrap { trim: false });
-    frame.render_widget(description, info_chunks[2]);
+
+    if project.target_date.is_none() && project.milestones.is_empty() {
+        frame.render_widget(description, info_chunks[3]);
+    } else {
+        let bottom_row = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+            .split(info_chunks[3]);
+        frame.render_widget(description, bottom_row[0]);
+        render_timeline(frame, project, bottom_row[1]);
+    }
+}
+
+/// The project's Markdown notes, lightly styled
+fn render_notes(frame: &mut Frame, app: &mut App, area: Rect) {
+    let lines = match &app.project_details_notes {
+        Some(notes) => markdown_lines(notes),
+        None => vec![Line::from(Span::styled("(no notes, press n to write some)", Style::default().fg(Color::DarkGray)))],
+    };
+
+    // Keep the scroll within the text
+    let max_scroll = (lines.len() as u16).saturating_sub(area.height.saturating_sub(2));
+    app.project_details_notes_scroll = app.project_details_notes_scroll.min(max_scroll);
+
+    let title = if max_scroll > 0 {
+        format!("Notes ({}/{})", app.project_details_notes_scroll + 1, max_scroll + 1)
+    } else {
+        "Notes".to_string()
+    };
+    let notes = Paragraph::new(lines)
+        .block(Block::default().borders(Borders::ALL).title(title))
+        .scroll((app.project_details_notes_scroll, 0));
+    frame.render_widget(notes, area);
+}
+
+/// Style headings, list items, quotes and code blocks; everything else is shown as written
+fn markdown_lines(text: &str) -> Vec<Line<'static>> {
+    let mut lines = Vec::new();
+    let mut in_code = false;
+    for line in text.lines() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") {
+            in_code = !in_code;
+            lines.push(Line::from(Span::styled(line.to_string(), Style::default().fg(Color::DarkGray))));
+        } else if in_code {
+            lines.push(Line::from(Span::styled(line.to_string(), Style::default().fg(Color::Yellow))));
+        } else if let Some(heading) = trimmed.strip_prefix('#').map(|h| h.trim_start_matches('#')).filter(|h| h.starts_with(' ')) {
+            lines.push(Line::from(Span::styled(
+                heading.trim().to_string(),
+                Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD),
+            )));
+        } else if let Some(item) = trimmed.strip_prefix("- ").or_else(|| trimmed.strip_prefix("* ")) {
+            let indent = &line[..line.len() - trimmed.len()];
+            lines.push(Line::from(vec![
+                Span::raw(format!("{}• ", indent)),
+                Span::raw(item.to_string()),
+            ]));
+        } else if let Some(quote) = trimmed.strip_prefix('>') {
+            lines.push(Line::from(Span::styled(
+                format!("│ {}", quote.trim_start()),
+                Style::default().fg(Color::Gray).add_modifier(Modifier::ITALIC),
+            )));
+        } else {
+            lines.push(Line::from(line.to_string()));
+        }
+    }
+    lines
+}
+
+/// Target date plus open milestones, overdue ones first
+fn render_timeline(frame: &mut Frame, project: &crate::models::Project, area: Rect) {
+    let today = chrono::Local::now().date_naive();
+    let due_span = |date: chrono::NaiveDate| {
+        let days = (date - today).num_days();
+        match days {
+            d if d < 0 => Span::styled(format!("{} days overdue", -d), Style::default().fg(Color::Red)),
+            0 => Span::styled("today", Style::default().fg(Color::Yellow)),
+            d => Span::styled(format!("in {} days", d), Style::default().fg(Color::Green)),
+        }
+    };
+
+    let mut lines = Vec::new();
+    if let Some(target) = project.target_date {
+        lines.push(Line::from(vec![
+            Span::styled("Target ", Style::default().add_modifier(Modifier::BOLD)),
+            Span::raw(format!("{}  ", target.format("%Y-%m-%d"))),
+            due_span(target),
+        ]));
+    }
+
+    let mut open: Vec<_> = project.milestones.iter().filter(|m| !m.done).collect();
+    open.sort_by_key(|m| m.date);
+    for milestone in &open {
+        let marker = if milestone.date < today { "! " } else { "◇ " };
+        lines.push(Line::from(vec![
+            Span::raw(marker),
+            Span::raw(format!("{}  {}  ", milestone.date.format("%Y-%m-%d"), milestone.name)),
+            due_span(milestone.date),
+        ]));
+    }
+
+    let done = project.milestones.len() - open.len();
+    let title = format!("Milestones ({}/{} done)", done, project.milestones.len());
+    let timeline = Paragraph::new(lines).block(Block::default().borders(Borders::ALL).title(title));
+    frame.render_widget(timeline, area);
+}
+
+/// Status changes, newest first
+fn render_status_history(frame: &mut Frame, app: &App, history: &[crate::models::StatusChange], area: Rect) {
+    let mut lines = Vec::new();
+    for change in history.iter().rev() {
+        let mut spans = vec![Span::styled(
+            change.at.format("%Y-%m-%d %H:%M  ").to_string(),
+            Style::default().fg(Color::DarkGray),
+        )];
+        if change.from.is_empty() {
+            spans.push(Span::raw("created as "));
+        } else {
+            spans.push(Span::styled(change.from.clone(), Style::default().fg(app.config.get_state_color(&change.from))));
+            spans.push(Span::raw(" → "));
+        }
+        spans.push(Span::styled(change.to.clone(), Style::default().fg(app.config.get_state_color(&change.to))));
+        lines.push(Line::from(spans));
+        if let Some(reason) = &change.reason {
+            lines.push(Line::from(Span::styled(
+                format!("    {}", reason),
+                Style::default().add_modifier(Modifier::ITALIC),
+            )));
+        }
+    }
+
+    let timeline = Paragraph::new(lines)
+        .block(Block::default().borders(Borders::ALL).title("Status history"))
+        .wrap(ratatui::widgets::Wrap { trim: false });
+    frame.render_widget(timeline, area);
+}
+
+fn render_details_tab(frame: &mut Frame, app: &mut App, area: Rect) {
+    if app.details_tab == DetailsTab::Logs {
+        render_log_list(frame, app, area);
+    } else {
+        render_todo_tab(frame, app, area);
+    }
+}
+
+/// Help for the current details tab: Tab to switch, then what the list does
+pub fn details_tab_help(app: &App) -> Vec<Span<'static>> {
+    let mut help = vec![
+        Span::styled("Tab", Style::default().fg(Color::Yellow)),
+        Span::raw(" Logs/todos  "),
+        Span::styled("↑↓", Style::default().fg(Color::Yellow)),
+        Span::raw(" Navigate  "),
+        Span::styled("Enter", Style::default().fg(Color::Yellow)),
+        Span::raw(" View log  "),
+    ];
+    if app.details_tab != DetailsTab::Logs {
+        help.push(Span::styled("x", Style::default().fg(Color::Yellow)));
+        help.push(Span::raw(" Toggle  "));
+    }
+    help
+}
+
+/// The tab names as a block title, the current one highlighted.
+/// Registers each name as a click target on the block's top border.
+pub fn details_tabs_title(app: &App, area: Rect, logs: usize) -> Line<'static> {
+    let open = app.details_todos.iter().filter(|t| !t.completed).count();
+    let labels = [
+        format!(" Logs ({}) ", logs),
+        format!(" Open todos ({}) ", open),
+        format!(" Completed todos ({}) ", app.details_todos.len() - open),
+    ];
+
+    let mut spans = Vec::new();
+    let mut x = area.x + 1;
+    for (i, (tab, label)) in DetailsTab::ALL.iter().zip(labels).enumerate() {
+        if i > 0 {
+            spans.push(Span::raw("│"));
+            x += 1;
+        }
+        let width = label.chars().count() as u16;
+        app.register_click(Rect::new(x, area.y, width, 1).intersection(area), ClickTarget::Row(ClickList::DetailsTabs, i));
+        x += width;
+        let style = if *tab == app.details_tab {
+            Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD | Modifier::REVERSED)
+        } else {
+            Style::default().fg(Color::Gray)
+        };
+        spans.push(Span::styled(label, style));
+    }
+    Line::from(spans)
+}
+
+/// Open or completed todos of the shown project or person
+pub fn render_todo_tab(frame: &mut Frame, app: &mut App, area: Rect) {
+    let logs = match app.screen {
+        Screen::PersonDetails(_) => app.person_details_logs.len(),
+        _ => app.project_details_logs.len(),
+    };
+    let todos = app.details_tab_todos();
+    let count = todos.len();
+    let items: Vec<ListItem> = todos
+        .iter()
+        .enumerate()
+        .map(|(i, (_, todo))| {
+            let text_style = match (i == app.details_todo_selected, todo.completed) {
+                (true, true) => Style::default().bg(Color::DarkGray).fg(Color::Gray).add_modifier(Modifier::CROSSED_OUT),
+                (true, false) => Style::default().bg(Color::DarkGray).fg(Color::White),
+                (false, true) => Style::default().fg(Color::Gray).add_modifier(Modifier::CROSSED_OUT),
+                (false, false) => Style::default(),
+            };
+            let mut spans = vec![
+                Span::styled(todo.state.marker(), super::todo_list::marker_style(todo.state)),
+                Span::raw(" "),
+                Span::styled(todo.text.clone(), text_style),
+            ];
+            for project in &todo.projects {
+                spans.push(Span::styled(format!(" #{}", project), Style::default().fg(Color::Green)));
+            }
+            for person in &todo.people {
+                spans.push(Span::styled(format!(" @{}", person), Style::default().fg(Color::Blue)));
+            }
+            ListItem::new(Line::from(spans))
+        })
+        .collect();
+
+    let empty = if app.details_tab == DetailsTab::OpenTodos { "(no open todos)" } else { "(no completed todos)" };
+    let list = if items.is_empty() {
+        List::new(vec![ListItem::new(Span::styled(empty, Style::default().fg(Color::DarkGray)))])
+    } else {
+        List::new(items)
+    };
+
+    let mut state = ListState::default();
+    if count > 0 {
+        state.select(Some(app.details_todo_selected));
+    }
+    let title = details_tabs_title(app, area, logs);
+    frame.render_stateful_widget(
+        list.block(Block::default().borders(Borders::ALL).title(title))
+            .highlight_style(Style::default().add_modifier(Modifier::BOLD)),
+        area,
+        &mut state,
+    );
+
+    // Rows select the todo, the checkbox at the start of each row toggles it
+    let inner = area.inner(ratatui::layout::Margin { vertical: 1, horizontal: 1 });
+    app.register_list_rows(inner, ClickList::DetailsTodos, state.offset(), count);
+    for row in 0..inner.height {
+        let idx = state.offset() + row as usize;
+        if idx >= count {
+            break;
+        }
+        app.register_click(Rect::new(inner.x, inner.y + row, 3, 1), ClickTarget::TodoCheckbox(idx));
+    }
 }
 
 fn render_log_list(frame: &mut Frame, app: &mut App, area: Rect) {
@@ -144,9 +490,12 @@ fn render_log_list(frame: &mut Frame, app: &mut App, area: Rect) {
     let mut state = ListState::default();
     state.select(Some(app.project_details_log_selected));
 
+    let title = details_tabs_title(app, area, app.project_details_logs.len());
     let list = List::new(items)
-        .block(Block::default().borders(Borders::ALL).title(format!("Log Entries ({} total)", app.project_details_logs.len())))
+        .block(Block::default().borders(Borders::ALL).title(title))
         .highlight_style(Style::default().add_modifier(Modifier::BOLD));
 
     frame.render_stateful_widget(list, area, &mut state);
+    let inner = area.inner(ratatui::layout::Margin { vertical: 1, horizontal: 1 });
+    app.register_list_rows(inner, ClickList::ProjectDetailsLogs, state.offset(), app.project_details_logs.len());
 }
@@ -6,7 +6,9 @@ use ratatui::{
     Frame,
 };
 
-use super::app::App;
+use super::app::{App, ClickList, ClickTarget};
+use super::field_block;
+use crate::validation::Field;
 
 pub fn render(frame: &mut Frame, app: &App, area: Rect) {
     let chunks = Layout::default()
@@ -19,7 +21,10 @@ pub fn render(frame: &mut Frame, app: &App, area: Rect) {
             Constraint::Length(3),  // Jira field
             Constraint::Length(3),  // Status field
             Constraint::Length(3),  // Group field
-            Constraint::Min(1),     // Spacer
+            Constraint::Length(3),  // Parent field
+            Constraint::Length(3),  // Target date field
+            Constraint::Min(4),     // Milestones
+            Constraint::Length(super::custom_fields::editor_height(app)), // Custom fields
             Constraint::Length(3),  // Help bar
         ])
         .split(area);
@@ -42,7 +47,7 @@ pub fn render(frame: &mut Frame, app: &App, area: Rect) {
     };
     let name_field = Paragraph::new(app.project_edit_name.as_str())
         .style(name_style)
-        .block(Block::default().borders(Borders::ALL).title("[1] Name *"));
+        .block(field_block("[1] Name *", app.edit_error(Field::Name)));
     frame.render_widget(name_field, chunks[1]);
 
     // Description field (field 1)
@@ -64,7 +69,7 @@ pub fn render(frame: &mut Frame, app: &App, area: Rect) {
     };
     let jira_field = Paragraph::new(app.project_edit_jira.as_str())
         .style(jira_style)
-        .block(Block::default().borders(Borders::ALL).title("[3] Jira URL"));
+        .block(field_block("[3] Jira URL", app.edit_error(Field::Jira)));
     frame.render_widget(jira_field, chunks[3]);
 
     // Status field (field 3) - dropdown
@@ -81,7 +86,7 @@ pub fn render(frame: &mut Frame, app: &App, area: Rect) {
     let status_text = format!("{} {}", status_display, if app.project_edit_field == 3 { "▼" } else { "" });
     let status_field = Paragraph::new(status_text)
         .style(status_style)
-        .block(Block::default().borders(Borders::ALL).title("[4] Status (↑↓ to select, Enter to confirm)"));
+        .block(field_block("[4] Status (↑↓ to select, Enter to confirm)", app.edit_error(Field::Status)));
     frame.render_widget(status_field, chunks[4]);
 
     // Group field (field 4) - dropdown
@@ -98,14 +103,37 @@ pub fn render(frame: &mut Frame, app: &App, area: Rect) {
     let group_text = format!("{} {}", group_display, if app.project_edit_field == 4 { "▼" } else { "" });
     let group_field = Paragraph::new(group_text)
         .style(group_style)
-        .block(Block::default().borders(Borders::ALL).title("[5] Group (↑↓ to select, Enter to confirm)"));
+        .block(field_block("[5] Group (↑↓ to select, Enter to confirm)", app.edit_error(Field::Group)));
     frame.render_widget(group_field, chunks[5]);
 
+    // Parent field (field 5)
+    let parent_style = if app.project_edit_field == 5 {
+        Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)
+    } else {
+        Style::default()
+    };
+    let parent_field = Paragraph::new(app.project_edit_parent.as_str())
+        .style(parent_style)
+        .block(field_block("[6] Parent project (empty = derived from a/b name)", app.edit_error(Field::Parent)));
+    frame.render_widget(parent_field, chunks[6]);
+
+    // Target date field (field 6)
+    let target_style = if app.project_edit_field == 6 {
+        Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)
+    } else {
+        Style::default()
+    };
+    let target_field = Paragraph::new(app.project_edit_target_date.as_str())
+        .style(target_style)
+        .block(field_block("[7] Target date (YYYY-MM-DD)", app.edit_error(Field::TargetDate)));
+    frame.render_widget(target_field, chunks[7]);
+
+
     // Help bar
     let help_text = vec![
         Span::styled("Tab", Style::default().fg(Color::Yellow)),
         Span::raw(" Next field  "),
-        Span::styled("1-5", Style::default().fg(Color::Yellow)),
+        Span::styled("1-9", Style::default().fg(Color::Yellow)),
         Span::raw(" Jump to field  "),
         Span::styled("Ctrl+S", Style::default().fg(Color::Yellow)),
         Span::raw(" Save  "),
@@ -114,14 +142,16 @@ pub fn render(frame: &mut Frame, app: &App, area: Rect) {
     ];
     let help = Paragraph::new(Line::from(help_text))
         .block(Block::default().borders(Borders::ALL));
-    frame.render_widget(help, chunks[7]);
+    frame.render_widget(help, chunks[10]);
 
     // Set cursor position for active field (only for text input fields, not dropdowns)
-    if app.project_edit_field < 3 {
+    if app.project_edit_field < 3 || app.project_edit_field == 5 || app.project_edit_field == 6 {
         let (field_area, input) = match app.project_edit_field {
             0 => (chunks[1], &app.project_edit_name),
             1 => (chunks[2], &app.project_edit_description),
             2 => (chunks[3], &app.project_edit_jira),
+            5 => (chunks[6], &app.project_edit_parent),
+            6 => (chunks[7], &app.project_edit_target_date),
             _ => (chunks[1], &app.project_edit_name),
         };
 
@@ -132,6 +162,16 @@ pub fn render(frame: &mut Frame, app: &App, area: Rect) {
         ));
     }
 
+    for (i, chunk) in chunks[1..=9].iter().enumerate() {
+        app.register_click(*chunk, ClickTarget::Row(ClickList::ProjectEditFields, i));
+    }
+
+    // Lists go after the field click areas so their rows take clicks
+    // Milestones (field 7)
+    render_milestones(frame, app, chunks[8]);
+    // Custom fields (field 8)
+    super::custom_fields::render_editor(frame, app, chunks[9], app.project_edit_field == 8, 9);
+
     // Render dropdowns if open
     if app.project_edit_field == 3 && app.project_edit_status_dropdown_open {
         render_status_dropdown(frame, app, chunks[4]);
@@ -139,10 +179,134 @@ pub fn render(frame: &mut Frame, app: &App, area: Rect) {
     if app.project_edit_field == 4 && app.project_edit_group_dropdown_open {
         render_group_dropdown(frame, app, chunks[5]);
     }
+    if app.project_edit_milestone_input.is_some() {
+        render_milestone_input(frame, app, area);
+    }
+    if app.project_edit_status_reason.is_some() {
+        render_status_reason_input(frame, app, area);
+    }
+    if app.custom_field_input.is_some() {
+        super::custom_fields::render_input(frame, app, area);
+    }
+}
+
+fn render_status_reason_input(frame: &mut Frame, app: &App, area: Rect) {
+    let input = app.project_edit_status_reason.as_deref().unwrap_or_default();
+    let popup_width = 60u16.min(area.width);
+    let popup_area = Rect::new(
+        area.x + (area.width.saturating_sub(popup_width)) / 2,
+        area.y + (area.height.saturating_sub(3)) / 2,
+        popup_width,
+        3,
+    );
+
+    frame.render_widget(Clear, popup_area);
+    let original = match app.screen {
+        super::app::Screen::ProjectEdit(Some(idx)) => app.projects.get(idx).map(|p| p.status.as_str()).unwrap_or_default(),
+        _ => "",
+    };
+    let requirement = if app.config.transition_requires_comment(original, &app.project_edit_status) {
+        "required"
+    } else {
+        "optional"
+    };
+    let title = format!("Status → {}: comment ({}, Enter save, ESC back)", app.project_edit_status, requirement);
+    let paragraph = Paragraph::new(input).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(title)
+            .border_style(Style::default().fg(Color::Yellow)),
+    );
+    frame.render_widget(paragraph, popup_area);
+    app.register_click(area, ClickTarget::Blocker);
+
+    frame.set_cursor_position((popup_area.x + 1 + input.chars().count() as u16, popup_area.y + 1));
+}
+
+fn render_milestones(frame: &mut Frame, app: &App, area: Rect) {
+    let focused = app.project_edit_field == 7;
+    let today = chrono::Local::now().date_naive();
+
+    let items: Vec<ListItem> = if app.project_edit_milestones.is_empty() {
+        vec![ListItem::new(Span::styled("(no milestones)", Style::default().fg(Color::DarkGray)))]
+    } else {
+        app.project_edit_milestones
+            .iter()
+            .enumerate()
+            .map(|(i, milestone)| {
+                let color = if milestone.done {
+                    Color::Green
+                } else if milestone.date < today {
+                    Color::Red
+                } else {
+                    Color::White
+                };
+                let style = if focused && i == app.project_edit_milestone_selected {
+                    Style::default().bg(Color::DarkGray).fg(color)
+                } else {
+                    Style::default().fg(color)
+                };
+                let checkbox = if milestone.done { "[x]" } else { "[ ]" };
+                ListItem::new(Line::from(Span::styled(
+                    format!("{} {}  {}", checkbox, milestone.date.format("%Y-%m-%d"), milestone.name),
+                    style,
+                )))
+            })
+            .collect()
+    };
+
+    let title = if focused {
+        "[8] Milestones (a add, Enter edit, Space done, d delete)"
+    } else {
+        "[8] Milestones"
+    };
+    let border_style = if focused {
+        Style::default().fg(Color::Yellow)
+    } else {
+        Style::default()
+    };
+
+    let mut state = ListState::default();
+    if focused && !app.project_edit_milestones.is_empty() {
+        state.select(Some(app.project_edit_milestone_selected));
+    }
+    let list = List::new(items).block(Block::default().borders(Borders::ALL).title(title).border_style(border_style));
+    frame.render_stateful_widget(list, area, &mut state);
+
+    let inner = area.inner(ratatui::layout::Margin { vertical: 1, horizontal: 1 });
+    app.register_list_rows(inner, ClickList::ProjectEditMilestones, state.offset(), app.project_edit_milestones.len());
+}
+
+fn render_milestone_input(frame: &mut Frame, app: &App, area: Rect) {
+    let input = app.project_edit_milestone_input.as_deref().unwrap_or_default();
+    let popup_width = 60u16.min(area.width);
+    let popup_area = Rect::new(
+        area.x + (area.width.saturating_sub(popup_width)) / 2,
+        area.y + (area.height.saturating_sub(3)) / 2,
+        popup_width,
+        3,
+    );
+
+    frame.render_widget(Clear, popup_area);
+    let title = if app.project_edit_milestone_editing.is_some() {
+        "Edit milestone: YYYY-MM-DD name (Enter save, ESC cancel)"
+    } else {
+        "New milestone: YYYY-MM-DD name (Enter add, ESC cancel)"
+    };
+    let paragraph = Paragraph::new(input).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(title)
+            .border_style(Style::default().fg(Color::Yellow)),
+    );
+    frame.render_widget(paragraph, popup_area);
+    app.register_click(area, ClickTarget::Blocker);
+
+    frame.set_cursor_position((popup_area.x + 1 + input.chars().count() as u16, popup_area.y + 1));
 }
 
 fn render_status_dropdown(frame: &mut Frame, app: &App, field_area: Rect) {
-    let states = app.config.allowed_state_names();
+    let states = app.project_edit_status_options();
     if states.is_empty() {
         return;
     }
@@ -184,6 +348,9 @@ fn render_status_dropdown(frame: &mut Frame, app: &App, field_area: Rect) {
         .highlight_style(Style::default().add_modifier(Modifier::BOLD));
 
     frame.render_stateful_widget(list, dropdown_area, &mut state);
+
+    let inner = dropdown_area.inner(ratatui::layout::Margin { vertical: 1, horizontal: 1 });
+    app.register_list_rows(inner, ClickList::ProjectEditStatus, state.offset(), states.len());
 }
 
 fn render_group_dropdown(frame: &mut Frame, app: &App, field_area: Rect) {
@@ -231,4 +398,7 @@ fn render_group_dropdown(frame: &mut Frame, app: &App, field_area: Rect) {
         .highlight_style(Style::default().add_modifier(Modifier::BOLD));
 
     frame.render_stateful_widget(list, dropdown_area, &mut state);
+
+    let inner = dropdown_area.inner(ratatui::layout::Margin { vertical: 1, horizontal: 1 });
+    app.register_list_rows(inner, ClickList::ProjectEditGroup, state.offset(), groups.len());
 }
@@ -6,7 +6,7 @@ use ratatui::{
     Frame,
 };
 
-use super::app::{App, ProjectFilterPanel};
+use super::app::{App, ClickList, ClickTarget, ProjectFilterPanel, ProjectStats};
 
 pub fn render(frame: &mut Frame, app: &mut App, area: Rect) {
     let chunks = Layout::default()
@@ -21,7 +21,13 @@ pub fn render(frame: &mut Frame, app: &mut App, area: Rect) {
         .split(area);
 
     // Title
-    let title = Paragraph::new(format!("Projects ({} total)", app.filtered_projects.len()))
+    let archived_note = if app.project_filter.show_archived { ", archived shown" } else { "" };
+    let title = Paragraph::new(format!(
+        "Projects ({} total, sorted by {}{})",
+        app.filtered_projects.len(),
+        app.project_sort.label(),
+        archived_note
+    ))
         .style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD))
         .block(Block::default().borders(Borders::ALL));
     frame.render_widget(title, chunks[0]);
@@ -42,10 +48,30 @@ pub fn render(frame: &mut Frame, app: &mut App, area: Rect) {
             Span::styled("n", Style::default().fg(Color::Yellow)),
             Span::raw(" New  "),
             Span::styled("g", Style::default().fg(Color::Yellow)),
-            Span::raw(" Filter groups  "),
+            Span::raw(" Groups  "),
+            Span::styled("t", Style::default().fg(Color::Yellow)),
+            Span::raw(" Status  "),
+            Span::styled("/", Style::default().fg(Color::Yellow)),
+            Span::raw(" Search  "),
+            Span::styled("f", Style::default().fg(Color::Yellow)),
+            Span::raw(" Fields  "),
+            Span::styled("s", Style::default().fg(Color::Yellow)),
+            Span::raw(" Sort  "),
+            Span::styled("d", Style::default().fg(Color::Yellow)),
+            Span::raw(" Sort by deadline  "),
+            Span::styled("A", Style::default().fg(Color::Yellow)),
+            Span::raw(" Show archived  "),
             Span::styled("ESC", Style::default().fg(Color::Yellow)),
             Span::raw(" Back"),
         ]
+    } else if app.project_filter_panel == ProjectFilterPanel::Search {
+        vec![
+            Span::raw("Type to filter by name or description  "),
+            Span::styled("Enter", Style::default().fg(Color::Yellow)),
+            Span::raw(" Keep  "),
+            Span::styled("ESC", Style::default().fg(Color::Yellow)),
+            Span::raw(" Clear"),
+        ]
     } else {
         vec![
             Span::styled("ESC", Style::default().fg(Color::Yellow)),
@@ -60,23 +86,79 @@ pub fn render(frame: &mut Frame, app: &mut App, area: Rect) {
     if app.project_filter_panel == ProjectFilterPanel::Groups {
         render_group_filter_popup(frame, app, area);
     }
+    if app.project_filter_panel == ProjectFilterPanel::Statuses {
+        render_status_filter_popup(frame, app, area);
+    }
+    if app.project_filter_panel == ProjectFilterPanel::Fields {
+        super::custom_fields::render_facet_popup(
+            frame,
+            app,
+            area,
+            &app.project_field_facets(),
+            &app.project_filter.fields,
+            app.project_filter_field_selected,
+            ClickList::ProjectFilterFields,
+        );
+    }
 }
 
 fn render_filter_summary(frame: &mut Frame, app: &App, area: Rect) {
-    let groups_text = if app.project_filter_groups.is_empty() {
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage(27),
+            Constraint::Percentage(27),
+            Constraint::Percentage(23),
+            Constraint::Percentage(23),
+        ])
+        .split(area);
+
+    let groups_text = if app.project_filter.groups.is_empty() {
         "[g] All groups".to_string()
     } else {
-        format!("[g] Groups: {}", app.project_filter_groups.join(", "))
+        format!("[g] Groups: {}", app.project_filter.groups.join(", "))
+    };
+    let statuses_text = match &app.project_filter.statuses {
+        None => "[t] Active statuses".to_string(),
+        Some(statuses) if statuses.is_empty() => "[t] No statuses".to_string(),
+        Some(statuses) => format!("[t] Status: {}", statuses.join(", ")),
     };
-    let groups_style = if app.project_filter_panel == ProjectFilterPanel::Groups {
-        Style::default().fg(Color::Yellow)
+    let search_text = if app.project_filter.text.is_empty() && app.project_filter_panel != ProjectFilterPanel::Search {
+        "[/] Search".to_string()
     } else {
-        Style::default()
+        format!("[/] {}", app.project_filter.text)
     };
-    let groups = Paragraph::new(groups_text)
-        .style(groups_style)
-        .block(Block::default().borders(Borders::ALL).title("Filter"));
-    frame.render_widget(groups, area);
+
+    let fields_text = match super::custom_fields::facet_summary(&app.config.fields.projects, &app.project_filter.fields) {
+        Some(summary) => format!("[f] {}", summary),
+        None => "[f] Fields".to_string(),
+    };
+
+    let panels = [
+        (groups_text, ProjectFilterPanel::Groups, "Filter"),
+        (statuses_text, ProjectFilterPanel::Statuses, ""),
+        (search_text, ProjectFilterPanel::Search, ""),
+        (fields_text, ProjectFilterPanel::Fields, ""),
+    ];
+    for (i, (text, panel, title)) in panels.into_iter().enumerate() {
+        let style = if app.project_filter_panel == panel {
+            Style::default().fg(Color::Yellow)
+        } else {
+            Style::default()
+        };
+        let paragraph = Paragraph::new(text)
+            .style(style)
+            .block(Block::default().borders(Borders::ALL).title(title));
+        frame.render_widget(paragraph, columns[i]);
+        app.register_click(columns[i], ClickTarget::Row(ClickList::ProjectFilterPanels, i));
+    }
+
+    if app.project_filter_panel == ProjectFilterPanel::Search {
+        frame.set_cursor_position((
+            columns[2].x + 5 + app.project_filter.text.chars().count() as u16,
+            columns[2].y + 1,
+        ));
+    }
 }
 
 fn render_group_filter_popup(frame: &mut Frame, app: &App, area: Rect) {
@@ -95,7 +177,7 @@ fn render_group_filter_popup(frame: &mut Frame, app: &App, area: Rect) {
         .map(|(i, group_name)| {
             // Convert "(No group)" to empty string for comparison
             let group_for_check = if group_name == "(No group)" { "" } else { group_name.as_str() };
-            let selected = app.project_filter_groups.contains(&group_for_check.to_string());
+            let selected = app.project_filter.groups.contains(&group_for_check.to_string());
             let checkbox = if selected { "[x]" } else { "[ ]" };
             let style = if i == app.project_filter_group_selected {
                 Style::default().bg(Color::DarkGray).fg(Color::White)
@@ -125,6 +207,9 @@ fn render_group_filter_popup(frame: &mut Frame, app: &App, area: Rect) {
         .highlight_style(Style::default().add_modifier(Modifier::BOLD));
 
     frame.render_stateful_widget(list, popup_area, &mut state);
+    app.register_click(area, ClickTarget::Blocker);
+    let inner = popup_area.inner(ratatui::layout::Margin { vertical: 1, horizontal: 1 });
+    app.register_list_rows(inner, ClickList::ProjectFilterGroups, state.offset(), all_groups.len());
 }
 
 fn render_project_list(frame: &mut Frame, app: &mut App, area: Rect) {
@@ -159,11 +244,31 @@ fn render_project_list(frame: &mut Frame, app: &mut App, area: Rect) {
     // Calculate available width for description
     // Account for: indent(2) + bullet(2) + separator(3) + space(1) + status(10) + borders(2) + padding(2)
     let available_width = area.width.saturating_sub(2); // Account for block borders
-    let fixed_overhead = 20; // indent + bullet + separator + space + status + padding
+    let fixed_overhead = 70; // indent + bullet + separator + space + status + activity + deadline + padding
+    let today = chrono::Local::now().date_naive();
+
+    // Sub-projects are indented under their parent, showing only the last part of `a/b` names
+    let tree_names: Vec<String> = app
+        .filtered_projects
+        .iter()
+        .enumerate()
+        .map(|(i, p)| {
+            let depth = app.filtered_project_depths.get(i).copied().unwrap_or(0);
+            let name = match p.parent_name() {
+                Some(parent) if depth > 0 => p.name.strip_prefix(&format!("{}/", parent)).unwrap_or(&p.name).to_string(),
+                _ => p.name.clone(),
+            };
+            if depth > 0 {
+                format!("{}└ {}", "  ".repeat(depth - 1), name)
+            } else {
+                name
+            }
+        })
+        .collect();
 
     // Find the longest project name to determine name column width
-    let max_name_len = app.filtered_projects.iter()
-        .map(|p| p.name.len())
+    let max_name_len = tree_names.iter()
+        .map(|n| n.chars().count())
         .max()
         .unwrap_or(20)
         .min(30); // Cap at 30 chars
@@ -175,6 +280,7 @@ fn render_project_list(frame: &mut Frame, app: &mut App, area: Rect) {
         .max(30) as usize; // Minimum 30 chars for description
 
     let mut items: Vec<ListItem> = Vec::new();
+    let mut row_projects: Vec<Option<usize>> = Vec::new(); // Project index for each visual row
     let mut project_index = 0;
     let mut visual_items_before_scroll = 0usize;
 
@@ -193,6 +299,7 @@ fn render_project_list(frame: &mut Frame, app: &mut App, area: Rect) {
 
     for (group_name, projects) in &grouped_projects {
         // Add group header
+        row_projects.push(None);
         items.push(ListItem::new(Line::from(vec![
             Span::styled(
                 format!("▼ {}", group_name),
@@ -210,8 +317,7 @@ fn render_project_list(frame: &mut Frame, app: &mut App, area: Rect) {
             };
 
             // Format description to fixed width
-            let desc_raw = project.description.as_ref()
-                .map(|d| d.as_str())
+            let desc_raw = project.description.as_deref()
                 .unwrap_or("(no description)");
 
             // Truncate or pad description to exact width
@@ -224,9 +330,9 @@ fn render_project_list(frame: &mut Frame, app: &mut App, area: Rect) {
             let status_color = app.config.get_state_color(&project.status);
 
             // Format project name to fixed width
-            let name_formatted = format!("{:width$}", project.name, width = max_name_len);
+            let name_formatted = format!("{:width$}", tree_names[project_index], width = max_name_len);
 
-            items.push(ListItem::new(Line::from(vec![
+            let mut spans = vec![
                 Span::raw("  "),
                 Span::styled(format!("• {}", name_formatted), text_style.add_modifier(Modifier::BOLD)),
                 Span::raw(" - "),
@@ -236,7 +342,19 @@ fn render_project_list(frame: &mut Frame, app: &mut App, area: Rect) {
                     format!("[{}]", project.status),
                     Style::default().fg(status_color),
                 ),
-            ])));
+            ];
+            spans.extend(activity_spans(app.project_stats.get(&project.name), today));
+            if project.archived {
+                spans.push(Span::styled(" (archived)", Style::default().fg(Color::DarkGray)));
+            }
+            if let Some(date) = project.next_deadline() {
+                spans.push(Span::styled(
+                    format!(" ⏰ {}", date.format("%Y-%m-%d")),
+                    Style::default().fg(if date < today { Color::Red } else { Color::DarkGray }),
+                ));
+            }
+            items.push(ListItem::new(Line::from(spans)));
+            row_projects.push(Some(project_index));
             project_index += 1;
         }
     }
@@ -253,4 +371,97 @@ fn render_project_list(frame: &mut Frame, app: &mut App, area: Rect) {
     }
 
     frame.render_stateful_widget(list, area, &mut state);
+
+    // Group headers aren't clickable
+    let inner = area.inner(ratatui::layout::Margin { vertical: 1, horizontal: 1 });
+    for (row, project) in row_projects.iter().skip(state.offset()).take(inner.height as usize).enumerate() {
+        if let Some(idx) = project {
+            app.register_click(
+                Rect::new(inner.x, inner.y + row as u16, inner.width, 1),
+                ClickTarget::Row(ClickList::Projects, *idx),
+            );
+        }
+    }
+}
+
+/// Todo counts, last log date with days since, and a weekly activity sparkline
+fn activity_spans(stats: Option<&ProjectStats>, today: chrono::NaiveDate) -> Vec<Span<'static>> {
+    const BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+    let stats = stats.cloned().unwrap_or_default();
+    let last_log = match stats.last_log {
+        Some(last) => {
+            let days = (today - last.date_naive()).num_days();
+            let color = match days {
+                0..=7 => Color::Green,
+                8..=30 => Color::Yellow,
+                _ => Color::Red,
+            };
+            Span::styled(format!(" {} ({:>3}d)", last.format("%Y-%m-%d"), days), Style::default().fg(color))
+        }
+        None => Span::styled(format!(" {:17}", "no logs"), Style::default().fg(Color::DarkGray)),
+    };
+
+    let peak = stats.weekly_logs.iter().copied().max().unwrap_or(0).max(1);
+    let sparkline: String = stats
+        .weekly_logs
+        .iter()
+        .map(|&count| if count == 0 { ' ' } else { BARS[(count * (BARS.len() - 1)).div_ceil(peak)] })
+        .collect();
+
+    vec![
+        Span::styled(format!(" ○{:<3}", stats.open_todos), Style::default().fg(Color::Yellow)),
+        Span::styled(format!(" ✓{:<3}", stats.completed_todos), Style::default().fg(Color::Green)),
+        last_log,
+        Span::styled(format!(" {}", sparkline), Style::default().fg(Color::Cyan)),
+    ]
+}
+
+fn render_status_filter_popup(frame: &mut Frame, app: &App, area: Rect) {
+    let popup_width = 50u16;
+    let popup_height = 15u16.min(area.height.saturating_sub(4));
+    let popup_x = (area.width.saturating_sub(popup_width)) / 2 + area.x;
+    let popup_y = (area.height.saturating_sub(popup_height)) / 2 + area.y;
+    let popup_area = Rect::new(popup_x, popup_y, popup_width, popup_height);
+
+    frame.render_widget(Clear, popup_area);
+
+    let states = app.config.allowed_state_names();
+    let items: Vec<ListItem> = states
+        .iter()
+        .enumerate()
+        .map(|(i, state)| {
+            let selected = app.project_filter.shows_status(state, &app.config);
+            let checkbox = if selected { "[x]" } else { "[ ]" };
+            let style = if i == app.project_filter_status_selected {
+                Style::default().bg(Color::DarkGray).fg(Color::White)
+            } else {
+                Style::default().fg(app.config.get_state_color(state))
+            };
+            let terminal = if app.config.is_terminal_state(state) { " (terminal)" } else { "" };
+            ListItem::new(Line::from(vec![
+                Span::styled(checkbox, if selected { Style::default().fg(Color::Green) } else { Style::default() }),
+                Span::raw(" "),
+                Span::styled(state.as_str(), style),
+                Span::styled(terminal, Style::default().fg(Color::DarkGray)),
+            ]))
+        })
+        .collect();
+
+    let mut state = ListState::default();
+    state.select(Some(app.project_filter_status_selected));
+
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title("Statuses (↑↓ navigate, x toggle, r reset, ESC close)")
+                .border_style(Style::default().fg(Color::Yellow)),
+        )
+        .highlight_style(Style::default().add_modifier(Modifier::BOLD));
+
+    frame.render_stateful_widget(list, popup_area, &mut state);
+    app.register_click(area, ClickTarget::Blocker);
+    let inner = popup_area.inner(ratatui::layout::Margin { vertical: 1, horizontal: 1 });
+    app.register_list_rows(inner, ClickList::ProjectFilterStatuses, state.offset(), states.len());
 }
@@ -0,0 +1,215 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph},
+    Frame,
+};
+
+use super::app::{App, ClickList, ClickTarget, SettingsSection};
+use crate::models::Config;
+
+pub fn render(frame: &mut Frame, app: &App, area: Rect) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints([
+            Constraint::Length(3), // Title
+            Constraint::Min(5),    // States and groups
+            Constraint::Length(3), // Help bar
+        ])
+        .split(area);
+
+    let title = Paragraph::new("Settings")
+        .style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD))
+        .block(Block::default().borders(Borders::ALL));
+    frame.render_widget(title, chunks[0]);
+
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(chunks[1]);
+    render_list(frame, app, columns[0], SettingsSection::States);
+    render_list(frame, app, columns[1], SettingsSection::Groups);
+
+    // Help bar
+    let mut help_text = vec![
+        Span::styled("Tab", Style::default().fg(Color::Yellow)),
+        Span::raw(" Switch list  "),
+        Span::styled("a", Style::default().fg(Color::Yellow)),
+        Span::raw(" Add  "),
+        Span::styled("Enter/r", Style::default().fg(Color::Yellow)),
+        Span::raw(" Rename  "),
+        Span::styled("d", Style::default().fg(Color::Yellow)),
+        Span::raw(" Remove  "),
+    ];
+    if app.settings_section == SettingsSection::States {
+        help_text.push(Span::styled("←→", Style::default().fg(Color::Yellow)));
+        help_text.push(Span::raw(" Colour  "));
+    }
+    help_text.extend([
+        Span::styled("Ctrl+S", Style::default().fg(Color::Yellow)),
+        Span::raw(" Save  "),
+        Span::styled("ESC", Style::default().fg(Color::Yellow)),
+        Span::raw(" Cancel"),
+    ]);
+    let help = Paragraph::new(Line::from(help_text))
+        .block(Block::default().borders(Borders::ALL));
+    frame.render_widget(help, chunks[2]);
+
+    if app.settings_input.is_some() {
+        render_input(frame, app, area);
+    } else if app.settings_rename_confirm.is_some() {
+        render_rename_confirm(frame, app, area);
+    }
+}
+
+/// The states or groups of the edited config, with how many projects use each
+fn render_list(frame: &mut Frame, app: &App, area: Rect, section: SettingsSection) {
+    let focused = app.settings_section == section;
+    let names = app.settings_items(section);
+    let selected = match section {
+        SettingsSection::States => app.settings_state_selected,
+        SettingsSection::Groups => app.settings_group_selected,
+    };
+    let name_width = names.iter().map(|n| n.chars().count()).max().unwrap_or(0);
+
+    let items: Vec<ListItem> = names
+        .iter()
+        .enumerate()
+        .map(|(i, name)| {
+            let style = if focused && i == selected {
+                Style::default().bg(Color::DarkGray).fg(Color::White)
+            } else {
+                Style::default()
+            };
+            let mut spans = Vec::new();
+            if section == SettingsSection::States {
+                let color = app.settings_config.projects.allowed_states.get(name).map(|s| s.color.clone()).unwrap_or_default();
+                spans.push(Span::styled("● ", style.fg(Config::parse_color(&color))));
+                spans.push(Span::styled(format!("{:width$}  ", name, width = name_width), style.add_modifier(Modifier::BOLD)));
+                spans.push(Span::styled(format!("{:12}", color), style.fg(Color::Gray)));
+                let terminal = if app.settings_config.is_terminal_state(name) { "terminal" } else { "" };
+                spans.push(Span::styled(format!("{:10}", terminal), style.fg(Color::DarkGray)));
+            } else {
+                spans.push(Span::styled(format!("{:width$}  ", name, width = name_width), style.add_modifier(Modifier::BOLD)));
+            }
+            let used = app.settings_usage(section, name);
+            spans.push(Span::styled(
+                format!("{} project{}", used, if used == 1 { "" } else { "s" }),
+                style.fg(Color::DarkGray),
+            ));
+            ListItem::new(Line::from(spans))
+        })
+        .collect();
+
+    let title = match section {
+        SettingsSection::States => "Project states",
+        SettingsSection::Groups => "Project groups",
+    };
+    let border_style = if focused {
+        Style::default().fg(Color::Yellow)
+    } else {
+        Style::default()
+    };
+    let list = if items.is_empty() {
+        List::new(vec![ListItem::new(Span::styled("(none, press a to add)", Style::default().fg(Color::DarkGray)))])
+    } else {
+        List::new(items)
+    };
+
+    let mut state = ListState::default();
+    if focused && !names.is_empty() {
+        state.select(Some(selected));
+    }
+    frame.render_stateful_widget(
+        list.block(Block::default().borders(Borders::ALL).title(title).border_style(border_style)),
+        area,
+        &mut state,
+    );
+
+    let inner = area.inner(ratatui::layout::Margin { vertical: 1, horizontal: 1 });
+    let click_list = match section {
+        SettingsSection::States => ClickList::SettingsStates,
+        SettingsSection::Groups => ClickList::SettingsGroups,
+    };
+    app.register_list_rows(inner, click_list, state.offset(), names.len());
+}
+
+/// Popup for typing the name of a new or renamed state/group
+fn render_input(frame: &mut Frame, app: &App, area: Rect) {
+    let input = app.settings_input.as_deref().unwrap_or("");
+    let kind = match app.settings_section {
+        SettingsSection::States => "state",
+        SettingsSection::Groups => "group",
+    };
+    let title = match app.settings_input_renaming.as_deref() {
+        Some(old) => format!("Rename {} '{}' (Enter save, ESC cancel)", kind, old),
+        None => format!("New {} (Enter add, ESC cancel)", kind),
+    };
+
+    let popup_width = 60u16.min(area.width);
+    let popup_area = Rect::new(
+        area.x + (area.width.saturating_sub(popup_width)) / 2,
+        area.y + (area.height.saturating_sub(3)) / 2,
+        popup_width,
+        3,
+    );
+    frame.render_widget(Clear, popup_area);
+    let paragraph = Paragraph::new(input).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(title)
+            .border_style(Style::default().fg(Color::Yellow)),
+    );
+    frame.render_widget(paragraph, popup_area);
+    app.register_click(area, ClickTarget::Blocker);
+
+    frame.set_cursor_position((popup_area.x + 1 + input.chars().count() as u16, popup_area.y + 1));
+}
+
+/// Ask whether the projects using a renamed state/group should follow it
+fn render_rename_confirm(frame: &mut Frame, app: &App, area: Rect) {
+    let Some(rename) = app.settings_rename_confirm.as_ref() else {
+        return;
+    };
+    let kind = match rename.section {
+        SettingsSection::States => "status",
+        SettingsSection::Groups => "group",
+    };
+
+    let popup_width = 60u16.min(area.width);
+    let popup_area = Rect::new(
+        area.x + (area.width.saturating_sub(popup_width)) / 2,
+        area.y + (area.height.saturating_sub(5)) / 2,
+        popup_width,
+        5,
+    );
+    let text = vec![
+        Line::from(format!(
+            "{} {} {} '{}'.",
+            rename.projects,
+            if rename.projects == 1 { "project uses" } else { "projects use" },
+            kind,
+            rename.from
+        )),
+        Line::from(vec![
+            Span::raw("Move them to '"),
+            Span::styled(rename.to.as_str(), Style::default().add_modifier(Modifier::BOLD)),
+            Span::raw("' on save? "),
+            Span::styled("y", Style::default().fg(Color::Yellow)),
+            Span::raw("/"),
+            Span::styled("n", Style::default().fg(Color::Yellow)),
+        ]),
+    ];
+
+    frame.render_widget(Clear, popup_area);
+    let paragraph = Paragraph::new(text).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title("Update projects")
+            .border_style(Style::default().fg(Color::Yellow)),
+    );
+    frame.render_widget(paragraph, popup_area);
+    app.register_click(area, ClickTarget::Blocker);
+}
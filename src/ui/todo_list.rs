@@ -6,7 +6,8 @@ use ratatui::{
     Frame,
 };
 
-use super::app::{App, TodoFilterPanel};
+use super::app::{App, ClickList, ClickTarget, TodoFilterPanel};
+use crate::models::TodoState;
 
 pub fn render(frame: &mut Frame, app: &mut App, area: Rect) {
     let chunks = Layout::default()
@@ -47,6 +48,8 @@ pub fn render(frame: &mut Frame, app: &mut App, area: Rect) {
             Span::raw(" Projects  "),
             Span::styled("h", Style::default().fg(Color::Yellow)),
             Span::raw(" People  "),
+            Span::styled("b", Style::default().fg(Color::Yellow)),
+            Span::raw(" Board  "),
             Span::styled("ESC", Style::default().fg(Color::Yellow)),
             Span::raw(" Back"),
         ]
@@ -60,7 +63,11 @@ pub fn render(frame: &mut Frame, app: &mut App, area: Rect) {
         .block(Block::default().borders(Borders::ALL));
     frame.render_widget(help, chunks[3]);
 
-    // Render filter panel popup if active
+    render_filter_popup(frame, app, area);
+}
+
+/// The popup of the open todo filter panel, if any
+pub fn render_filter_popup(frame: &mut Frame, app: &App, area: Rect) {
     match app.todo_filter_panel {
         TodoFilterPanel::None => {}
         TodoFilterPanel::Completed => {
@@ -75,7 +82,7 @@ pub fn render(frame: &mut Frame, app: &mut App, area: Rect) {
     }
 }
 
-fn render_filters_summary(frame: &mut Frame, app: &App, area: Rect) {
+pub fn render_filters_summary(frame: &mut Frame, app: &App, area: Rect) {
     let filter_chunks = Layout::default()
         .direction(Direction::Horizontal)
         .constraints([
@@ -132,6 +139,10 @@ fn render_filters_summary(frame: &mut Frame, app: &App, area: Rect) {
         .style(people_style)
         .block(Block::default().borders(Borders::ALL).title("People"));
     frame.render_widget(people, filter_chunks[2]);
+
+    for (i, chunk) in filter_chunks.iter().enumerate() {
+        app.register_click(*chunk, ClickTarget::Row(ClickList::TodoFilterPanels, i));
+    }
 }
 
 fn render_completed_filter_popup(frame: &mut Frame, app: &App, area: Rect) {
@@ -169,6 +180,10 @@ fn render_completed_filter_popup(frame: &mut Frame, app: &App, area: Rect) {
         );
 
     frame.render_widget(list, popup_area);
+
+    app.register_click(area, ClickTarget::Blocker);
+    let inner = popup_area.inner(ratatui::layout::Margin { vertical: 1, horizontal: 1 });
+    app.register_list_rows(inner, ClickList::TodoFilterCompleted, 0, 2);
 }
 
 fn render_project_filter_popup(frame: &mut Frame, app: &App, area: Rect) {
@@ -215,6 +230,10 @@ fn render_project_filter_popup(frame: &mut Frame, app: &App, area: Rect) {
         .highlight_style(Style::default().add_modifier(Modifier::BOLD));
 
     frame.render_stateful_widget(list, popup_area, &mut state);
+
+    app.register_click(area, ClickTarget::Blocker);
+    let inner = popup_area.inner(ratatui::layout::Margin { vertical: 1, horizontal: 1 });
+    app.register_list_rows(inner, ClickList::TodoFilterProjects, state.offset(), all_projects.len());
 }
 
 fn render_people_filter_popup(frame: &mut Frame, app: &App, area: Rect) {
@@ -261,6 +280,20 @@ fn render_people_filter_popup(frame: &mut Frame, app: &App, area: Rect) {
         .highlight_style(Style::default().add_modifier(Modifier::BOLD));
 
     frame.render_stateful_widget(list, popup_area, &mut state);
+
+    app.register_click(area, ClickTarget::Blocker);
+    let inner = popup_area.inner(ratatui::layout::Margin { vertical: 1, horizontal: 1 });
+    app.register_list_rows(inner, ClickList::TodoFilterPeople, state.offset(), all_people.len());
+}
+
+/// Colour of a todo's marker by state
+pub fn marker_style(state: TodoState) -> Style {
+    match state {
+        TodoState::Open => Style::default().fg(Color::Yellow),
+        TodoState::InProgress => Style::default().fg(Color::Cyan),
+        TodoState::Waiting => Style::default().fg(Color::Magenta),
+        TodoState::Done => Style::default().fg(Color::Green),
+    }
 }
 
 fn render_todo_list(frame: &mut Frame, app: &mut App, area: Rect) {
@@ -269,8 +302,6 @@ fn render_todo_list(frame: &mut Frame, app: &mut App, area: Rect) {
         .iter()
         .enumerate()
         .map(|(i, todo)| {
-            let checkbox = if todo.completed { "[x]" } else { "[]" };
-
             let is_selected = i == app.todo_selected;
             let text_style = if is_selected {
                 if todo.completed {
@@ -289,14 +320,7 @@ fn render_todo_list(frame: &mut Frame, app: &mut App, area: Rect) {
 
             // Build spans with proper formatting
             let mut spans = vec![
-                Span::styled(
-                    checkbox,
-                    if todo.completed {
-                        Style::default().fg(Color::Green)
-                    } else {
-                        Style::default().fg(Color::Yellow)
-                    },
-                ),
+                Span::styled(todo.state.marker(), marker_style(todo.state)),
                 Span::raw(" "),
                 Span::styled(&todo.text, text_style),
             ];
@@ -331,4 +355,15 @@ fn render_todo_list(frame: &mut Frame, app: &mut App, area: Rect) {
         .highlight_style(Style::default().add_modifier(Modifier::BOLD));
 
     frame.render_stateful_widget(list, area, &mut state);
+
+    // Rows select the todo, the checkbox at the start of each row toggles it
+    let inner = area.inner(ratatui::layout::Margin { vertical: 1, horizontal: 1 });
+    app.register_list_rows(inner, ClickList::Todos, state.offset(), app.filtered_todos.len());
+    for row in 0..inner.height {
+        let idx = state.offset() + row as usize;
+        if idx >= app.filtered_todos.len() {
+            break;
+        }
+        app.register_click(Rect::new(inner.x, inner.y + row, 3, 1), ClickTarget::TodoCheckbox(idx));
+    }
 }
@@ -0,0 +1,242 @@
+use std::collections::BTreeMap;
+
+use crate::models::{project_within, Config, Person, Project};
+
+/// A form field that can fail validation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Field {
+    Name,
+    Jira,
+    Status,
+    Group,
+    Parent,
+    TargetDate,
+    Email,
+}
+
+/// Validation errors by field, in form order
+pub type FieldErrors = BTreeMap<Field, String>;
+
+/// Check that a name can be used as a #project or @person tag.
+/// Project names may use `/` to separate sub-projects.
+pub fn check_tag_name(name: &str, allow_slash: bool) -> Result<(), String> {
+    if name.is_empty() {
+        return Err("Name cannot be empty".to_string());
+    }
+    if name.contains(char::is_whitespace) {
+        return Err("Name can't contain spaces, it couldn't be tagged".to_string());
+    }
+    if let Some(c) = name
+        .chars()
+        .find(|&c| !(c.is_alphanumeric() || c == '-' || c == '_' || (allow_slash && c == '/')))
+    {
+        return Err(format!("Name can't contain '{}', use letters, digits, - or _", c));
+    }
+    if allow_slash && (name.starts_with('/') || name.ends_with('/') || name.contains("//")) {
+        return Err("Sub-project names need text on both sides of '/'".to_string());
+    }
+    Ok(())
+}
+
+/// Check that a value looks like a URL: a scheme, `://` and a host, without spaces
+pub fn check_url(value: &str) -> Result<(), String> {
+    let valid = match value.split_once("://") {
+        Some((scheme, rest)) => {
+            !scheme.is_empty()
+                && scheme.chars().all(|c| c.is_ascii_alphanumeric() || c == '+' || c == '-' || c == '.')
+                && !rest.is_empty()
+                && !rest.starts_with('/')
+                && !value.contains(char::is_whitespace)
+        }
+        None => false,
+    };
+    if valid {
+        Ok(())
+    } else {
+        Err("Must be a URL like https://example.com".to_string())
+    }
+}
+
+/// Check that a value looks like an email address: `user@domain.tld` without spaces
+pub fn check_email(value: &str) -> Result<(), String> {
+    let valid = match value.split_once('@') {
+        Some((user, domain)) => {
+            !user.is_empty()
+                && !domain.contains('@')
+                && domain.contains('.')
+                && !domain.starts_with('.')
+                && !domain.ends_with('.')
+                && !value.contains(char::is_whitespace)
+        }
+        None => false,
+    };
+    if valid {
+        Ok(())
+    } else {
+        Err("Must be an email address like name@example.com".to_string())
+    }
+}
+
+/// Validate a project about to be saved. `editing` is its index in `projects`
+/// when it already exists, so it isn't counted as its own duplicate.
+///
+/// Unchanged values are kept even if they wouldn't pass as new ones, so data saved
+/// before a rule or a config.yml change never blocks editing something else.
+pub fn validate_project(project: &Project, projects: &[Project], editing: Option<usize>, config: &Config) -> FieldErrors {
+    let mut errors = FieldErrors::new();
+    let existing = editing.and_then(|idx| projects.get(idx));
+
+    if existing.is_none_or(|p| p.name != project.name) {
+        if let Err(error) = check_tag_name(&project.name, true) {
+            errors.insert(Field::Name, error);
+        } else if projects
+            .iter()
+            .enumerate()
+            .any(|(i, p)| Some(i) != editing && p.name == project.name)
+        {
+            errors.insert(Field::Name, format!("Project '{}' already exists", project.name));
+        }
+    }
+
+    if let Some(jira) = project.jira.as_deref() {
+        let unchanged = existing.is_some_and(|p| p.jira.as_deref() == Some(jira));
+        if let (false, Err(error)) = (unchanged, check_url(jira)) {
+            errors.insert(Field::Jira, error);
+        }
+    }
+
+    // Unchanged values are kept even if config.yml no longer lists them
+    let old_status = existing.map(|p| p.status.as_str());
+    if old_status != Some(project.status.as_str()) {
+        let allowed = match old_status {
+            Some(old) => config.next_states(old),
+            None => config.allowed_state_names(),
+        };
+        if !allowed.contains(&project.status) {
+            let error = match old_status {
+                Some(old) => format!("Can't move from '{}' to '{}'", old, project.status),
+                None => format!("Unknown status '{}'", project.status),
+            };
+            errors.insert(Field::Status, error);
+        }
+    }
+
+    let old_group = existing.map(|p| p.group.as_str());
+    if !project.group.is_empty()
+        && old_group != Some(project.group.as_str())
+        && !config.allowed_groups().contains(&project.group)
+    {
+        errors.insert(Field::Group, format!("Unknown group '{}'", project.group));
+    }
+
+    if let Some(parent) = project.parent.as_deref() {
+        let error = if parent == project.name {
+            Some("A project can't be its own parent".to_string())
+        } else if !projects.iter().any(|p| p.name == parent) {
+            Some(format!("Parent project '{}' doesn't exist", parent))
+        } else {
+            // The new parent must not be one of this project's descendants
+            existing
+                .filter(|p| project_within(projects, parent, &p.name))
+                .map(|p| format!("'{}' is a sub-project of '{}'", parent, p.name))
+        };
+        if let Some(error) = error {
+            errors.insert(Field::Parent, error);
+        }
+    }
+
+    errors
+}
+
+/// Validate a person about to be saved, see `validate_project`
+pub fn validate_person(person: &Person, people: &[Person], editing: Option<usize>) -> FieldErrors {
+    let mut errors = FieldErrors::new();
+    let existing = editing.and_then(|idx| people.get(idx));
+
+    if existing.is_none_or(|p| p.name != person.name) {
+        if let Err(error) = check_tag_name(&person.name, false) {
+            errors.insert(Field::Name, error);
+        } else if people
+            .iter()
+            .enumerate()
+            .any(|(i, p)| Some(i) != editing && p.name == person.name)
+        {
+            errors.insert(Field::Name, format!("Person '{}' already exists", person.name));
+        }
+    }
+
+    if let Some(email) = person.email.as_deref() {
+        let unchanged = existing.is_some_and(|p| p.email.as_deref() == Some(email));
+        if let (false, Err(error)) = (unchanged, check_email(email)) {
+            errors.insert(Field::Email, error);
+        }
+    }
+
+    errors
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tag_names() {
+        assert!(check_tag_name("alpha", false).is_ok());
+        assert!(check_tag_name("team-a_2", false).is_ok());
+        assert!(check_tag_name("web/frontend", true).is_ok());
+
+        assert!(check_tag_name("", false).is_err());
+        assert!(check_tag_name("two words", false).is_err());
+        assert!(check_tag_name("a.b", false).is_err());
+        assert!(check_tag_name("web/frontend", false).is_err());
+        assert!(check_tag_name("/web", true).is_err());
+        assert!(check_tag_name("web/", true).is_err());
+        assert!(check_tag_name("web//frontend", true).is_err());
+    }
+
+    #[test]
+    fn urls() {
+        assert!(check_url("https://example.com").is_ok());
+        assert!(check_url("https://jira.example.com/browse/ABC-1").is_ok());
+        assert!(check_url("git+ssh://host/repo").is_ok());
+
+        assert!(check_url("").is_err());
+        assert!(check_url("example.com").is_err());
+        assert!(check_url("https://").is_err());
+        assert!(check_url("file:///tmp/x").is_err());
+        assert!(check_url("://example.com").is_err());
+        assert!(check_url("https://example.com/a b").is_err());
+    }
+
+    #[test]
+    fn emails() {
+        assert!(check_email("name@example.com").is_ok());
+        assert!(check_email("first.last@mail.example.org").is_ok());
+
+        assert!(check_email("").is_err());
+        assert!(check_email("name").is_err());
+        assert!(check_email("@example.com").is_err());
+        assert!(check_email("name@example").is_err());
+        assert!(check_email("name@.example.com").is_err());
+        assert!(check_email("name@example.com.").is_err());
+        assert!(check_email("a@b@example.com").is_err());
+        assert!(check_email("na me@example.com").is_err());
+    }
+
+    #[test]
+    fn unchanged_values_are_kept() {
+        let mut person = Person::example();
+        person.name = "old name".to_string();
+        person.email = Some("not-an-email".to_string());
+        let people = vec![person.clone()];
+
+        assert!(validate_person(&person, &people, Some(0)).is_empty());
+        assert!(validate_person(&person, &people, None).contains_key(&Field::Name));
+
+        let mut edited = person.clone();
+        edited.email = Some("still-not-an-email".to_string());
+        let errors = validate_person(&edited, &people, Some(0));
+        assert!(errors.contains_key(&Field::Email));
+        assert!(!errors.contains_key(&Field::Name));
+    }
+}
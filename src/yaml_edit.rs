@@ -0,0 +1,349 @@
+//! Format-preserving updates of YAML files
+//!
+//! `projects.yml` and `people.yml` are lists of mappings keyed by `name`. Instead of
+//! re-serialising the whole list, `update_records` splices the new values into the
+//! existing text: unchanged records are copied verbatim, and in a changed record only
+//! the keys whose values changed are rewritten. Comments, blank lines and key order
+//! survive everywhere else. `update_mapping_file` does the same for a file holding a
+//! single mapping such as `config.yml`, descending into nested mappings.
+
+use serde_yaml::{Mapping, Value};
+
+/// A top-level list item with the blank and comment lines just before it
+struct Item {
+    lead: Vec<String>,
+    lines: Vec<String>, // Starts with the `- ` line
+}
+
+/// The text of a list file split around its items
+struct Document {
+    header: Vec<String>,
+    items: Vec<Item>,
+    footer: Vec<String>,
+}
+
+/// One key of a mapping, with the comment lines just before it
+struct Segment {
+    key: Option<Value>, // None for comments before the first key
+    lead: Vec<String>,
+    lines: Vec<String>, // Starting at the mapping's indent
+}
+
+/// Splice `records` (a sequence of mappings) into the existing file text.
+///
+/// `normalize` turns a record read from the file into the form it would be saved in,
+/// so records that only differ in defaults count as unchanged. Returns None if the
+/// file doesn't look like a plain list of records; the caller then rewrites it whole.
+pub fn update_records(old: &str, records: &Value, key: &str, normalize: impl Fn(&Value) -> Option<Value>) -> Option<String> {
+    let records = records.as_sequence()?;
+    let doc = split_document(old)?;
+
+    // What each existing item holds, normalized for comparison
+    let old_values: Vec<Option<Value>> = doc
+        .items
+        .iter()
+        .map(|item| {
+            let parsed: Vec<Value> = serde_yaml::from_str(&item.lines.join("\n")).ok()?;
+            match parsed.as_slice() {
+                [value] => normalize(value),
+                _ => None,
+            }
+        })
+        .collect();
+    let name_of = |value: &Value| value.get(key).and_then(Value::as_str).map(str::to_string);
+    let new_names: Vec<Option<String>> = records.iter().map(name_of).collect();
+
+    let mut used = vec![false; doc.items.len()];
+    let mut out = doc.header.clone();
+    for (i, record) in records.iter().enumerate() {
+        // The item with the same name, or the one in the same place if a record was renamed
+        let matched = (0..doc.items.len())
+            .find(|&j| !used[j] && new_names[i].is_some() && old_values[j].as_ref().and_then(name_of) == new_names[i])
+            .or_else(|| {
+                let old_name = old_values.get(i)?.as_ref().and_then(name_of);
+                (records.len() == doc.items.len() && !used[i] && !new_names.contains(&old_name)).then_some(i)
+            });
+
+        match matched {
+            Some(j) => {
+                used[j] = true;
+                let item = &doc.items[j];
+                out.extend(item.lead.iter().cloned());
+                let old_value = old_values[j].as_ref();
+                if old_value == Some(record) {
+                    out.extend(item.lines.iter().cloned());
+                } else {
+                    match old_value.and_then(|old_value| update_item(&item.lines, old_value, record)) {
+                        Some(lines) => out.extend(lines),
+                        None => out.extend(serialize_item(record)?),
+                    }
+                }
+            }
+            None => out.extend(serialize_item(record)?),
+        }
+    }
+    out.extend(doc.footer);
+
+    let mut text = out.join("\n");
+    text.push('\n');
+    Some(text)
+}
+
+/// Splice `new` (a mapping) into the existing text of a file holding one mapping.
+///
+/// Unchanged keys are copied verbatim and nested block mappings are updated key by key.
+/// `normalize` works as in `update_records`. Returns None if the file can't be parsed
+/// or its layout isn't understood.
+pub fn update_mapping_file(old: &str, new: &Value, normalize: impl Fn(&Value) -> Option<Value>) -> Option<String> {
+    let new = new.as_mapping()?;
+    let old_value = normalize(&serde_yaml::from_str(old).ok()?)?;
+    let old_value = old_value.as_mapping()?;
+    let lines: Vec<String> = old.lines().map(str::to_string).collect();
+
+    let mut text = update_mapping(&lines, 0, old_value, new)?.join("\n");
+    text.push('\n');
+    Some(text)
+}
+
+/// Split the file into header, items and footer. Comments and blank lines between
+/// items belong to the item after them.
+fn split_document(text: &str) -> Option<Document> {
+    let mut doc = Document { header: Vec::new(), items: Vec::new(), footer: Vec::new() };
+    let mut pending: Vec<String> = Vec::new();
+
+    for line in text.lines() {
+        if line == "-" || line.starts_with("- ") {
+            doc.items.push(Item { lead: std::mem::take(&mut pending), lines: vec![line.to_string()] });
+        } else if doc.items.is_empty() {
+            // Anything but comments before the first item means this isn't a plain list
+            if !(line.trim().is_empty() || line.starts_with('#')) {
+                return None;
+            }
+            doc.header.push(line.to_string());
+        } else if line.trim().is_empty() || line.starts_with('#') {
+            pending.push(line.to_string());
+        } else if line.starts_with(' ') {
+            let item = doc.items.last_mut()?;
+            item.lines.append(&mut pending);
+            item.lines.push(line.to_string());
+        } else {
+            return None;
+        }
+    }
+    doc.footer = pending;
+    Some(doc)
+}
+
+/// Rewrite only the changed keys of an item, or None if its layout isn't understood.
+/// `old` is the normalized record the item holds now.
+fn update_item(lines: &[String], old: &Value, record: &Value) -> Option<Vec<String>> {
+    let record = record.as_mapping()?;
+    let old = old.as_mapping()?;
+    let first = lines.first()?;
+    let dash_alone = first == "-";
+
+    // Indent the first key like the others so every key sits at two spaces
+    let mut body: Vec<String> = Vec::new();
+    if !dash_alone {
+        body.push(format!("  {}", first.strip_prefix("- ")?));
+    }
+    body.extend(lines[1..].iter().cloned());
+
+    let mut out: Vec<String> = Vec::new();
+    if dash_alone {
+        out.push("-".to_string());
+    }
+    out.extend(update_mapping(&body, 2, old, record)?);
+
+    // Put the dash back on the first key
+    if !dash_alone {
+        let first_key = out.iter().position(|l| is_key_line(l, 2))?;
+        out[first_key] = format!("- {}", &out[first_key][2..]);
+    }
+    Some(out)
+}
+
+/// Rewrite only the changed keys of a block mapping whose keys sit at `indent`,
+/// or None if its layout isn't understood. `old` is what the lines hold now.
+fn update_mapping(lines: &[String], indent: usize, old: &Mapping, new: &Mapping) -> Option<Vec<String>> {
+    let mut segments = split_segments(lines, indent)?;
+    for segment in segments.iter_mut() {
+        let Some(key) = segment.key.clone() else {
+            continue;
+        };
+        match new.get(&key) {
+            Some(value) if old.get(&key) == Some(value) => {}
+            Some(value) if !value.is_null() => {
+                segment.lines = update_value(&segment.lines, indent, &key, old.get(&key), value)?;
+            }
+            // Keys that were removed or cleared
+            _ => {
+                segment.lead.clear();
+                segment.lines.clear();
+            }
+        }
+    }
+
+    // New keys go at the end, leaving out unset ones
+    for (key, value) in new {
+        if value.is_null() || old.get(key) == Some(value) || segments.iter().any(|s| s.key.as_ref() == Some(key)) {
+            continue;
+        }
+        segments.push(Segment { key: Some(key.clone()), lead: Vec::new(), lines: serialize_key(key, value, indent)? });
+    }
+
+    Some(segments.into_iter().flat_map(|s| s.lead.into_iter().chain(s.lines)).collect())
+}
+
+/// The new lines of a changed key: a mapping written as a block below `key:` is
+/// updated in place, anything else is rewritten
+fn update_value(lines: &[String], indent: usize, key: &Value, old: Option<&Value>, value: &Value) -> Option<Vec<String>> {
+    if let (Some(Value::Mapping(old)), Value::Mapping(new)) = (old, value) {
+        let head = parse_lines(&lines[..1], indent)?;
+        let child_indent = lines[1..]
+            .iter()
+            .find(|l| !(l.trim().is_empty() || l.trim_start().starts_with('#')))
+            .map(|l| l.len() - l.trim_start().len())
+            .filter(|&i| i > indent);
+        if let (true, false, Some(child_indent)) = (head.get(key).is_some_and(Value::is_null), new.is_empty(), child_indent) {
+            if let Some(body) = update_mapping(&lines[1..], child_indent, old, new) {
+                return Some(std::iter::once(lines[0].clone()).chain(body).collect());
+            }
+        }
+    }
+    serialize_key(key, value, indent)
+}
+
+/// Whether a line starts a key of a mapping indented by `indent` spaces
+fn is_key_line(line: &str, indent: usize) -> bool {
+    line.len() > indent
+        && line.as_bytes()[..indent].iter().all(|&b| b == b' ')
+        && !line[indent..].starts_with([' ', '#', '-'])
+}
+
+/// Split the lines of a mapping into one segment per key
+fn split_segments(body: &[String], indent: usize) -> Option<Vec<Segment>> {
+    let mut segments = vec![Segment { key: None, lead: Vec::new(), lines: Vec::new() }];
+    let mut pending: Vec<String> = Vec::new();
+
+    for line in body {
+        if is_key_line(line, indent) {
+            segments.push(Segment { key: None, lead: std::mem::take(&mut pending), lines: vec![line.clone()] });
+        } else if line.trim().is_empty() || line.trim_start().starts_with('#') {
+            pending.push(line.clone());
+        } else {
+            let segment = segments.last_mut()?;
+            if segment.lines.is_empty() {
+                return None; // Content before the first key
+            }
+            segment.lines.append(&mut pending);
+            segment.lines.push(line.clone());
+        }
+    }
+    segments.last_mut()?.lines.append(&mut pending);
+
+    for segment in segments.iter_mut().skip(1) {
+        let mapping = parse_lines(&segment.lines, indent)?;
+        segment.key = Some(mapping.keys().next()?.clone());
+    }
+    Some(segments)
+}
+
+/// Parse the lines of a single key, indented by `indent` spaces
+fn parse_lines(lines: &[String], indent: usize) -> Option<Mapping> {
+    let prefix = " ".repeat(indent);
+    let text: Vec<&str> = lines.iter().map(|l| l.strip_prefix(prefix.as_str()).unwrap_or(l)).collect();
+    let mapping: Mapping = serde_yaml::from_str(&text.join("\n")).ok()?;
+    (mapping.len() == 1).then_some(mapping)
+}
+
+/// A single `key: value` entry, indented by `indent` spaces
+fn serialize_key(key: &Value, value: &Value, indent: usize) -> Option<Vec<String>> {
+    let mut mapping = Mapping::new();
+    mapping.insert(key.clone(), value.clone());
+    let text = serde_yaml::to_string(&mapping).ok()?;
+    let prefix = " ".repeat(indent);
+    Some(text.lines().map(|l| if l.is_empty() { String::new() } else { format!("{}{}", prefix, l) }).collect())
+}
+
+/// A whole record as a new list item
+fn serialize_item(record: &Value) -> Option<Vec<String>> {
+    let text = serde_yaml::to_string(&vec![record]).ok()?;
+    Some(text.lines().map(str::to_string).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PEOPLE: &str = "\
+# Team
+- name: alice
+  # Works remotely
+  email: alice@example.com
+  company: acme
+
+# Contractors
+- name: bob
+  email: bob@example.com
+";
+
+    fn update(old: &str, new: &str) -> Option<String> {
+        let new: Value = serde_yaml::from_str(new).unwrap();
+        update_records(old, &new, "name", |v| Some(v.clone()))
+    }
+
+    #[test]
+    fn changed_key_keeps_comments() {
+        let new = "[{name: alice, email: alice@example.com, company: initech}, {name: bob, email: bob@example.com}]";
+        assert_eq!(
+            update(PEOPLE, new).unwrap(),
+            PEOPLE.replace("company: acme", "company: initech")
+        );
+    }
+
+    #[test]
+    fn renamed_record_stays_in_place() {
+        let new = "[{name: alicia, email: alice@example.com, company: acme}, {name: bob, email: bob@example.com}]";
+        assert_eq!(update(PEOPLE, new).unwrap(), PEOPLE.replace("name: alice", "name: alicia"));
+    }
+
+    #[test]
+    fn deleted_record_and_key() {
+        let new = "[{name: bob}]";
+        assert_eq!(update(PEOPLE, new).unwrap(), "# Team\n\n# Contractors\n- name: bob\n");
+    }
+
+    #[test]
+    fn appended_record() {
+        let new = "[{name: alice, email: alice@example.com, company: acme}, {name: bob, email: bob@example.com}, {name: carol}]";
+        assert_eq!(update(PEOPLE, new).unwrap(), format!("{}- name: carol\n", PEOPLE));
+    }
+
+    #[test]
+    fn non_list_file_falls_back() {
+        let new = "[{name: alice}]";
+        assert_eq!(update("people:\n- name: alice\n", new), None);
+        assert_eq!(update("name: alice\n", new), None);
+    }
+
+    #[test]
+    fn nested_mapping_keeps_comments() {
+        let old = "\
+# Settings
+projects:
+  allowed_states:
+    open: blue  # default
+    closed: red
+  groups: []
+
+attachments:
+  dedupe: false
+";
+        let new: Value = serde_yaml::from_str("{projects: {allowed_states: {open: blue, closed: green}, groups: []}, attachments: {dedupe: false}}").unwrap();
+        assert_eq!(
+            update_mapping_file(old, &new, |v| Some(v.clone())).unwrap(),
+            old.replace("closed: red", "closed: green")
+        );
+    }
+}